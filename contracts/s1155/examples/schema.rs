@@ -3,7 +3,8 @@ use cw1155::{
     ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse, TokenInfoResponse,
     TokensResponse,
 };
-use s1155::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use s1155::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, VoucherSignerResponse};
+use s_uri::UriPolicy;
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
@@ -23,4 +24,6 @@ fn main() {
     export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(BatchBalanceResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
+    export_schema(&schema_for!(UriPolicy), &out_dir);
+    export_schema(&schema_for!(VoucherSignerResponse), &out_dir);
 }
@@ -1,21 +1,24 @@
-#[cfg(not(feature = "library"))]
 use crate::error::ContractError;
-use crate::event::{Event, TransferEvent};
+use crate::event::{ToEvent, TransferEvent};
 use crate::msg::{
     BatchReceiveMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, TokenUri,
+    VoucherSignerResponse,
 };
-use crate::state::ROYALTY;
-use cosmwasm_std::{attr, entry_point, to_binary, Addr, Binary, Coin, Deps, Uint128};
+use crate::state::{BASE_URI, REDEEMED_VOUCHERS, ROYALTY, URI_POLICY, VOUCHER_SIGNER};
+use crate::voucher::MintVoucher;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{attr, coins, to_binary, Addr, BankMsg, Binary, Coin, Deps, Uint128};
 use cosmwasm_std::{DepsMut, Env, MessageInfo, StdResult};
-use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg, TokenId};
+use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg, TokenId, TokenInfoResponse};
 use cw1155_base::contract::{execute as base_execute, query as base_query};
 use cw1155_base::state::{APPROVES, BALANCES, MINTER, TOKENS};
 use cw1155_base::ContractError as BaseError;
 use cw2::set_contract_version;
-use s1::{check_royalty_payment, OWNER_PERCENT, ROYALTY_FEE};
+use cw_utils::must_pay;
+use s1::{check_royalty_payment, FeeSchedule, OWNER_PERCENT, ROYALTY_FEE, ROYALTY_FEE_SCHEDULE};
 use s2::{check_payment, MIN_FEE};
-use s_std::{Response, SubMsg, MULTISIG, NATIVE_DENOM};
-use url::Url;
+use s_std::{error::FeeError, Response, SubMsg, MULTISIG, NATIVE_DENOM};
 
 // Version info for migration info
 const CONTRACT_NAME: &str = "crates.io:s1155";
@@ -27,11 +30,21 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     MINTER.save(deps.storage, &info.sender)?;
     ROYALTY.save(deps.storage, &deps.api.addr_validate(&msg.royalty_address)?)?;
 
+    let uri_policy = msg.uri_policy.unwrap_or_default();
+    if let Some(base_uri) = msg.base_uri {
+        if !base_uri.contains("{id}") {
+            return Err(ContractError::InvalidBaseUri {});
+        }
+        uri_policy.validate(&base_uri)?;
+        BASE_URI.save(deps.storage, &base_uri)?;
+    }
+    URI_POLICY.save(deps.storage, &uri_policy)?;
+
     Ok(Response::default()
         .add_attribute("action", "instantiate")
         .add_attribute("contract_name", CONTRACT_NAME)
@@ -77,6 +90,16 @@ pub fn execute(
             msg,
         } => execute_mint(env, to, token_id, value, token_uri, msg),
         ExecuteMsg::BatchMint { to, batch, msg } => execute_batch_mint(env, to, batch, msg),
+        ExecuteMsg::Burn {
+            from,
+            token_id,
+            value,
+        } => execute_burn(env, from, token_id, value),
+        ExecuteMsg::BatchBurn { from, batch } => execute_batch_burn(env, from, batch),
+        ExecuteMsg::SetVoucherSigner { pubkey } => execute_set_voucher_signer(env, pubkey),
+        ExecuteMsg::RedeemVoucher { voucher, signature } => {
+            execute_redeem_voucher(env, voucher, signature)
+        }
         _ => {
             let result = base_execute(env.deps, env.env, env.info, Cw1155ExecuteMsg::from(msg));
             match result {
@@ -119,7 +142,7 @@ pub fn execute_send_from(
 
     guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
 
-    let mut rsp = Response::default();
+    let mut rsp = Response::default().add_attribute("action", "send_from");
 
     let event = execute_transfer_inner(
         &mut deps,
@@ -128,7 +151,7 @@ pub fn execute_send_from(
         &token_id,
         amount,
     )?;
-    event.add_attributes(&mut rsp, "transfer");
+    rsp.events.push(event.to_event(info.sender.as_ref(), false));
     rsp.attributes.push(attr(
         "royalty_fee",
         Coin::new(ROYALTY_FEE, NATIVE_DENOM).to_string(),
@@ -171,15 +194,13 @@ pub fn execute_batch_send_from(
     let from_addr = deps.api.addr_validate(&from)?;
     let to_addr = deps.api.addr_validate(&to)?;
     let royalty = ROYALTY.load(deps.storage)?;
-    // ROYALTY_FEE * Number of Tokens
-    let fee = Uint128::from(u128::try_from(batch.len()).unwrap())
-        .checked_mul(Uint128::from(ROYALTY_FEE))
-        .unwrap();
-    let mut msgs = check_royalty_payment(&info, fee.u128(), royalty.clone())?;
+    // Royalty fee for number of tokens, discounted for larger batches
+    let fee = ROYALTY_FEE_SCHEDULE.fee(batch.len())?;
+    let mut msgs = check_royalty_payment(&info, fee, royalty.clone())?;
 
     guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
 
-    let mut rsp = Response::default();
+    let mut rsp = Response::default().add_attribute("action", "batch_send_from");
     for (token_id, amount) in batch.iter() {
         let event = execute_transfer_inner(
             &mut deps,
@@ -188,11 +209,11 @@ pub fn execute_batch_send_from(
             token_id,
             *amount,
         )?;
-        event.add_attributes(&mut rsp, "transfer");
+        rsp.events.push(event.to_event(info.sender.as_ref(), true));
     }
     rsp.attributes.push(attr(
         "royalty_fee",
-        Coin::new(fee.u128(), NATIVE_DENOM).to_string(),
+        Coin::new(fee, NATIVE_DENOM).to_string(),
     ));
     rsp.attributes
         .push(attr("royalty_address", royalty.to_string()));
@@ -220,7 +241,7 @@ pub fn execute_mint(
     to: String,
     token_id: TokenId,
     amount: Uint128,
-    token_uri: TokenUri,
+    token_uri: Option<TokenUri>,
     msg: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let ExecuteEnv { mut deps, info, .. } = env;
@@ -234,12 +255,12 @@ pub fn execute_mint(
         return Err(ContractError::Unauthorized {});
     }
 
-    Url::parse(&token_uri)?;
+    _check_token_uri(deps.as_ref(), &token_uri)?;
 
-    let mut rsp = Response::default();
+    let mut rsp = Response::default().add_attribute("action", "mint");
 
     let event = execute_transfer_inner(&mut deps, None, Some(&to_addr), &token_id, amount)?;
-    event.add_attributes(&mut rsp, "mint");
+    rsp.events.push(event.to_event(info.sender.as_ref(), false));
     rsp.attributes
         .push(attr("mint_fee", info.funds[0].to_string()));
     rsp.attributes.push(attr("payment_address", MULTISIG));
@@ -258,11 +279,7 @@ pub fn execute_mint(
     }
     rsp.messages = msgs;
 
-    // insert if not exist
-    if !TOKENS.has(deps.storage, &token_id) {
-        // we must save some valid data here
-        TOKENS.save(deps.storage, &token_id, &token_uri)?;
-    }
+    _save_token(deps, &token_id, token_uri)?;
 
     Ok(rsp)
 }
@@ -270,7 +287,7 @@ pub fn execute_mint(
 pub fn execute_batch_mint(
     env: ExecuteEnv,
     to: String,
-    batch: Vec<(TokenId, TokenUri, Uint128)>,
+    batch: Vec<(TokenId, Option<TokenUri>, Uint128)>,
     msg: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let ExecuteEnv { mut deps, info, .. } = env;
@@ -281,28 +298,22 @@ pub fn execute_batch_mint(
     }
 
     // MIN_FEE * Number of Tokens
-    let min_fee = Uint128::from(u128::try_from(batch.len()).unwrap())
-        .checked_mul(Uint128::from(MIN_FEE))
-        .unwrap();
-    let mut msgs = vec![check_payment(&info, min_fee.u128(), multisig)?];
+    let min_fee = FeeSchedule::PerItem(MIN_FEE).fee(batch.len())?;
+    let mut msgs = vec![check_payment(&info, min_fee, multisig)?];
 
     let to_addr = deps.api.addr_validate(&to)?;
 
-    let mut rsp = Response::default();
+    let mut rsp = Response::default().add_attribute("action", "batch_mint");
 
     let mut msg_batch: Vec<(TokenId, Uint128)> = vec![];
-    for (_, (token_id, token_uri, amount)) in batch.iter().enumerate() {
-        Url::parse(token_uri)?;
+    for (token_id, token_uri, amount) in batch {
+        _check_token_uri(deps.as_ref(), &token_uri)?;
 
-        let event = execute_transfer_inner(&mut deps, None, Some(&to_addr), token_id, *amount)?;
-        event.add_attributes(&mut rsp, "mint");
+        let event = execute_transfer_inner(&mut deps, None, Some(&to_addr), &token_id, amount)?;
+        rsp.events.push(event.to_event(info.sender.as_ref(), true));
 
-        // insert if not exist
-        if !TOKENS.has(deps.storage, token_id) {
-            // we must save some valid data here
-            TOKENS.save(deps.storage, token_id, token_uri)?;
-        }
-        msg_batch.push((token_id.clone(), *amount));
+        _save_token(deps.branch(), &token_id, token_uri)?;
+        msg_batch.push((token_id, amount));
     }
     rsp.attributes
         .push(attr("mint_fee", info.funds[0].to_string()));
@@ -324,6 +335,162 @@ pub fn execute_batch_mint(
     Ok(rsp)
 }
 
+pub fn execute_set_voucher_signer(
+    env: ExecuteEnv,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    if info.sender != MINTER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &pubkey {
+        // Compressed or uncompressed secp256k1 public key
+        Some(pubkey) if pubkey.len() != 33 && pubkey.len() != 65 => {
+            return Err(ContractError::InvalidPubkey {})
+        }
+        Some(pubkey) => VOUCHER_SIGNER.save(deps.storage, pubkey)?,
+        None => VOUCHER_SIGNER.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "set_voucher_signer")
+        .add_attribute(
+            "pubkey",
+            pubkey.map(|key| key.to_base64()).unwrap_or_default(),
+        ))
+}
+
+pub fn execute_redeem_voucher(
+    env: ExecuteEnv,
+    voucher: MintVoucher,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        env,
+        info,
+    } = env;
+
+    if voucher.expires.is_expired(&env.block) {
+        return Err(ContractError::VoucherExpired {});
+    }
+    let signer = VOUCHER_SIGNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoVoucherSigner {})?;
+    let hash = voucher.hash(&env.contract.address)?;
+    if !deps
+        .api
+        .secp256k1_verify(&hash, &signature, &signer)
+        .map_err(|_| ContractError::InvalidSignature {})?
+    {
+        return Err(ContractError::InvalidSignature {});
+    }
+    if REDEEMED_VOUCHERS.has(deps.storage, &hash) {
+        return Err(ContractError::VoucherRedeemed {});
+    }
+
+    // Buyer pays the minting fee to multisig and the price to the minter
+    let expected = voucher
+        .price
+        .checked_add(MIN_FEE.into())
+        .map_err(FeeError::from)?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != expected {
+        return Err(ContractError::IncorrectPayment {
+            expected: expected.u128(),
+            received: payment.u128(),
+        });
+    }
+    let minter = MINTER.load(deps.storage)?;
+    let mut msgs = vec![SubMsg::new(BankMsg::Send {
+        to_address: MULTISIG.to_string(),
+        amount: coins(MIN_FEE, NATIVE_DENOM),
+    })];
+    if !voucher.price.is_zero() {
+        msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: minter.to_string(),
+            amount: coins(voucher.price.u128(), NATIVE_DENOM),
+        }));
+    }
+
+    _check_token_uri(deps.as_ref(), &voucher.token_uri)?;
+    REDEEMED_VOUCHERS.save(deps.storage, &hash, &true)?;
+
+    let mut rsp = Response::default().add_attribute("action", "redeem_voucher");
+
+    let event = execute_transfer_inner(
+        &mut deps,
+        None,
+        Some(&info.sender),
+        &voucher.token_id,
+        voucher.value,
+    )?;
+    rsp.events.push(event.to_event(info.sender.as_ref(), false));
+    rsp.attributes.push(attr(
+        "mint_fee",
+        Coin::new(MIN_FEE, NATIVE_DENOM).to_string(),
+    ));
+    rsp.attributes.push(attr("payment_address", MULTISIG));
+    rsp.attributes.push(attr(
+        "price",
+        Coin::new(voucher.price.u128(), NATIVE_DENOM).to_string(),
+    ));
+    rsp.messages = msgs;
+
+    _save_token(deps, &voucher.token_id, voucher.token_uri)?;
+
+    Ok(rsp)
+}
+
+pub fn execute_burn(
+    env: ExecuteEnv,
+    from: String,
+    token_id: TokenId,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        env,
+        info,
+    } = env;
+
+    let from_addr = deps.api.addr_validate(&from)?;
+
+    // whoever can transfer these tokens can burn
+    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
+
+    let mut rsp = Response::default().add_attribute("action", "burn");
+    let event = execute_transfer_inner(&mut deps, Some(&from_addr), None, &token_id, amount)?;
+    rsp.events.push(event.to_event(info.sender.as_ref(), false));
+
+    Ok(rsp)
+}
+
+pub fn execute_batch_burn(
+    env: ExecuteEnv,
+    from: String,
+    batch: Vec<(TokenId, Uint128)>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        env,
+        info,
+    } = env;
+
+    let from_addr = deps.api.addr_validate(&from)?;
+
+    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
+
+    let mut rsp = Response::default().add_attribute("action", "batch_burn");
+    for (token_id, amount) in batch.iter() {
+        let event = execute_transfer_inner(&mut deps, Some(&from_addr), None, token_id, *amount)?;
+        rsp.events.push(event.to_event(info.sender.as_ref(), true));
+    }
+
+    Ok(rsp)
+}
+
 /********************************* QUERIES ************************************/
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -336,13 +503,59 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             min_fee: Coin::new(MIN_FEE, NATIVE_DENOM),
             royalty_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
             royalty_share: OWNER_PERCENT,
+            base_uri: BASE_URI.may_load(deps.storage)?,
+        }),
+        QueryMsg::TokenInfo { token_id } => to_binary(&query_token_info(deps, token_id)?),
+        QueryMsg::UriPolicy {} => {
+            to_binary(&URI_POLICY.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::VoucherSigner {} => to_binary(&VoucherSignerResponse {
+            pubkey: VOUCHER_SIGNER.may_load(deps.storage)?,
         }),
         _ => base_query(deps, env, Cw1155QueryMsg::from(msg)),
     }
 }
 
+fn query_token_info(deps: Deps, token_id: TokenId) -> StdResult<TokenInfoResponse> {
+    let url = TOKENS.load(deps.storage, &token_id)?;
+    if !url.is_empty() {
+        return Ok(TokenInfoResponse { url });
+    }
+    let base_uri = BASE_URI.load(deps.storage)?;
+    Ok(TokenInfoResponse {
+        url: base_uri.replace("{id}", &token_id),
+    })
+}
+
 /********************************* HELPERS ************************************/
 
+/// Tokens minted without a uri fall back to the base uri
+fn _check_token_uri(deps: Deps, token_uri: &Option<TokenUri>) -> Result<(), ContractError> {
+    match token_uri {
+        Some(token_uri) => {
+            let uri_policy = URI_POLICY.may_load(deps.storage)?.unwrap_or_default();
+            uri_policy.validate(token_uri)?;
+        }
+        None if BASE_URI.may_load(deps.storage)?.is_none() => {
+            return Err(ContractError::MissingTokenUri {})
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Save the token uri on first mint, an empty uri is stored for tokens using the base uri
+fn _save_token(
+    deps: DepsMut,
+    token_id: &str,
+    token_uri: Option<TokenUri>,
+) -> Result<(), ContractError> {
+    if !TOKENS.has(deps.storage, token_id) {
+        TOKENS.save(deps.storage, token_id, &token_uri.unwrap_or_default())?;
+    }
+    Ok(())
+}
+
 /// When from is None: mint new coins
 /// When to is None: burn coins
 /// When both are None: no token balance is changed, pointless but valid
@@ -417,10 +630,11 @@ mod tests {
     use cosmwasm_std::{
         coins, from_binary,
         testing::{mock_dependencies, mock_env, mock_info},
-        BankMsg,
+        BankMsg, Event,
     };
     use cw1155::{BalanceResponse, BatchBalanceResponse, TokenInfoResponse};
     use s_std::{create_fund_community_pool_msg, error::FeeError};
+    use s_uri::{UriError, UriPolicy};
 
     use super::*;
 
@@ -432,6 +646,8 @@ mod tests {
 
         let msg = InstantiateMsg {
             royalty_address: royalty.clone(),
+            base_uri: None,
+            uri_policy: None,
         };
 
         let rsp = Response::new()
@@ -453,6 +669,7 @@ mod tests {
                 min_fee: Coin::new(MIN_FEE, NATIVE_DENOM),
                 royalty_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
                 royalty_share: OWNER_PERCENT,
+                base_uri: None,
             })
         );
     }
@@ -470,6 +687,8 @@ mod tests {
         // instantiate contract for "minter"
         let msg = InstantiateMsg {
             royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
         };
         instantiate(
             deps.as_mut(),
@@ -484,7 +703,7 @@ mod tests {
             to: minter.clone(),
             token_id: token1.clone(),
             value: 2u64.into(),
-            token_uri,
+            token_uri: Some(token_uri),
             msg: None,
         };
         execute(
@@ -537,14 +756,18 @@ mod tests {
         let community_msg =
             SubMsg::new(create_fund_community_pool_msg(coins(50u128, NATIVE_DENOM)));
         let mut rsp = Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("from", &minter)
-            .add_attribute("to", &user1)
+            .add_attribute("action", "send_from")
             .add_attribute("royalty_fee", info.funds[0].to_string())
             .add_attribute("royalty_address", &minter)
-            .add_attribute("royalty_share", OWNER_PERCENT.to_string());
+            .add_attribute("royalty_share", OWNER_PERCENT.to_string())
+            .add_event(
+                Event::new("transfer_single")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("from", &minter)
+                    .add_attribute("to", &user1),
+            );
         rsp.messages = vec![bank_msg, community_msg];
         assert_eq!(
             execute(deps.as_mut(), mock_env(), info, transfer_msg,).unwrap(),
@@ -609,14 +832,18 @@ mod tests {
         let community_msg =
             SubMsg::new(create_fund_community_pool_msg(coins(50u128, NATIVE_DENOM)));
         let mut rsp = Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("from", &minter)
-            .add_attribute("to", &user2)
+            .add_attribute("action", "send_from")
             .add_attribute("royalty_fee", info.funds[0].to_string())
             .add_attribute("royalty_address", &minter)
-            .add_attribute("royalty_share", OWNER_PERCENT.to_string());
+            .add_attribute("royalty_share", OWNER_PERCENT.to_string())
+            .add_event(
+                Event::new("transfer_single")
+                    .add_attribute("operator", &user2)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("from", &minter)
+                    .add_attribute("to", &user2),
+            );
         rsp.messages = vec![bank_msg, community_msg];
         assert_eq!(
             execute(deps.as_mut(), mock_env(), info, transfer_msg,).unwrap(),
@@ -670,6 +897,8 @@ mod tests {
         // instantiate contract for "minter"
         let msg = InstantiateMsg {
             royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
         };
         instantiate(
             deps.as_mut(),
@@ -683,8 +912,8 @@ mod tests {
         let mint_msg = ExecuteMsg::BatchMint {
             to: minter.clone(),
             batch: vec![
-                (token1.clone(), token_uri1, Uint128::from(1u128)),
-                (token2.clone(), token_uri2, Uint128::from(3u128)),
+                (token1.clone(), Some(token_uri1), Uint128::from(1u128)),
+                (token2.clone(), Some(token_uri2), Uint128::from(3u128)),
             ],
             msg: None,
         };
@@ -739,19 +968,26 @@ mod tests {
         let community_msg =
             SubMsg::new(create_fund_community_pool_msg(coins(100u128, NATIVE_DENOM)));
         let mut rsp = Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("from", &minter)
-            .add_attribute("to", &user1)
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token2)
-            .add_attribute("amount", 2u64.to_string())
-            .add_attribute("from", &minter)
-            .add_attribute("to", &user1)
+            .add_attribute("action", "batch_send_from")
             .add_attribute("royalty_fee", Coin::new(payment, NATIVE_DENOM).to_string())
             .add_attribute("royalty_address", &minter)
-            .add_attribute("royalty_share", OWNER_PERCENT.to_string());
+            .add_attribute("royalty_share", OWNER_PERCENT.to_string())
+            .add_event(
+                Event::new("transfer_batch")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("from", &minter)
+                    .add_attribute("to", &user1),
+            )
+            .add_event(
+                Event::new("transfer_batch")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token2)
+                    .add_attribute("amount", 2u64.to_string())
+                    .add_attribute("from", &minter)
+                    .add_attribute("to", &user1),
+            );
         rsp.messages = vec![bank_msg, community_msg];
         assert_eq!(
             execute(
@@ -823,19 +1059,26 @@ mod tests {
         let community_msg =
             SubMsg::new(create_fund_community_pool_msg(coins(100u128, NATIVE_DENOM)));
         let mut rsp = Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("from", &user1)
-            .add_attribute("to", &minter)
-            .add_attribute("action", "transfer")
-            .add_attribute("token_id", &token2)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("from", &user1)
-            .add_attribute("to", &minter)
+            .add_attribute("action", "batch_send_from")
             .add_attribute("royalty_fee", Coin::new(payment, NATIVE_DENOM).to_string())
             .add_attribute("royalty_address", &minter)
-            .add_attribute("royalty_share", OWNER_PERCENT.to_string());
+            .add_attribute("royalty_share", OWNER_PERCENT.to_string())
+            .add_event(
+                Event::new("transfer_batch")
+                    .add_attribute("operator", &user2)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("from", &user1)
+                    .add_attribute("to", &minter),
+            )
+            .add_event(
+                Event::new("transfer_batch")
+                    .add_attribute("operator", &user2)
+                    .add_attribute("token_id", &token2)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("from", &user1)
+                    .add_attribute("to", &minter),
+            );
         rsp.messages = vec![bank_msg, community_msg];
         assert_eq!(
             execute(
@@ -879,6 +1122,190 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_send_all_discount() {
+        let minter = String::from("minter");
+        let user1 = String::from("user1");
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(minter.as_str(), &[]),
+            msg,
+        )
+        .unwrap();
+
+        // mint 10 tokens
+        let batch: Vec<(TokenId, Option<TokenUri>, Uint128)> = (0..10)
+            .map(|i| {
+                (
+                    format!("token{}", i),
+                    Some(format!("https://example.com/token_uri{}", i)),
+                    Uint128::from(1u128),
+                )
+            })
+            .collect();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(minter.as_ref(), &coins(MIN_FEE * 10, NATIVE_DENOM)),
+            ExecuteMsg::BatchMint {
+                to: minter.clone(),
+                batch: batch.clone(),
+                msg: None,
+            },
+        )
+        .unwrap();
+
+        // the 10th token is charged at the discounted tier
+        let payment = ROYALTY_FEE_SCHEDULE.fee(10).unwrap();
+        assert!(payment < ROYALTY_FEE * 10);
+
+        let transfer_msg = ExecuteMsg::BatchSendFrom {
+            from: minter.clone(),
+            to: user1,
+            batch: batch
+                .into_iter()
+                .map(|(token_id, _, amount)| (token_id, amount))
+                .collect(),
+            msg: None,
+        };
+        let rsp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(minter.as_ref(), &coins(payment, NATIVE_DENOM)),
+            transfer_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: minter,
+                    amount: coins(9405u128, NATIVE_DENOM.to_string()),
+                }),
+                SubMsg::new(create_fund_community_pool_msg(coins(495u128, NATIVE_DENOM)))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_burn() {
+        let minter = String::from("minter");
+        let user1 = String::from("user1");
+        let token1 = "token1".to_owned();
+        let token_uri = "https://example.com/token_uri1".to_owned();
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(minter.as_str(), &[]),
+            msg,
+        )
+        .unwrap();
+
+        let mint_msg = ExecuteMsg::Mint {
+            to: minter.clone(),
+            token_id: token1.clone(),
+            value: 3u64.into(),
+            token_uri: Some(token_uri),
+            msg: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(minter.as_ref(), &coins(MIN_FEE, NATIVE_DENOM)),
+            mint_msg,
+        )
+        .unwrap();
+
+        // user1 not approved to burn minter's token
+        let burn_msg = ExecuteMsg::Burn {
+            from: minter.clone(),
+            token_id: token1.clone(),
+            value: 1u64.into(),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(user1.as_ref(), &[]),
+                burn_msg.clone(),
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // burn 1 token
+        let rsp = Response::new().add_attribute("action", "burn").add_event(
+            Event::new("burn_single")
+                .add_attribute("operator", &minter)
+                .add_attribute("token_id", &token1)
+                .add_attribute("amount", 1u64.to_string())
+                .add_attribute("from", &minter),
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                burn_msg
+            )
+            .unwrap(),
+            rsp
+        );
+
+        // burn 2 tokens in a batch
+        let burn_msg = ExecuteMsg::BatchBurn {
+            from: minter.clone(),
+            batch: vec![(token1.clone(), 2u64.into())],
+        };
+        let rsp = Response::new()
+            .add_attribute("action", "batch_burn")
+            .add_event(
+                Event::new("burn_batch")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 2u64.to_string())
+                    .add_attribute("from", &minter),
+            );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                burn_msg
+            )
+            .unwrap(),
+            rsp
+        );
+
+        assert_eq!(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Balance {
+                    owner: minter,
+                    token_id: token1,
+                }
+            ),
+            to_binary(&BalanceResponse {
+                balance: 0u64.into()
+            })
+        );
+    }
+
     #[test]
     fn test_mint() {
         let minter = String::from("minter");
@@ -891,6 +1318,8 @@ mod tests {
         // instantiate contract for "minter"
         let msg = InstantiateMsg {
             royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
         };
         instantiate(
             deps.as_mut(),
@@ -904,7 +1333,7 @@ mod tests {
             to: minter.clone(),
             token_id: token1.clone(),
             value: 1u64.into(),
-            token_uri: token_uri.clone(),
+            token_uri: Some(token_uri.clone()),
             msg: None,
         };
 
@@ -923,7 +1352,7 @@ mod tests {
             to: minter.clone(),
             token_id: token1.clone(),
             value: 1u64.into(),
-            token_uri: token_uri.clone(),
+            token_uri: Some(token_uri.clone()),
             msg: None,
         };
 
@@ -948,11 +1377,15 @@ mod tests {
         });
         let mut rsp = Response::new()
             .add_attribute("action", "mint")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("to", &minter)
             .add_attribute("mint_fee", info.funds[0].to_string())
-            .add_attribute("payment_address", MULTISIG);
+            .add_attribute("payment_address", MULTISIG)
+            .add_event(
+                Event::new("mint_single")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("to", &minter),
+            );
         rsp.messages = vec![bank_msg];
         assert_eq!(
             execute(deps.as_mut(), mock_env(), info, mint_msg.clone(),).unwrap(),
@@ -1020,8 +1453,16 @@ mod tests {
         let token_uri2 = "https://example.com/token_uri2".to_owned();
 
         let token_batch = vec![
-            (token1.clone(), token_uri1.clone(), Uint128::from(1u128)),
-            (token2.clone(), token_uri2.clone(), Uint128::from(3u128)),
+            (
+                token1.clone(),
+                Some(token_uri1.clone()),
+                Uint128::from(1u128),
+            ),
+            (
+                token2.clone(),
+                Some(token_uri2.clone()),
+                Uint128::from(3u128),
+            ),
         ];
         let payment = MIN_FEE * 2; // Min amount to be paid
         let demon_string = NATIVE_DENOM.to_string();
@@ -1029,6 +1470,8 @@ mod tests {
         let mut deps = mock_dependencies();
         let msg = InstantiateMsg {
             royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
         };
         instantiate(
             deps.as_mut(),
@@ -1073,16 +1516,23 @@ mod tests {
             amount: coins(payment, demon_string),
         });
         let mut rsp = Response::new()
-            .add_attribute("action", "mint")
-            .add_attribute("token_id", &token1)
-            .add_attribute("amount", 1u64.to_string())
-            .add_attribute("to", &minter)
-            .add_attribute("action", "mint")
-            .add_attribute("token_id", &token2)
-            .add_attribute("amount", 3u64.to_string())
-            .add_attribute("to", &minter)
+            .add_attribute("action", "batch_mint")
             .add_attribute("mint_fee", info.funds[0].to_string())
-            .add_attribute("payment_address", MULTISIG);
+            .add_attribute("payment_address", MULTISIG)
+            .add_event(
+                Event::new("mint_batch")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token1)
+                    .add_attribute("amount", 1u64.to_string())
+                    .add_attribute("to", &minter),
+            )
+            .add_event(
+                Event::new("mint_batch")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token2)
+                    .add_attribute("amount", 3u64.to_string())
+                    .add_attribute("to", &minter),
+            );
         rsp.messages = vec![bank_msg];
         assert_eq!(
             execute(deps.as_mut(), mock_env(), info, mint_msg,).unwrap(),
@@ -1124,4 +1574,312 @@ mod tests {
         let value: TokenInfoResponse = from_binary(&res).unwrap();
         assert_eq!(TokenInfoResponse { url: token_uri2 }, value);
     }
+
+    #[test]
+    fn test_redeem_voucher() {
+        use cosmwasm_std::to_vec;
+        use cw_utils::Expiration;
+        use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &[]),
+            InstantiateMsg {
+                royalty_address: minter.clone(),
+                base_uri: None,
+                uri_policy: None,
+            },
+        )
+        .unwrap();
+
+        let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+        let pubkey = Binary::from(key.verifying_key().to_bytes().as_slice());
+        let voucher = MintVoucher {
+            token_id: "token".to_string(),
+            token_uri: Some("https://example.com/token_uri".to_string()),
+            value: Uint128::new(5),
+            price: Uint128::new(10_000_000),
+            expires: Expiration::AtHeight(mock_env().block.height + 100),
+            nonce: 1,
+        };
+        // signed off-chain over contract address ++ json(voucher)
+        let contract_addr = mock_env().contract.address;
+        let sign = |voucher: &MintVoucher| {
+            let preimage = [contract_addr.as_bytes(), &to_vec(voucher).unwrap()].concat();
+            let signature: Signature = key.sign(&preimage);
+            Binary::from(signature.as_ref())
+        };
+        let redeem_msg = ExecuteMsg::RedeemVoucher {
+            voucher: voucher.clone(),
+            signature: sign(&voucher),
+        };
+        let payment = coins(MIN_FEE + 10_000_000, NATIVE_DENOM);
+
+        // Error: no voucher signer
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::NoVoucherSigner {})
+        ));
+
+        // Error: only minter can set the voucher signer
+        let set_msg = ExecuteMsg::SetVoucherSigner {
+            pubkey: Some(pubkey.clone()),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &[]),
+                set_msg.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+        execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), set_msg).unwrap();
+        let res: VoucherSignerResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::VoucherSigner {}).unwrap())
+                .unwrap();
+        assert_eq!(res.pubkey, Some(pubkey));
+
+        // Error: voucher altered by the buyer
+        let mut more = voucher.clone();
+        more.value = Uint128::new(50);
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                ExecuteMsg::RedeemVoucher {
+                    voucher: more,
+                    signature: sign(&voucher),
+                }
+            ),
+            Err(ContractError::InvalidSignature {})
+        ));
+
+        // Error: minting fee not paid on top of the price
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &coins(10_000_000, NATIVE_DENOM)),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        // buyer mints the tokens, paying the minting fee and the price
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &payment),
+            redeem_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: MULTISIG.to_string(),
+                    amount: coins(MIN_FEE, NATIVE_DENOM),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: minter,
+                    amount: coins(10_000_000, NATIVE_DENOM),
+                }),
+            ]
+        );
+        let res: BalanceResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Balance {
+                    owner: "buyer".to_string(),
+                    token_id: "token".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.balance, Uint128::new(5));
+
+        // Error: voucher cannot be redeemed twice
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                redeem_msg
+            ),
+            Err(ContractError::VoucherRedeemed {})
+        ));
+
+        // same voucher with a new nonce mints more of the token
+        let mut again = voucher;
+        again.nonce = 2;
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &payment),
+            ExecuteMsg::RedeemVoucher {
+                signature: sign(&again),
+                voucher: again,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_base_uri() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let info = mock_info(&minter, &[]);
+
+        // Error: base uri is not a template
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: Some("ipfs://hash/".to_string()),
+            uri_policy: None,
+        };
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg),
+            Err(ContractError::InvalidBaseUri {})
+        ));
+
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: Some("ipfs://hash/{id}.json".to_string()),
+            uri_policy: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let batch_mint_msg = ExecuteMsg::BatchMint {
+            to: minter.clone(),
+            batch: vec![
+                ("token1".to_string(), None, Uint128::new(1)),
+                (
+                    "token2".to_string(),
+                    Some("https://example.com/token2".to_string()),
+                    Uint128::new(1),
+                ),
+            ],
+            msg: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &coins(MIN_FEE * 2, NATIVE_DENOM)),
+            batch_mint_msg,
+        )
+        .unwrap();
+
+        let token_info = |token_id: &str| -> TokenInfoResponse {
+            let msg = QueryMsg::TokenInfo {
+                token_id: token_id.to_string(),
+            };
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+        };
+        // token uri is expanded from the base uri, unless minted with its own uri
+        assert_eq!(token_info("token1").url, "ipfs://hash/token1.json");
+        assert_eq!(token_info("token2").url, "https://example.com/token2");
+
+        // Error: token uri is required without a base uri
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+        let mint_msg = ExecuteMsg::Mint {
+            to: minter.clone(),
+            token_id: "token1".to_string(),
+            value: Uint128::new(1),
+            token_uri: None,
+            msg: None,
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+                mint_msg
+            ),
+            Err(ContractError::MissingTokenUri {})
+        ));
+    }
+
+    #[test]
+    fn test_uri_policy() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let msg = InstantiateMsg {
+            royalty_address: minter.clone(),
+            base_uri: None,
+            uri_policy: Some(UriPolicy {
+                schemes: vec!["ipfs".to_string(), "https".to_string()],
+                max_length: Some(64),
+                hosts: Some(vec!["gateway.sign.net".to_string()]),
+            }),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+
+        let mint = |deps: DepsMut, token_uri: &str| {
+            let msg = ExecuteMsg::BatchMint {
+                to: minter.clone(),
+                batch: vec![(
+                    "token".to_string(),
+                    Some(token_uri.to_string()),
+                    Uint128::new(1),
+                )],
+                msg: None,
+            };
+            execute(
+                deps,
+                mock_env(),
+                mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+                msg,
+            )
+        };
+
+        // Error: scheme, host and length must be allowed by the policy
+        assert!(matches!(
+            mint(
+                deps.as_mut(),
+                "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"
+            ),
+            Err(ContractError::Uri(UriError::SchemeNotAllowed { .. }))
+        ));
+        assert!(matches!(
+            mint(deps.as_mut(), "https://example.com/token.json"),
+            Err(ContractError::Uri(UriError::HostNotAllowed { .. }))
+        ));
+        assert!(matches!(
+            mint(
+                deps.as_mut(),
+                &format!("https://gateway.sign.net/{}", "a".repeat(64))
+            ),
+            Err(ContractError::Uri(UriError::TooLong { max: 64 }))
+        ));
+
+        // bare CIDs are minted as ipfs uris
+        mint(
+            deps.as_mut(),
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        )
+        .unwrap();
+        mint(deps.as_mut(), "https://gateway.sign.net/token.json").unwrap();
+
+        let res: UriPolicy =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::UriPolicy {}).unwrap())
+                .unwrap();
+        assert_eq!(res.max_length, Some(64));
+    }
 }
@@ -2,6 +2,7 @@ use cosmwasm_std::StdError;
 use cw1155_base::ContractError as Cw1155ContractError;
 use cw_utils::PaymentError;
 use s_std::error::FeeError;
+use s_uri::UriError;
 use thiserror::Error;
 use url::ParseError;
 
@@ -19,6 +20,30 @@ pub enum ContractError {
     #[error("Mismatch number of token and token info")]
     TokenInfoMismatch {},
 
+    #[error("Base uri must contain {{id}}")]
+    InvalidBaseUri {},
+
+    #[error("Token uri required without a base uri")]
+    MissingTokenUri {},
+
+    #[error("Voucher signer not set")]
+    NoVoucherSigner {},
+
+    #[error("Invalid secp256k1 public key")]
+    InvalidPubkey {},
+
+    #[error("Invalid voucher signature")]
+    InvalidSignature {},
+
+    #[error("Voucher expired")]
+    VoucherExpired {},
+
+    #[error("Voucher already redeemed")]
+    VoucherRedeemed {},
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
     #[error("{0}")]
     Fee(#[from] FeeError),
 
@@ -27,6 +52,9 @@ pub enum ContractError {
 
     #[error("{0}")]
     Parse(#[from] ParseError),
+
+    #[error("{0}")]
+    Uri(#[from] UriError),
 }
 
 impl From<ContractError> for Cw1155ContractError {
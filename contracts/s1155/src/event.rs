@@ -1,5 +1,4 @@
-use cosmwasm_std::{attr, Uint128};
-use s_std::Response;
+use cosmwasm_std::{Event, Uint128};
 
 pub struct TransferEvent<'a> {
     pub from: Option<&'a str>,
@@ -8,21 +7,36 @@ pub struct TransferEvent<'a> {
     pub amount: Uint128,
 }
 
-pub trait Event {
-    /// Append attributes to response
-    fn add_attributes(&self, response: &mut Response, action: &str);
+pub trait ToEvent {
+    /// Create a wasm event, `batch` if emitted from a batch message
+    fn to_event(&self, operator: &str, batch: bool) -> Event;
 }
 
-impl<'a> Event for TransferEvent<'a> {
-    fn add_attributes(&self, rsp: &mut Response, action: &str) {
-        rsp.attributes.push(attr("action", action.to_string()));
-        rsp.attributes.push(attr("token_id", self.token_id));
-        rsp.attributes.push(attr("amount", self.amount));
+impl<'a> TransferEvent<'a> {
+    /// Mint when from is None, burn when to is None, transfer otherwise
+    fn kind(&self) -> &'static str {
+        match (self.from, self.to) {
+            (None, Some(_)) => "mint",
+            (Some(_), None) => "burn",
+            _ => "transfer",
+        }
+    }
+}
+
+impl<'a> ToEvent for TransferEvent<'a> {
+    /// Emitted as `wasm-{transfer,mint,burn}_{single,batch}`
+    fn to_event(&self, operator: &str, batch: bool) -> Event {
+        let size = if batch { "batch" } else { "single" };
+        let mut event = Event::new(format!("{}_{}", self.kind(), size))
+            .add_attribute("operator", operator)
+            .add_attribute("token_id", self.token_id)
+            .add_attribute("amount", self.amount);
         if let Some(from) = self.from {
-            rsp.attributes.push(attr("from", from.to_string()));
+            event = event.add_attribute("from", from);
         }
         if let Some(to) = self.to {
-            rsp.attributes.push(attr("to", to.to_string()));
+            event = event.add_attribute("to", to);
         }
+        event
     }
 }
@@ -3,5 +3,6 @@ mod error;
 pub mod event;
 pub mod msg;
 pub mod state;
+pub mod voucher;
 
 pub use crate::error::ContractError;
@@ -1,7 +1,9 @@
+use crate::voucher::MintVoucher;
 use cosmwasm_std::{to_binary, Binary, Coin, StdResult, Uint128, WasmMsg};
 use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg, TokenId};
 use cw_utils::Expiration;
 use s_std::CosmosMsg;
+use s_uri::UriPolicy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +12,10 @@ pub type TokenUri = String;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub royalty_address: String,
+    /// ERC-1155 style uri template, `{id}` is replaced by the token id
+    pub base_uri: Option<String>,
+    /// Defaults to `ipfs`, `ar` and `https` uris
+    pub uri_policy: Option<UriPolicy>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -32,12 +38,13 @@ pub enum ExecuteMsg {
         to: String,
         token_id: TokenId,
         value: Uint128,
-        token_uri: TokenUri,
+        /// Defaults to the base uri when omitted
+        token_uri: Option<TokenUri>,
         msg: Option<Binary>,
     },
     BatchMint {
         to: String,
-        batch: Vec<(TokenId, TokenUri, Uint128)>,
+        batch: Vec<(TokenId, Option<TokenUri>, Uint128)>,
         msg: Option<Binary>,
     },
     Burn {
@@ -56,6 +63,16 @@ pub enum ExecuteMsg {
     RevokeAll {
         operator: String,
     },
+    /// Set the compressed or uncompressed secp256k1 public key signing mint vouchers,
+    /// can only be called by the contract minter. None disables vouchers.
+    SetVoucherSigner {
+        pubkey: Option<Binary>,
+    },
+    /// Mint the voucher tokens to the sender, paying the minting fee and the voucher price
+    RedeemVoucher {
+        voucher: MintVoucher,
+        signature: Binary,
+    },
 }
 
 impl From<ExecuteMsg> for Cw1155ExecuteMsg {
@@ -138,6 +155,10 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     Config {},
+    /// Validation policy of the token uris
+    UriPolicy {},
+    /// Public key signing mint vouchers
+    VoucherSigner {},
 }
 
 impl From<QueryMsg> for Cw1155QueryMsg {
@@ -187,6 +208,12 @@ pub struct ConfigResponse {
     pub min_fee: Coin,
     pub royalty_fee: Coin,
     pub royalty_share: u64,
+    pub base_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VoucherSignerResponse {
+    pub pubkey: Option<Binary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
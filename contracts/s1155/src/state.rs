@@ -1,5 +1,18 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Binary};
+use cw_storage_plus::{Item, Map};
+use s_uri::UriPolicy;
 
 /// Address to send royalty payment to.
 pub const ROYALTY: Item<Addr> = Item::new("royalty");
+
+/// Collection uri template, `{id}` is replaced by the token id for tokens minted without a uri
+pub const BASE_URI: Item<String> = Item::new("base_uri");
+
+/// Validation policy of the token uris
+pub const URI_POLICY: Item<UriPolicy> = Item::new("uri_policy");
+
+/// Public key of the minter key signing mint vouchers
+pub const VOUCHER_SIGNER: Item<Binary> = Item::new("voucher_signer");
+
+// Voucher hash -> redeemed
+pub const REDEEMED_VOUCHERS: Map<&[u8], bool> = Map::new("redeemed_vouchers");
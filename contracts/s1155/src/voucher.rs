@@ -0,0 +1,34 @@
+use cosmwasm_std::{to_vec, Addr, StdResult, Uint128};
+use cw1155::TokenId;
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::msg::TokenUri;
+
+/// Mint signed off-chain by the voucher signer and redeemed by the buyer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintVoucher {
+    pub token_id: TokenId,
+    pub token_uri: Option<TokenUri>,
+    pub value: Uint128,
+    /// Price in usign paid to the minter, on top of the minting fee
+    pub price: Uint128,
+    pub expires: Expiration,
+    /// Tells apart vouchers minting the same token, each voucher can be redeemed once
+    pub nonce: u64,
+}
+
+impl MintVoucher {
+    /// Message hash signed by the voucher signer, `sha256(contract_address ++ json(voucher))`
+    pub fn hash(&self, contract: &Addr) -> StdResult<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(contract.as_bytes());
+        hasher.update(to_vec(self)?);
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hasher.finalize());
+        Ok(hash)
+    }
+}
@@ -9,7 +9,11 @@ use cw721::{
     NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
 use cw721_base::MinterResponse;
-use s721::msg::{CollectionInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use s721::msg::{
+    BlindBoxResponse, CollectionInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    UserOfResponse, VoucherSignerResponse,
+};
+use s_uri::UriPolicy;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -21,6 +25,7 @@ fn main() {
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(CollectionInfoResponse), &out_dir);
+    export_schema(&schema_for!(BlindBoxResponse), &out_dir);
     export_schema_with_title(
         &schema_for!(AllNftInfoResponse<Empty>),
         &out_dir,
@@ -45,4 +50,7 @@ fn main() {
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
     export_schema(&schema_for!(OwnerOfResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
+    export_schema(&schema_for!(UriPolicy), &out_dir);
+    export_schema(&schema_for!(UserOfResponse), &out_dir);
+    export_schema(&schema_for!(VoucherSignerResponse), &out_dir);
 }
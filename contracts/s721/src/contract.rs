@@ -1,17 +1,30 @@
-#[cfg(not(feature = "library"))]
-use crate::msg::{CollectionInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{CollectionInfo, COLLECTION_INFO};
+use crate::event::{ToEvent, TransferEvent};
+use crate::msg::{
+    BlindBoxResponse, CollectionInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg, UserOfResponse,
+    VoucherSignerResponse,
+};
+use crate::state::{
+    CollectionInfo, TokenUser, BLIND_BOX, COLLECTION_INFO, REDEEMED_VOUCHERS, REVEALED_BASE_URI,
+    URI_POLICY, USERS, VOUCHER_SIGNER,
+};
+use crate::voucher::MintVoucher;
 use crate::ContractError;
-use cosmwasm_std::{entry_point, Addr, Coin};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, Addr, BankMsg, Coin};
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, StdResult};
 use cw2::set_contract_version;
-use cw721::{ContractInfoResponse, Cw721ReceiveMsg};
+use cw721::{
+    AllNftInfoResponse, ContractInfoResponse, Cw721Query, Cw721ReceiveMsg, NftInfoResponse,
+};
 use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
 use cw721_base::state::TokenInfo;
 use cw721_base::{ContractError as BaseError, Cw721Contract, MintMsg};
-use s1::{check_royalty_payment, OWNER_PERCENT, ROYALTY_FEE};
+use cw_utils::{must_pay, Expiration};
+use s1::{check_royalty_payment, OWNER_PERCENT, ROYALTY_FEE, ROYALTY_FEE_SCHEDULE};
 use s2::{check_payment, MIN_FEE};
-use s_std::{Response, SubMsg, MULTISIG, NATIVE_DENOM};
+use s_std::{error::FeeError, Response, SubMsg, MULTISIG, NATIVE_DENOM};
+use s_uri::UriPolicy;
 use url::Url;
 
 // version info for migration info
@@ -52,12 +65,20 @@ pub fn instantiate(
         return Err(ContractError::DescriptionTooLong {});
     }
 
-    Url::parse(&msg.collection_info.image)?;
+    let uri_policy = msg.uri_policy.unwrap_or_default();
+    uri_policy.validate(&msg.collection_info.image)?;
 
     if let Some(ref external_link) = msg.collection_info.external_link {
         Url::parse(external_link)?;
     }
 
+    if let Some(ref base_uri) = msg.collection_info.base_uri {
+        if !base_uri.contains("{id}") {
+            return Err(ContractError::InvalidBaseUri {});
+        }
+        uri_policy.validate(base_uri)?;
+    }
+
     deps.api
         .addr_validate(&msg.collection_info.royalty_address)?;
     deps.api.addr_validate(&msg.collection_info.creator)?;
@@ -68,10 +89,18 @@ pub fn instantiate(
         image: msg.collection_info.image,
         external_link: msg.collection_info.external_link,
         royalty_address: msg.collection_info.royalty_address,
+        base_uri: msg.collection_info.base_uri,
     };
 
     COLLECTION_INFO.save(deps.storage, &collection_info)?;
 
+    if let Some(blind_box) = msg.blind_box {
+        uri_policy.validate(&blind_box.placeholder_uri)?;
+        BLIND_BOX.save(deps.storage, &blind_box)?;
+    }
+
+    URI_POLICY.save(deps.storage, &uri_policy)?;
+
     let mut rsp = Response::default();
     rsp.messages = msgs;
 
@@ -110,6 +139,17 @@ pub fn execute(
             msg,
         } => execute_send_nft(env, contract, token_id, msg),
         ExecuteMsg::Mint(msg) => execute_mint(env, msg),
+        ExecuteMsg::Burn { token_id } => execute_burn(env, token_id),
+        ExecuteMsg::Reveal { base_uri } => execute_reveal(env, base_uri),
+        ExecuteMsg::SetVoucherSigner { pubkey } => execute_set_voucher_signer(env, pubkey),
+        ExecuteMsg::RedeemVoucher { voucher, signature } => {
+            execute_redeem_voucher(env, voucher, signature)
+        }
+        ExecuteMsg::SetUser {
+            token_id,
+            user,
+            expires,
+        } => execute_set_user(env, token_id, user, expires),
         _ => {
             match S721Contract::default().execute(
                 env.deps,
@@ -147,15 +187,19 @@ pub fn execute_transfer_nft(
     let ExecuteEnv { deps, env, info } = env;
     let royalty_address = COLLECTION_INFO.load(deps.storage)?.royalty_address;
 
-    let msgs = _transfer_nft(deps, &env, &info, &recipient, &token_id, &royalty_address)?;
+    let (msgs, owner, royalty_fee) =
+        _transfer_nft(deps, &env, &info, &recipient, &token_id, &royalty_address)?;
+    let event = TransferEvent {
+        from: Some(owner.as_ref()),
+        to: Some(&recipient),
+        token_id: &token_id,
+    };
     let mut rsp = Response::new()
+        .add_event(event.to_event(info.sender.as_ref()))
         .add_attribute("action", "transfer_nft")
-        .add_attribute("sender", info.sender)
-        .add_attribute("recipient", recipient)
-        .add_attribute("token_id", token_id)
         .add_attribute(
             "royalty_fee",
-            Coin::new(ROYALTY_FEE, NATIVE_DENOM).to_string(),
+            Coin::new(royalty_fee, NATIVE_DENOM).to_string(),
         )
         .add_attribute("royalty_address", royalty_address)
         .add_attribute("royalty_share", OWNER_PERCENT.to_string());
@@ -173,7 +217,8 @@ pub fn execute_send_nft(
 
     // Transfer token
     let royalty_address = COLLECTION_INFO.load(deps.storage)?.royalty_address;
-    let mut msgs = _transfer_nft(deps, &env, &info, &contract, &token_id, &royalty_address)?;
+    let (mut msgs, owner, royalty_fee) =
+        _transfer_nft(deps, &env, &info, &contract, &token_id, &royalty_address)?;
     msgs.push(SubMsg::new(
         Cw721ReceiveMsg {
             sender: info.sender.to_string(),
@@ -183,14 +228,17 @@ pub fn execute_send_nft(
         .into_cosmos_msg(contract.clone())?,
     ));
 
+    let event = TransferEvent {
+        from: Some(owner.as_ref()),
+        to: Some(&contract),
+        token_id: &token_id,
+    };
     let mut rsp = Response::new()
+        .add_event(event.to_event(info.sender.as_ref()))
         .add_attribute("action", "send_nft")
-        .add_attribute("sender", info.sender)
-        .add_attribute("recipient", contract)
-        .add_attribute("token_id", token_id)
         .add_attribute(
             "royalty_fee",
-            Coin::new(ROYALTY_FEE, NATIVE_DENOM).to_string(),
+            Coin::new(royalty_fee, NATIVE_DENOM).to_string(),
         )
         .add_attribute("royalty_address", royalty_address)
         .add_attribute("royalty_share", OWNER_PERCENT.to_string());
@@ -212,27 +260,17 @@ pub fn execute_mint(env: ExecuteEnv, msg: MintMsg<Empty>) -> Result<Response, Co
     let multisig = Addr::unchecked(MULTISIG);
     let msgs = vec![check_payment(&info, MIN_FEE, multisig)?];
 
-    // create the token
-    let token = TokenInfo {
-        owner: deps.api.addr_validate(&msg.owner)?,
-        approvals: vec![],
-        token_uri: msg.token_uri,
-        extension: msg.extension,
-    };
-    S721Contract::default()
-        .tokens
-        .update(deps.storage, &msg.token_id, |old| match old {
-            Some(_) => Err(ContractError::Claimed {}),
-            None => Ok(token),
-        })?;
-
-    S721Contract::default().increment_tokens(deps.storage)?;
+    let owner = deps.api.addr_validate(&msg.owner)?;
+    _create_token(deps, owner, &msg.token_id, msg.token_uri)?;
 
+    let event = TransferEvent {
+        from: None,
+        to: Some(&msg.owner),
+        token_id: &msg.token_id,
+    };
     let mut rsp = Response::new()
+        .add_event(event.to_event(info.sender.as_ref()))
         .add_attribute("action", "mint")
-        .add_attribute("minter", info.sender)
-        .add_attribute("owner", msg.owner)
-        .add_attribute("token_id", msg.token_id)
         .add_attribute("mint_fee", info.funds[0].to_string())
         .add_attribute("payment_address", MULTISIG);
     rsp.messages = msgs;
@@ -240,10 +278,214 @@ pub fn execute_mint(env: ExecuteEnv, msg: MintMsg<Empty>) -> Result<Response, Co
     Ok(rsp)
 }
 
+pub fn execute_reveal(env: ExecuteEnv, base_uri: String) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    let creator = COLLECTION_INFO.load(deps.storage)?.creator;
+    if info.sender != creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let blind_box = BLIND_BOX
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotBlindBox {})?;
+    if REVEALED_BASE_URI.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+
+    if !base_uri.contains("{id}") {
+        return Err(ContractError::InvalidBaseUri {});
+    }
+    _uri_policy(deps.as_ref())?.validate(&base_uri)?;
+    REVEALED_BASE_URI.save(deps.storage, &base_uri)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal")
+        .add_attribute("base_uri", base_uri)
+        .add_attribute(
+            "provenance_hash",
+            blind_box.provenance_hash.unwrap_or_default(),
+        ))
+}
+
+pub fn execute_set_voucher_signer(
+    env: ExecuteEnv,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    let minter = S721Contract::default().minter.load(deps.storage)?;
+    if info.sender != minter {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &pubkey {
+        // Compressed or uncompressed secp256k1 public key
+        Some(pubkey) if pubkey.len() != 33 && pubkey.len() != 65 => {
+            return Err(ContractError::InvalidPubkey {})
+        }
+        Some(pubkey) => VOUCHER_SIGNER.save(deps.storage, pubkey)?,
+        None => VOUCHER_SIGNER.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_voucher_signer")
+        .add_attribute(
+            "pubkey",
+            pubkey.map(|key| key.to_base64()).unwrap_or_default(),
+        ))
+}
+
+pub fn execute_redeem_voucher(
+    env: ExecuteEnv,
+    voucher: MintVoucher,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    if voucher.expires.is_expired(&env.block) {
+        return Err(ContractError::VoucherExpired {});
+    }
+    let signer = VOUCHER_SIGNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoVoucherSigner {})?;
+    let hash = voucher.hash(&env.contract.address)?;
+    if !deps
+        .api
+        .secp256k1_verify(&hash, &signature, &signer)
+        .map_err(|_| ContractError::InvalidSignature {})?
+    {
+        return Err(ContractError::InvalidSignature {});
+    }
+    if REDEEMED_VOUCHERS.has(deps.storage, &hash) {
+        return Err(ContractError::VoucherRedeemed {});
+    }
+
+    // Buyer pays the minting fee to multisig and the price to the minter
+    let expected = voucher
+        .price
+        .checked_add(MIN_FEE.into())
+        .map_err(FeeError::from)?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != expected {
+        return Err(ContractError::IncorrectPayment {
+            expected: expected.u128(),
+            received: payment.u128(),
+        });
+    }
+    let minter = S721Contract::default().minter.load(deps.storage)?;
+    let mut msgs = vec![SubMsg::new(BankMsg::Send {
+        to_address: MULTISIG.to_string(),
+        amount: coins(MIN_FEE, NATIVE_DENOM),
+    })];
+    if !voucher.price.is_zero() {
+        msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: minter.to_string(),
+            amount: coins(voucher.price.u128(), NATIVE_DENOM),
+        }));
+    }
+
+    REDEEMED_VOUCHERS.save(deps.storage, &hash, &true)?;
+    _create_token(
+        deps,
+        info.sender.clone(),
+        &voucher.token_id,
+        voucher.token_uri,
+    )?;
+
+    let event = TransferEvent {
+        from: None,
+        to: Some(info.sender.as_ref()),
+        token_id: &voucher.token_id,
+    };
+    let mut rsp = Response::new()
+        .add_event(event.to_event(info.sender.as_ref()))
+        .add_attribute("action", "redeem_voucher")
+        .add_attribute("mint_fee", Coin::new(MIN_FEE, NATIVE_DENOM).to_string())
+        .add_attribute("payment_address", MULTISIG)
+        .add_attribute(
+            "price",
+            Coin::new(voucher.price.u128(), NATIVE_DENOM).to_string(),
+        );
+    rsp.messages = msgs;
+
+    Ok(rsp)
+}
+
+pub fn execute_set_user(
+    env: ExecuteEnv,
+    token_id: String,
+    user: Option<String>,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let token = S721Contract::default()
+        .tokens
+        .load(deps.storage, &token_id)?;
+    _check_can_send(deps.as_ref(), &env, &info, &token)?;
+
+    match &user {
+        Some(_) if expires.is_expired(&env.block) => return Err(ContractError::Expired {}),
+        Some(user) => USERS.save(
+            deps.storage,
+            &token_id,
+            &TokenUser {
+                user: deps.api.addr_validate(user)?,
+                expires,
+            },
+        )?,
+        None => USERS.remove(deps.storage, &token_id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_user")
+        .add_attribute("token_id", token_id)
+        .add_attribute("user", user.unwrap_or_default())
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub fn execute_burn(env: ExecuteEnv, token_id: String) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let contract = S721Contract::default();
+
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    _check_can_send(deps.as_ref(), &env, &info, &token)?;
+
+    contract.tokens.remove(deps.storage, &token_id)?;
+    contract.decrement_tokens(deps.storage)?;
+    USERS.remove(deps.storage, &token_id);
+
+    let event = TransferEvent {
+        from: Some(token.owner.as_ref()),
+        to: None,
+        token_id: &token_id,
+    };
+    Ok(Response::new()
+        .add_event(event.to_event(info.sender.as_ref()))
+        .add_attribute("action", "burn"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::CollectionInfo {} => to_binary(&query_config(deps)?),
+        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_binary(&query_all_nft_info(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::BlindBox {} => to_binary(&BlindBoxResponse {
+            blind_box: BLIND_BOX.may_load(deps.storage)?,
+            base_uri: REVEALED_BASE_URI.may_load(deps.storage)?,
+        }),
+        QueryMsg::UriPolicy {} => to_binary(&_uri_policy(deps)?),
+        QueryMsg::VoucherSigner {} => to_binary(&VoucherSignerResponse {
+            pubkey: VOUCHER_SIGNER.may_load(deps.storage)?,
+        }),
+        QueryMsg::UserOf { token_id } => to_binary(&query_user_of(deps, env, token_id)?),
         _ => S721Contract::default().query(deps, env, msg.into()),
     }
 }
@@ -255,6 +497,7 @@ fn query_config(deps: Deps) -> StdResult<CollectionInfoResponse> {
         image,
         external_link,
         royalty_address,
+        base_uri,
     } = COLLECTION_INFO.load(deps.storage)?;
 
     Ok(CollectionInfoResponse {
@@ -263,6 +506,7 @@ fn query_config(deps: Deps) -> StdResult<CollectionInfoResponse> {
         image,
         external_link,
         royalty_address,
+        base_uri,
         multisig: MULTISIG.to_string(),
         min_fee: Coin::new(MIN_FEE, NATIVE_DENOM),
         royalty_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
@@ -270,8 +514,60 @@ fn query_config(deps: Deps) -> StdResult<CollectionInfoResponse> {
     })
 }
 
+fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse<Empty>> {
+    let mut info = S721Contract::default().nft_info(deps, token_id.clone())?;
+    info.token_uri = _token_uri(deps, &token_id, info.token_uri)?;
+    Ok(info)
+}
+
+fn query_all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<AllNftInfoResponse<Empty>> {
+    let mut res =
+        S721Contract::default().all_nft_info(deps, env, token_id.clone(), include_expired)?;
+    res.info.token_uri = _token_uri(deps, &token_id, res.info.token_uri)?;
+    Ok(res)
+}
+
+fn query_user_of(deps: Deps, env: Env, token_id: String) -> StdResult<UserOfResponse> {
+    // Fails for unknown tokens
+    S721Contract::default()
+        .tokens
+        .load(deps.storage, &token_id)?;
+    Ok(match USERS.may_load(deps.storage, &token_id)? {
+        Some(user) if !user.expires.is_expired(&env.block) => UserOfResponse {
+            user: Some(user.user.to_string()),
+            expires: Some(user.expires),
+        },
+        _ => UserOfResponse {
+            user: None,
+            expires: None,
+        },
+    })
+}
+
 /**********************************HELPERS*************************************/
 
+/// Token uri shown by queries, the placeholder until a blind box is revealed.
+/// Tokens minted without a uri are expanded from the collection base uri.
+fn _token_uri(deps: Deps, token_id: &str, token_uri: Option<String>) -> StdResult<Option<String>> {
+    let blind_box = match BLIND_BOX.may_load(deps.storage)? {
+        Some(blind_box) => blind_box,
+        None if token_uri.is_some() => return Ok(token_uri),
+        None => {
+            let base_uri = COLLECTION_INFO.load(deps.storage)?.base_uri;
+            return Ok(base_uri.map(|base_uri| base_uri.replace("{id}", token_id)));
+        }
+    };
+    Ok(Some(match REVEALED_BASE_URI.may_load(deps.storage)? {
+        Some(base_uri) => base_uri.replace("{id}", token_id),
+        None => blind_box.placeholder_uri,
+    }))
+}
+
 fn _transfer_nft(
     deps: DepsMut,
     env: &Env,
@@ -279,7 +575,7 @@ fn _transfer_nft(
     recipient: &str,
     token_id: &str,
     royalty_address: &str,
-) -> Result<Vec<SubMsg>, ContractError> {
+) -> Result<(Vec<SubMsg>, Addr, u128), ContractError> {
     let mut token = S721Contract::default()
         .tokens
         .load(deps.storage, token_id)?;
@@ -288,20 +584,56 @@ fn _transfer_nft(
     _check_can_send(deps.as_ref(), env, info, &token)?;
 
     // Royalty payment
+    let royalty_fee = ROYALTY_FEE_SCHEDULE.fee(1)?;
     let msgs = check_royalty_payment(
         info,
-        ROYALTY_FEE,
+        royalty_fee,
         // Has been validated at contract instantiation
         Addr::unchecked(royalty_address),
     )?;
 
-    // set owner and remove existing approvals
+    // set owner and remove existing approvals and user
+    let owner = token.owner;
     token.owner = deps.api.addr_validate(recipient)?;
     token.approvals = vec![];
     S721Contract::default()
         .tokens
         .save(deps.storage, token_id, &token)?;
-    Ok(msgs)
+    USERS.remove(deps.storage, token_id);
+    Ok((msgs, owner, royalty_fee))
+}
+
+/// Collections instantiated before uri policies use the default policy
+fn _uri_policy(deps: Deps) -> StdResult<UriPolicy> {
+    Ok(URI_POLICY.may_load(deps.storage)?.unwrap_or_default())
+}
+
+/// Create the token, return an error if the token id is already claimed
+fn _create_token(
+    deps: DepsMut,
+    owner: Addr,
+    token_id: &str,
+    token_uri: Option<String>,
+) -> Result<(), ContractError> {
+    if let Some(ref token_uri) = token_uri {
+        _uri_policy(deps.as_ref())?.validate(token_uri)?;
+    }
+
+    let token = TokenInfo {
+        owner,
+        approvals: vec![],
+        token_uri,
+        extension: Empty {},
+    };
+    S721Contract::default()
+        .tokens
+        .update(deps.storage, token_id, |old| match old {
+            Some(_) => Err(ContractError::Claimed {}),
+            None => Ok(token),
+        })?;
+
+    S721Contract::default().increment_tokens(deps.storage)?;
+    Ok(())
 }
 
 /// returns true iff the sender can transfer ownership of the token
@@ -345,12 +677,14 @@ fn _check_can_send(
 mod tests {
     use super::*;
 
-    use crate::state::CollectionInfo;
+    use crate::state::{BlindBox, CollectionInfo};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary, BankMsg, WasmMsg};
+    use cosmwasm_std::{from_binary, to_vec, Event, Uint128, WasmMsg};
     use cw721::{Cw721Query, NftInfoResponse, OwnerOfResponse};
+    use cw_utils::Expiration;
     use s_std::error::FeeError;
     use s_std::{create_fund_community_pool_msg, CosmosMsg, SubMsg, NATIVE_DENOM};
+    use s_uri::UriError;
 
     fn setup_contract(deps: DepsMut<'_>, minter: String, creator: String) {
         let collection = String::from("collection0");
@@ -365,7 +699,10 @@ mod tests {
                 image: "https://example.com/image.png".to_string(),
                 external_link: Some("https://example.com/external.html".to_string()),
                 royalty_address: creator,
+                base_uri: None,
             },
+            blind_box: None,
+            uri_policy: None,
         };
         instantiate(deps, mock_env(), info, msg).unwrap();
     }
@@ -388,7 +725,10 @@ mod tests {
                 image: "https://example.com/image.png".to_string(),
                 external_link: Some("https://example.com/external.html".to_string()),
                 royalty_address: creator.clone(),
+                base_uri: None,
             },
+            blind_box: None,
+            uri_policy: None,
         };
 
         // Error: Insufficient minting fee
@@ -479,10 +819,14 @@ mod tests {
             amount: coins(MIN_FEE, NATIVE_DENOM.to_string()),
         });
         let mut rsp = Response::new()
+            .add_event(
+                Event::new("mint_single")
+                    .add_attribute("operator", &minter)
+                    .add_attribute("token_id", &token_id)
+                    .add_attribute("amount", "1")
+                    .add_attribute("to", &user),
+            )
             .add_attribute("action", "mint")
-            .add_attribute("minter", minter.clone())
-            .add_attribute("owner", user.clone())
-            .add_attribute("token_id", token_id.clone())
             .add_attribute("mint_fee", info.funds[0].to_string())
             .add_attribute("payment_address", MULTISIG);
         rsp.messages = vec![bank_msg];
@@ -601,10 +945,15 @@ mod tests {
         let community_msg =
             SubMsg::new(create_fund_community_pool_msg(coins(50u128, NATIVE_DENOM)));
         let mut rsp = Response::new()
+            .add_event(
+                Event::new("transfer_single")
+                    .add_attribute("operator", &user1)
+                    .add_attribute("token_id", &token_id)
+                    .add_attribute("amount", "1")
+                    .add_attribute("from", &user1)
+                    .add_attribute("to", &user2),
+            )
             .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", user1)
-            .add_attribute("recipient", user2.clone())
-            .add_attribute("token_id", token_id.clone())
             .add_attribute("royalty_fee", info.funds[0].to_string())
             .add_attribute("royalty_address", creator)
             .add_attribute("royalty_share", OWNER_PERCENT.to_string());
@@ -707,10 +1056,15 @@ mod tests {
         }
         let info = mock_info(user1.as_ref(), &coins(ROYALTY_FEE, NATIVE_DENOM));
         let mut rsp = Response::new()
+            .add_event(
+                Event::new("transfer_single")
+                    .add_attribute("operator", &user1)
+                    .add_attribute("token_id", &token_id)
+                    .add_attribute("amount", "1")
+                    .add_attribute("from", &user1)
+                    .add_attribute("to", &contract),
+            )
             .add_attribute("action", "send_nft")
-            .add_attribute("sender", user1)
-            .add_attribute("recipient", contract.clone())
-            .add_attribute("token_id", token_id.clone())
             .add_attribute("royalty_fee", info.funds[0].to_string())
             .add_attribute("royalty_address", creator)
             .add_attribute("royalty_share", OWNER_PERCENT.to_string());
@@ -733,4 +1087,619 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_burn() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let user1 = String::from("user1");
+        setup_contract(deps.as_mut(), minter.clone(), String::from("creator"));
+        let contract = S721Contract::default();
+
+        let token_id = "token".to_string();
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Empty> {
+            token_id: token_id.clone(),
+            owner: user1.clone(),
+            token_uri: None,
+            extension: Empty {},
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+            mint_msg,
+        )
+        .unwrap();
+
+        // random cannot burn
+        let burn_msg = ExecuteMsg::Burn {
+            token_id: token_id.clone(),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("random", &[]),
+                burn_msg.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // owner can burn
+        let rsp = Response::new()
+            .add_event(
+                Event::new("burn_single")
+                    .add_attribute("operator", &user1)
+                    .add_attribute("token_id", &token_id)
+                    .add_attribute("amount", "1")
+                    .add_attribute("from", &user1),
+            )
+            .add_attribute("action", "burn");
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(user1.as_ref(), &[]),
+                burn_msg
+            )
+            .unwrap(),
+            rsp
+        );
+        assert_eq!(0, contract.token_count.load(&deps.storage).unwrap());
+    }
+
+    #[test]
+    fn test_set_user() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        setup_contract(deps.as_mut(), minter.clone(), String::from("creator"));
+
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Empty> {
+            token_id: "token".to_string(),
+            owner: "owner".to_string(),
+            token_uri: None,
+            extension: Empty {},
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+            mint_msg,
+        )
+        .unwrap();
+        let user_of = |deps: Deps, env: Env| -> UserOfResponse {
+            let msg = QueryMsg::UserOf {
+                token_id: "token".to_string(),
+            };
+            from_binary(&query(deps, env, msg).unwrap()).unwrap()
+        };
+
+        let expires = Expiration::AtHeight(mock_env().block.height + 100);
+        let set_msg = ExecuteMsg::SetUser {
+            token_id: "token".to_string(),
+            user: Some("gamer".to_string()),
+            expires,
+        };
+
+        // Error: only the owner or approved can set the user
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gamer", &[]),
+                set_msg.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // Error: rental already expired
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::SetUser {
+                    token_id: "token".to_string(),
+                    user: Some("gamer".to_string()),
+                    expires: Expiration::AtHeight(mock_env().block.height),
+                }
+            ),
+            Err(ContractError::Expired {})
+        ));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            set_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            user_of(deps.as_ref(), mock_env()),
+            UserOfResponse {
+                user: Some("gamer".to_string()),
+                expires: Some(expires),
+            }
+        );
+
+        // ownership does not move
+        let owner = S721Contract::default()
+            .owner_of(deps.as_ref(), mock_env(), "token".to_string(), false)
+            .unwrap();
+        assert_eq!(owner.owner, "owner");
+
+        // user is cleared once expired
+        let mut env = mock_env();
+        env.block.height += 100;
+        assert_eq!(user_of(deps.as_ref(), env).user, None);
+
+        // user is cleared on transfer
+        let transfer_msg = ExecuteMsg::TransferNft {
+            recipient: "buyer".to_string(),
+            token_id: "token".to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+            transfer_msg,
+        )
+        .unwrap();
+        assert_eq!(user_of(deps.as_ref(), mock_env()).user, None);
+
+        // new owner can rent the token again, or remove the user
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), set_msg).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[]),
+            ExecuteMsg::SetUser {
+                token_id: "token".to_string(),
+                user: None,
+                expires,
+            },
+        )
+        .unwrap();
+        assert_eq!(user_of(deps.as_ref(), mock_env()).user, None);
+    }
+
+    #[test]
+    fn test_base_uri() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let creator = String::from("creator");
+        let mut msg = InstantiateMsg {
+            name: String::from("collection0"),
+            symbol: String::from("DOC"),
+            minter: minter.clone(),
+            collection_info: CollectionInfo {
+                creator: creator.clone(),
+                description: String::from("Document"),
+                image: "https://example.com/image.png".to_string(),
+                external_link: None,
+                royalty_address: creator,
+                base_uri: Some("ipfs://hash/".to_string()),
+            },
+            blind_box: None,
+            uri_policy: None,
+        };
+        let info = mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM));
+
+        // Error: base uri is not a template
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+            Err(ContractError::InvalidBaseUri {})
+        ));
+
+        msg.collection_info.base_uri = Some("ipfs://hash/{id}.json".to_string());
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        for (token_id, token_uri) in [("1", None), ("2", Some("https://example.com/2"))] {
+            let mint_msg = ExecuteMsg::Mint(MintMsg::<Empty> {
+                token_id: token_id.to_string(),
+                owner: String::from("user"),
+                token_uri: token_uri.map(String::from),
+                extension: Empty {},
+            });
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+                mint_msg,
+            )
+            .unwrap();
+        }
+
+        // token uri is expanded from the base uri, unless minted with its own uri
+        let token_uri = |token_id: &str| {
+            let msg = QueryMsg::NftInfo {
+                token_id: token_id.to_string(),
+            };
+            let res: NftInfoResponse<Empty> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            res.token_uri
+        };
+        assert_eq!(token_uri("1"), Some("ipfs://hash/1.json".to_string()));
+        assert_eq!(token_uri("2"), Some("https://example.com/2".to_string()));
+    }
+
+    #[test]
+    fn test_uri_policy() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let creator = String::from("creator");
+        let mut msg = InstantiateMsg {
+            name: String::from("collection0"),
+            symbol: String::from("DOC"),
+            minter: minter.clone(),
+            collection_info: CollectionInfo {
+                creator: creator.clone(),
+                description: String::from("Document"),
+                image: "http://example.com/image.png".to_string(),
+                external_link: None,
+                royalty_address: creator,
+                base_uri: None,
+            },
+            blind_box: None,
+            uri_policy: None,
+        };
+        let info = mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM));
+
+        // Error: default policy does not allow http
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+            Err(ContractError::Uri(UriError::SchemeNotAllowed { .. }))
+        ));
+
+        let uri_policy = UriPolicy {
+            schemes: vec!["http".to_string(), "ipfs".to_string()],
+            max_length: None,
+            hosts: Some(vec!["example.com".to_string()]),
+        };
+        msg.uri_policy = Some(uri_policy.clone());
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res: UriPolicy =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::UriPolicy {}).unwrap())
+                .unwrap();
+        assert_eq!(res, uri_policy);
+
+        let mint = |deps: DepsMut, token_uri: &str| {
+            let msg = ExecuteMsg::Mint(MintMsg::<Empty> {
+                token_id: "token".to_string(),
+                owner: String::from("user"),
+                token_uri: Some(token_uri.to_string()),
+                extension: Empty {},
+            });
+            execute(
+                deps,
+                mock_env(),
+                mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+                msg,
+            )
+        };
+
+        // Error: token uri host is not allowed
+        assert!(matches!(
+            mint(deps.as_mut(), "http://other.com/token.json"),
+            Err(ContractError::Uri(UriError::HostNotAllowed { .. }))
+        ));
+        mint(deps.as_mut(), "http://example.com/token.json").unwrap();
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let creator = String::from("creator");
+        let msg = InstantiateMsg {
+            name: String::from("collection0"),
+            symbol: String::from("DOC"),
+            minter: minter.clone(),
+            collection_info: CollectionInfo {
+                creator: creator.clone(),
+                description: String::from("Document"),
+                image: "https://example.com/image.png".to_string(),
+                external_link: None,
+                royalty_address: creator.clone(),
+                base_uri: None,
+            },
+            blind_box: Some(BlindBox {
+                placeholder_uri: "ipfs://placeholder.json".to_string(),
+                provenance_hash: Some("provenance".to_string()),
+            }),
+            uri_policy: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+            msg,
+        )
+        .unwrap();
+
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Empty> {
+            token_id: "1".to_string(),
+            owner: String::from("user"),
+            token_uri: None,
+            extension: Empty {},
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &coins(MIN_FEE, NATIVE_DENOM)),
+            mint_msg,
+        )
+        .unwrap();
+        let nft_info = |deps: Deps| -> NftInfoResponse<Empty> {
+            from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        // placeholder until revealed
+        assert_eq!(
+            nft_info(deps.as_ref()).token_uri,
+            Some("ipfs://placeholder.json".to_string())
+        );
+
+        // Error: base uri without the token id
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&creator, &[]),
+                ExecuteMsg::Reveal {
+                    base_uri: "ipfs://metadata/".to_string(),
+                }
+            ),
+            Err(ContractError::InvalidBaseUri {})
+        ));
+
+        // Error: only the creator can reveal
+        let reveal_msg = ExecuteMsg::Reveal {
+            base_uri: "ipfs://metadata/{id}.json".to_string(),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&minter, &[]),
+                reveal_msg.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&creator, &[]),
+            reveal_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            nft_info(deps.as_ref()).token_uri,
+            Some("ipfs://metadata/1.json".to_string())
+        );
+        let res: AllNftInfoResponse<Empty> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllNftInfo {
+                    token_id: "1".to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.info.token_uri,
+            Some("ipfs://metadata/1.json".to_string())
+        );
+        let res: BlindBoxResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::BlindBox {}).unwrap()).unwrap();
+        assert_eq!(res.base_uri, Some("ipfs://metadata/{id}.json".to_string()));
+
+        // Error: reveal is final
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&creator, &[]),
+                reveal_msg
+            ),
+            Err(ContractError::AlreadyRevealed {})
+        ));
+    }
+
+    #[test]
+    fn test_redeem_voucher() {
+        use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        setup_contract(deps.as_mut(), minter.clone(), String::from("creator"));
+
+        let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+        let pubkey = Binary::from(key.verifying_key().to_bytes().as_slice());
+        let voucher = MintVoucher {
+            token_id: "token".to_string(),
+            token_uri: Some("https://example.com/token_uri".to_string()),
+            price: Uint128::new(10_000_000),
+            expires: Expiration::AtHeight(mock_env().block.height + 100),
+            nonce: 1,
+        };
+        // signed off-chain over contract address ++ json(voucher)
+        let contract_addr = mock_env().contract.address;
+        let sign = |voucher: &MintVoucher| {
+            let preimage = [contract_addr.as_bytes(), &to_vec(voucher).unwrap()].concat();
+            let signature: Signature = key.sign(&preimage);
+            Binary::from(signature.as_ref())
+        };
+        let signature = sign(&voucher);
+        let redeem_msg = ExecuteMsg::RedeemVoucher {
+            voucher: voucher.clone(),
+            signature: signature.clone(),
+        };
+        let payment = coins(MIN_FEE + 10_000_000, NATIVE_DENOM);
+
+        // Error: no voucher signer
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::NoVoucherSigner {})
+        ));
+
+        // Error: only minter can set the voucher signer
+        let set_msg = ExecuteMsg::SetVoucherSigner {
+            pubkey: Some(pubkey.clone()),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &[]),
+                set_msg.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+        execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), set_msg).unwrap();
+        let res: VoucherSignerResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::VoucherSigner {}).unwrap())
+                .unwrap();
+        assert_eq!(res.pubkey, Some(pubkey));
+
+        // Error: voucher altered by the buyer
+        let mut cheap = voucher.clone();
+        cheap.price = Uint128::zero();
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &coins(MIN_FEE, NATIVE_DENOM)),
+                ExecuteMsg::RedeemVoucher {
+                    voucher: cheap,
+                    signature: signature.clone(),
+                }
+            ),
+            Err(ContractError::InvalidSignature {})
+        ));
+
+        // Error: minting fee not paid on top of the price
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &coins(10_000_000, NATIVE_DENOM)),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        // Error: expired voucher
+        let mut env = mock_env();
+        env.block.height += 100;
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env,
+                mock_info("buyer", &payment),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::VoucherExpired {})
+        ));
+
+        // buyer mints the token, paying the minting fee and the price
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &payment),
+            redeem_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: MULTISIG.to_string(),
+                    amount: coins(MIN_FEE, NATIVE_DENOM),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: minter,
+                    amount: coins(10_000_000, NATIVE_DENOM),
+                }),
+            ]
+        );
+        let info = S721Contract::default()
+            .nft_info(deps.as_ref(), "token".to_string())
+            .unwrap();
+        assert_eq!(info.token_uri, voucher.token_uri);
+        let owner = S721Contract::default()
+            .owner_of(deps.as_ref(), mock_env(), "token".to_string(), true)
+            .unwrap();
+        assert_eq!(owner.owner, "buyer");
+
+        // Error: voucher cannot be redeemed twice
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                redeem_msg.clone()
+            ),
+            Err(ContractError::VoucherRedeemed {})
+        ));
+
+        // Error: nor after the token is burned
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[]),
+            ExecuteMsg::Burn {
+                token_id: "token".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &payment),
+                redeem_msg
+            ),
+            Err(ContractError::VoucherRedeemed {})
+        ));
+
+        // a new voucher for the burned token id mints it again
+        let voucher = MintVoucher {
+            nonce: 2,
+            ..voucher
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &payment),
+            ExecuteMsg::RedeemVoucher {
+                signature: sign(&voucher),
+                voucher,
+            },
+        )
+        .unwrap();
+        let owner = S721Contract::default()
+            .owner_of(deps.as_ref(), mock_env(), "token".to_string(), true)
+            .unwrap();
+        assert_eq!(owner.owner, "buyer");
+    }
 }
@@ -2,6 +2,7 @@ use cosmwasm_std::StdError;
 use cw721_base::ContractError as Cw721ContractError;
 use cw_utils::PaymentError;
 use s_std::error::FeeError;
+use s_uri::UriError;
 use thiserror::Error;
 use url::ParseError;
 
@@ -31,6 +32,33 @@ pub enum ContractError {
     #[error("Description too long")]
     DescriptionTooLong {},
 
+    #[error("Base uri must contain {{id}}")]
+    InvalidBaseUri {},
+
+    #[error("Collection is not a blind box")]
+    NotBlindBox {},
+
+    #[error("Collection already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Voucher signer not set")]
+    NoVoucherSigner {},
+
+    #[error("Invalid secp256k1 public key")]
+    InvalidPubkey {},
+
+    #[error("Invalid voucher signature")]
+    InvalidSignature {},
+
+    #[error("Voucher expired")]
+    VoucherExpired {},
+
+    #[error("Voucher already redeemed")]
+    VoucherRedeemed {},
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
@@ -39,6 +67,9 @@ pub enum ContractError {
 
     #[error("{0}")]
     Parse(#[from] ParseError),
+
+    #[error("{0}")]
+    Uri(#[from] UriError),
 }
 
 impl From<ContractError> for Cw721ContractError {
@@ -0,0 +1,40 @@
+use cosmwasm_std::Event;
+
+pub struct TransferEvent<'a> {
+    pub from: Option<&'a str>,
+    pub to: Option<&'a str>,
+    pub token_id: &'a str,
+}
+
+pub trait ToEvent {
+    /// Create a wasm event
+    fn to_event(&self, operator: &str) -> Event;
+}
+
+impl<'a> TransferEvent<'a> {
+    /// Mint when from is None, burn when to is None, transfer otherwise
+    fn kind(&self) -> &'static str {
+        match (self.from, self.to) {
+            (None, Some(_)) => "mint",
+            (Some(_), None) => "burn",
+            _ => "transfer",
+        }
+    }
+}
+
+impl<'a> ToEvent for TransferEvent<'a> {
+    /// Emitted as `wasm-{transfer,mint,burn}_single`, same schema as s1155
+    fn to_event(&self, operator: &str) -> Event {
+        let mut event = Event::new(format!("{}_single", self.kind()))
+            .add_attribute("operator", operator)
+            .add_attribute("token_id", self.token_id)
+            .add_attribute("amount", "1");
+        if let Some(from) = self.from {
+            event = event.add_attribute("from", from);
+        }
+        if let Some(to) = self.to {
+            event = event.add_attribute("to", to);
+        }
+        event
+    }
+}
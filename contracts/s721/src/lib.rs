@@ -1,6 +1,8 @@
 pub mod contract;
 mod error;
+pub mod event;
 pub mod msg;
 pub mod state;
+pub mod voucher;
 
 pub use crate::error::ContractError;
@@ -1,8 +1,10 @@
-use crate::state::CollectionInfo;
+use crate::state::{BlindBox, CollectionInfo};
+use crate::voucher::MintVoucher;
 use cosmwasm_std::{Binary, Coin, Empty};
 use cw721_base::msg::{ExecuteMsg as Cw721ExecuteMsg, QueryMsg as Cw721QueryMsg};
 use cw721_base::MintMsg;
 use cw_utils::Expiration;
+use s_uri::UriPolicy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +14,10 @@ pub struct InstantiateMsg {
     pub symbol: String,
     pub minter: String,
     pub collection_info: CollectionInfo,
+    /// Hide the token metadata behind a placeholder until revealed
+    pub blind_box: Option<BlindBox>,
+    /// Defaults to `ipfs`, `ar` and `https` uris
+    pub uri_policy: Option<UriPolicy>,
 }
 
 // pub type ExecuteMsg = cw721_base::ExecuteMsg<Empty>;
@@ -52,6 +58,32 @@ pub enum ExecuteMsg {
     Burn {
         token_id: String,
     },
+
+    /// Reveal a blind box collection, can only be called once by the collection creator.
+    /// `base_uri` is a uri template like the collection `base_uri`, it must contain `{id}`.
+    Reveal {
+        base_uri: String,
+    },
+
+    /// Set the compressed or uncompressed secp256k1 public key signing mint vouchers,
+    /// can only be called by the contract minter. None disables vouchers.
+    SetVoucherSigner {
+        pubkey: Option<Binary>,
+    },
+
+    /// Mint the voucher token to the sender, paying the minting fee and the voucher price
+    RedeemVoucher {
+        voucher: MintVoucher,
+        signature: Binary,
+    },
+
+    /// Grant the user role of a token until `expires`, without moving ownership.
+    /// Can be called by anyone able to transfer the token. None removes the user.
+    SetUser {
+        token_id: String,
+        user: Option<String>,
+        expires: Expiration,
+    },
 }
 
 impl From<ExecuteMsg> for Cw721ExecuteMsg<Empty> {
@@ -91,6 +123,7 @@ impl From<ExecuteMsg> for Cw721ExecuteMsg<Empty> {
             ExecuteMsg::RevokeAll { operator } => Cw721ExecuteMsg::RevokeAll { operator },
             ExecuteMsg::Mint(msg) => Cw721ExecuteMsg::Mint(msg),
             ExecuteMsg::Burn { token_id } => Self::Burn { token_id },
+            _ => unreachable!("cannot convert {:?} to Cw721ExecuteMsg", msg),
         }
     }
 }
@@ -137,6 +170,16 @@ pub enum QueryMsg {
     },
     Minter {},
     CollectionInfo {},
+    /// Blind box placeholder and revealed base uri
+    BlindBox {},
+    /// Validation policy of the token uris
+    UriPolicy {},
+    /// Public key signing mint vouchers
+    VoucherSigner {},
+    /// Current user of a token, None once the rental expired
+    UserOf {
+        token_id: String,
+    },
 }
 
 impl From<QueryMsg> for Cw721QueryMsg {
@@ -211,8 +254,27 @@ pub struct CollectionInfoResponse {
     pub image: String,
     pub external_link: Option<String>,
     pub royalty_address: String,
+    pub base_uri: Option<String>,
     pub multisig: String,
     pub min_fee: Coin,
     pub royalty_fee: Coin,
     pub royalty_share: u64,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlindBoxResponse {
+    pub blind_box: Option<BlindBox>,
+    /// None until revealed
+    pub base_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherSignerResponse {
+    pub pubkey: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserOfResponse {
+    pub user: Option<String>,
+    pub expires: Option<Expiration>,
+}
@@ -1,4 +1,7 @@
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Binary};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+use s_uri::UriPolicy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +12,40 @@ pub struct CollectionInfo {
     pub image: String,
     pub external_link: Option<String>,
     pub royalty_address: String,
+    /// Uri template for tokens minted without a uri, `{id}` is replaced by the token id
+    pub base_uri: Option<String>,
 }
 
 pub const COLLECTION_INFO: Item<CollectionInfo> = Item::new("collection_info");
+
+/// Validation policy of the collection image and token uris
+pub const URI_POLICY: Item<UriPolicy> = Item::new("uri_policy");
+
+/// Blind-box drop, every token shows `placeholder_uri` until the creator reveals the metadata
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlindBox {
+    pub placeholder_uri: String,
+    /// Hash of the metadata committed before the drop, so holders can check the reveal
+    pub provenance_hash: Option<String>,
+}
+
+pub const BLIND_BOX: Item<BlindBox> = Item::new("blind_box");
+
+/// Uri template of the revealed metadata, `{id}` is replaced by the token id
+pub const REVEALED_BASE_URI: Item<String> = Item::new("revealed_base_uri");
+
+/// Public key of the minter key signing mint vouchers
+pub const VOUCHER_SIGNER: Item<Binary> = Item::new("voucher_signer");
+
+// Voucher hash -> redeemed, kept after the token is burned
+pub const REDEEMED_VOUCHERS: Map<&[u8], bool> = Map::new("redeemed_vouchers");
+
+/// Rental of a token, the user can use the token without owning it until `expires`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenUser {
+    pub user: Addr,
+    pub expires: Expiration,
+}
+
+// Token id -> user, cleared on transfer and burn
+pub const USERS: Map<&str, TokenUser> = Map::new("users");
@@ -0,0 +1,21 @@
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use sign_auction::msg::{
+    AuctionMsg, AuctionResponse, AuctionsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(AuctionMsg), &out_dir);
+
+    export_schema(&schema_for!(AuctionResponse), &out_dir);
+    export_schema(&schema_for!(AuctionsResponse), &out_dir);
+}
@@ -0,0 +1,778 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut};
+use cosmwasm_std::{Env, MessageInfo, Order, StdResult, Uint128};
+use cw2::set_contract_version;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+use s1::sale_payment;
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use s_std::{Response, SubMsg, NATIVE_DENOM};
+
+use crate::msg::{
+    AuctionMsg, AuctionResponse, AuctionsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{Auction, AuctionKind, Bid, AUCTIONS, AUCTION_COUNT};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_auction";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// Longest auction and time extension, 30 days in seconds
+const MAX_DURATION: u64 = 30 * 24 * 60 * 60;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    AUCTION_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute_batch_receive(deps, env, info, msg),
+        ExecuteMsg::Bid { auction_id } => execute_bid(deps, env, info, auction_id),
+        ExecuteMsg::Settle { auction_id } => execute_settle(deps, env, auction_id),
+        ExecuteMsg::Cancel { auction_id } => execute_cancel(deps, env, info, auction_id),
+    }
+}
+
+/// s721 token sent with `SendNft`, sender of the nft contract is the seller
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.sender)?;
+    let nft = Nft::S721 {
+        contract: info.sender,
+        token_id: msg.token_id,
+    };
+    _create_auction(deps, env, seller, nft, &msg.msg)
+}
+
+/// s1155 tokens sent with `SendFrom`, previous owner of the tokens is the seller
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: vec![(msg.token_id, msg.amount)],
+    };
+    _create_auction(deps, env, seller, nft, &msg.msg)
+}
+
+/// s1155 tokens sent with `BatchSendFrom`, auctioned as a single lot
+pub fn execute_batch_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: BatchReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: msg.batch,
+    };
+    _create_auction(deps, env, seller, nft, &msg.msg)
+}
+
+pub fn execute_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if info.sender == auction.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time;
+    let amount = must_pay(&info, NATIVE_DENOM)?;
+    let min_bid = auction.min_bid(now)?;
+    if amount < min_bid {
+        return Err(ContractError::BidTooLow {
+            min: min_bid.u128(),
+            received: amount.u128(),
+        });
+    }
+
+    let rsp = Response::new()
+        .add_attribute("action", "bid")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", info.sender.as_str());
+
+    match auction.kind {
+        AuctionKind::English {
+            time_extension,
+            ref mut highest_bid,
+            ..
+        } => {
+            if now >= auction.end_time {
+                return Err(ContractError::AuctionEnded {});
+            }
+
+            // Refund the bid being replaced
+            let mut rsp =
+                rsp.add_attribute("amount", Coin::new(amount.u128(), NATIVE_DENOM).to_string());
+            if let Some(Bid { bidder, amount }) = highest_bid.take() {
+                rsp = rsp.add_submessage(_refund_msg(&bidder, amount));
+            }
+            *highest_bid = Some(Bid {
+                bidder: info.sender,
+                amount,
+            });
+
+            // Late bids extend the auction so other bidders can respond
+            if auction.end_time.seconds() - now.seconds() < time_extension {
+                auction.end_time = now.plus_seconds(time_extension);
+            }
+            AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+            Ok(rsp.add_attribute("end_time", auction.end_time.to_string()))
+        }
+        AuctionKind::Dutch { .. } => {
+            // First bid at or above the current price wins, excess is refunded
+            let mut rsp = rsp.add_attribute(
+                "amount",
+                Coin::new(min_bid.u128(), NATIVE_DENOM).to_string(),
+            );
+            let excess = amount - min_bid;
+            if !excess.is_zero() {
+                rsp = rsp.add_submessage(_refund_msg(&info.sender, excess));
+            }
+            AUCTIONS.remove(deps.storage, auction_id);
+
+            let messages = _settle_msgs(deps.as_ref(), &env, auction, &info.sender, min_bid)?;
+            Ok(rsp.add_submessages(messages))
+        }
+    }
+}
+
+/// Anyone can settle an English auction once it has ended
+pub fn execute_settle(deps: DepsMut, env: Env, auction_id: u64) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let bid = match &auction.kind {
+        AuctionKind::English { highest_bid, .. } => highest_bid.clone(),
+        // Dutch auctions settle on the winning bid
+        AuctionKind::Dutch { .. } => None,
+    };
+    let Bid { bidder, amount } = bid.ok_or(ContractError::NoBids {})?;
+    if env.block.time < auction.end_time {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    AUCTIONS.remove(deps.storage, auction_id);
+
+    let seller = auction.seller.clone();
+    let messages = _settle_msgs(deps.as_ref(), &env, auction, &bidder, amount)?;
+    Ok(Response::new()
+        .add_attribute("action", "settle")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("winner", bidder.as_str())
+        .add_attribute("seller", seller.as_str())
+        .add_attribute("amount", Coin::new(amount.u128(), NATIVE_DENOM).to_string())
+        .add_submessages(messages))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let Auction {
+        seller, nft, kind, ..
+    } = AUCTIONS.load(deps.storage, auction_id)?;
+    if info.sender != seller {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let AuctionKind::English {
+        highest_bid: Some(_),
+        ..
+    } = kind
+    {
+        return Err(ContractError::HasBids {});
+    }
+
+    // Seller pays the fee to return the tokens
+    let fee = nft.transfer_fee()?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment.u128() != fee {
+        return Err(ContractError::IncorrectPayment {
+            expected: fee,
+            received: payment.u128(),
+        });
+    }
+
+    AUCTIONS.remove(deps.storage, auction_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("seller", seller.as_str())
+        .add_submessage(nft.transfer_msg(&env.contract.address, seller.as_str(), fee)?))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Auction { auction_id } => to_binary(&query_auction(deps, env, auction_id)?),
+        QueryMsg::Auctions { start_after, limit } => {
+            to_binary(&query_auctions(deps, env, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_auction(deps: Deps, env: Env, auction_id: u64) -> StdResult<AuctionResponse> {
+    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    _auction_response(&env, auction_id, auction)
+}
+
+pub fn query_auctions(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let auctions = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(id, auction)| _auction_response(&env, id, auction)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AuctionsResponse { auctions })
+}
+
+/********************************* HELPERS ************************************/
+
+fn _create_auction(
+    deps: DepsMut,
+    env: Env,
+    seller: Addr,
+    nft: Nft,
+    msg: &Binary,
+) -> Result<Response, ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidAuction {
+        reason: reason.to_string(),
+    };
+
+    // Lowest sale must cover the fee to transfer the tokens to the winner
+    let fee = Uint128::from(nft.transfer_fee()?);
+    let (kind, duration) = match from_binary(msg)? {
+        AuctionMsg::English {
+            reserve_price,
+            min_increment,
+            duration,
+            time_extension,
+        } => {
+            if reserve_price <= fee {
+                return Err(invalid("reserve price must exceed the transfer fee"));
+            }
+            if min_increment.is_zero() {
+                return Err(invalid("min increment must be positive"));
+            }
+            if time_extension > MAX_DURATION {
+                return Err(invalid("time extension must be at most 30 days"));
+            }
+            let kind = AuctionKind::English {
+                reserve_price,
+                min_increment,
+                time_extension,
+                highest_bid: None,
+            };
+            (kind, duration)
+        }
+        AuctionMsg::Dutch {
+            start_price,
+            end_price,
+            duration,
+        } => {
+            if end_price <= fee {
+                return Err(invalid("end price must exceed the transfer fee"));
+            }
+            if start_price <= end_price {
+                return Err(invalid("start price must exceed end price"));
+            }
+            let kind = AuctionKind::Dutch {
+                start_price,
+                end_price,
+            };
+            (kind, duration)
+        }
+    };
+    if duration == 0 {
+        return Err(invalid("duration must be positive"));
+    }
+    if duration > MAX_DURATION {
+        return Err(invalid("duration must be at most 30 days"));
+    }
+
+    let auction_id =
+        AUCTION_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    let auction = Auction {
+        seller: seller.clone(),
+        nft,
+        kind,
+        start_time: env.block.time,
+        end_time: env.block.time.plus_seconds(duration),
+    };
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_auction")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("seller", seller)
+        .add_attribute("end_time", auction.end_time.to_string()))
+}
+
+/// Transfer the tokens to the winner and pay the seller and royalties, less the transfer fee
+fn _settle_msgs(
+    deps: Deps,
+    env: &Env,
+    auction: Auction,
+    winner: &Addr,
+    amount: Uint128,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let Auction { seller, nft, .. } = auction;
+    let fee = nft.transfer_fee()?;
+    let royalty_address = nft.royalty_address(&deps.querier)?;
+
+    let mut messages = vec![nft.transfer_msg(&env.contract.address, winner.as_str(), fee)?];
+    messages.extend(sale_payment(amount.u128() - fee, royalty_address, seller));
+    Ok(messages)
+}
+
+fn _refund_msg(bidder: &Addr, amount: Uint128) -> SubMsg {
+    SubMsg::new(BankMsg::Send {
+        to_address: bidder.to_string(),
+        amount: coins(amount.u128(), NATIVE_DENOM),
+    })
+}
+
+fn _auction_response(env: &Env, auction_id: u64, auction: Auction) -> StdResult<AuctionResponse> {
+    let min_bid = auction.min_bid(env.block.time)?;
+    Ok(AuctionResponse {
+        auction_id,
+        seller: auction.seller.to_string(),
+        nft: auction.nft,
+        kind: auction.kind,
+        start_time: auction.start_time,
+        end_time: auction.end_time,
+        min_bid: Coin::new(min_bid.u128(), NATIVE_DENOM),
+    })
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{OwnedDeps, StdError};
+    use s1::ROYALTY_FEE;
+    use s_nft::testing::mock_nft_querier;
+    use s_std::create_fund_community_pool_msg;
+
+    const S721: &str = "s721";
+    const S1155: &str = "s1155";
+    const ROYALTY: &str = "royalty";
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        // nft contracts return the royalty address
+        deps.querier.update_wasm(mock_nft_querier(ROYALTY));
+        deps
+    }
+
+    fn create_s721(deps: DepsMut, env: Env, msg: AuctionMsg) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "seller".to_string(),
+            token_id: "token".to_string(),
+            msg: to_binary(&msg).unwrap(),
+        });
+        execute(deps, env, mock_info(S721, &[]), msg)
+    }
+
+    fn english() -> AuctionMsg {
+        AuctionMsg::English {
+            reserve_price: Uint128::new(100_000),
+            min_increment: Uint128::new(10_000),
+            duration: 3600,
+            time_extension: 600,
+        }
+    }
+
+    fn bid(deps: DepsMut, env: Env, bidder: &str, amount: u128) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            env,
+            mock_info(bidder, &coins(amount, NATIVE_DENOM)),
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "instantiate")
+                .add_attribute("contract_name", CONTRACT_NAME)
+                .add_attribute("contract_version", CONTRACT_VERSION)
+        );
+        assert_eq!(AUCTION_COUNT.load(&deps.storage).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_create_auction() {
+        let mut deps = setup_contract();
+
+        // Error: reserve price does not cover the transfer fee
+        let msg = AuctionMsg::English {
+            reserve_price: Uint128::new(ROYALTY_FEE),
+            min_increment: Uint128::new(1),
+            duration: 3600,
+            time_extension: 0,
+        };
+        assert!(matches!(
+            create_s721(deps.as_mut(), mock_env(), msg),
+            Err(ContractError::InvalidAuction { .. })
+        ));
+
+        // Error: auction and time extension longer than 30 days
+        let msg = AuctionMsg::English {
+            reserve_price: Uint128::new(100_000),
+            min_increment: Uint128::new(1),
+            duration: MAX_DURATION + 1,
+            time_extension: 0,
+        };
+        assert!(matches!(
+            create_s721(deps.as_mut(), mock_env(), msg),
+            Err(ContractError::InvalidAuction { .. })
+        ));
+        let msg = AuctionMsg::English {
+            reserve_price: Uint128::new(100_000),
+            min_increment: Uint128::new(1),
+            duration: 3600,
+            time_extension: u64::MAX,
+        };
+        assert!(matches!(
+            create_s721(deps.as_mut(), mock_env(), msg),
+            Err(ContractError::InvalidAuction { .. })
+        ));
+
+        // Error: price must decay
+        let msg = AuctionMsg::Dutch {
+            start_price: Uint128::new(100_000),
+            end_price: Uint128::new(100_000),
+            duration: 3600,
+        };
+        assert!(matches!(
+            create_s721(deps.as_mut(), mock_env(), msg),
+            Err(ContractError::InvalidAuction { .. })
+        ));
+
+        // auction s1155 lot
+        let batch = vec![
+            ("token1".to_string(), Uint128::new(1)),
+            ("token2".to_string(), Uint128::new(5)),
+        ];
+        let msg = ExecuteMsg::BatchReceive(BatchReceiveMsg {
+            operator: "operator".to_string(),
+            from: Some("seller".to_string()),
+            batch: batch.clone(),
+            msg: to_binary(&english()).unwrap(),
+        });
+        let env = mock_env();
+        let res = execute(deps.as_mut(), env.clone(), mock_info(S1155, &[]), msg).unwrap();
+        let end_time = env.block.time.plus_seconds(3600);
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "create_auction")
+                .add_attribute("auction_id", "1")
+                .add_attribute("seller", "seller")
+                .add_attribute("end_time", end_time.to_string())
+        );
+
+        let res = query_auction(deps.as_ref(), env.clone(), 1).unwrap();
+        assert_eq!(
+            res,
+            AuctionResponse {
+                auction_id: 1,
+                seller: "seller".to_string(),
+                nft: Nft::S1155 {
+                    contract: Addr::unchecked(S1155),
+                    batch,
+                },
+                kind: AuctionKind::English {
+                    reserve_price: Uint128::new(100_000),
+                    min_increment: Uint128::new(10_000),
+                    time_extension: 600,
+                    highest_bid: None,
+                },
+                start_time: env.block.time,
+                end_time,
+                min_bid: Coin::new(100_000, NATIVE_DENOM),
+            }
+        );
+    }
+
+    #[test]
+    fn test_english_auction() {
+        let mut deps = setup_contract();
+        let mut env = mock_env();
+        create_s721(deps.as_mut(), env.clone(), english()).unwrap();
+
+        // Error: below reserve price
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "alice", 99_999),
+            Err(ContractError::BidTooLow {
+                min: 100_000,
+                received: 99_999
+            })
+        ));
+        // Error: seller cannot bid
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "seller", 100_000),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        let res = bid(deps.as_mut(), env.clone(), "alice", 100_000).unwrap();
+        assert!(res.messages.is_empty());
+
+        // Error: below min increment
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "bob", 105_000),
+            Err(ContractError::BidTooLow {
+                min: 110_000,
+                received: 105_000
+            })
+        ));
+
+        // outbid in the last minute, previous bidder is refunded and auction is extended
+        let end_time = env.block.time.plus_seconds(3600);
+        env.block.time = env.block.time.plus_seconds(3540);
+        let res = bid(deps.as_mut(), env.clone(), "bob", 110_000).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![_refund_msg(
+                &Addr::unchecked("alice"),
+                Uint128::new(100_000)
+            )]
+        );
+        let auction = AUCTIONS.load(&deps.storage, 1).unwrap();
+        assert_eq!(auction.end_time, env.block.time.plus_seconds(600));
+        assert!(auction.end_time > end_time);
+
+        // Error: cannot cancel with bids
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("seller", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+                ExecuteMsg::Cancel { auction_id: 1 },
+            ),
+            Err(ContractError::HasBids {})
+        ));
+
+        // Error: not ended yet
+        assert!(matches!(
+            execute_settle(deps.as_mut(), env.clone(), 1),
+            Err(ContractError::AuctionNotEnded {})
+        ));
+
+        // Error: no bids after the end
+        env.block.time = auction.end_time;
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "carol", 200_000),
+            Err(ContractError::AuctionEnded {})
+        ));
+
+        // settle, winner receives the token and seller the price less fee and royalties
+        let res = execute_settle(deps.as_mut(), env.clone(), 1).unwrap();
+        let nft = Nft::S721 {
+            contract: Addr::unchecked(S721),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(
+            res.messages,
+            vec![
+                nft.transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "bob", ROYALTY_FEE)
+                    .unwrap(),
+                SubMsg::new(BankMsg::Send {
+                    to_address: ROYALTY.to_string(),
+                    amount: coins(5177, NATIVE_DENOM),
+                }),
+                SubMsg::new(create_fund_community_pool_msg(coins(273, NATIVE_DENOM))),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "seller".to_string(),
+                    amount: coins(103_550, NATIVE_DENOM),
+                }),
+            ]
+        );
+        assert!(query_auction(deps.as_ref(), env, 1).is_err());
+    }
+
+    #[test]
+    fn test_min_bid_overflow() {
+        let mut deps = setup_contract();
+        let env = mock_env();
+        create_s721(deps.as_mut(), env.clone(), english()).unwrap();
+        bid(deps.as_mut(), env.clone(), "alice", u128::MAX).unwrap();
+
+        // Error: min increment over the highest bid overflows
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "bob", u128::MAX),
+            Err(ContractError::Std(StdError::Overflow { .. }))
+        ));
+        assert!(query_auction(deps.as_ref(), env, 1).is_err());
+    }
+
+    #[test]
+    fn test_dutch_auction() {
+        let mut deps = setup_contract();
+        let mut env = mock_env();
+        let msg = AuctionMsg::Dutch {
+            start_price: Uint128::new(200_000),
+            end_price: Uint128::new(100_000),
+            duration: 1000,
+        };
+        create_s721(deps.as_mut(), env.clone(), msg).unwrap();
+
+        // price decays linearly
+        env.block.time = env.block.time.plus_seconds(250);
+        let res = query_auction(deps.as_ref(), env.clone(), 1).unwrap();
+        assert_eq!(res.min_bid, Coin::new(175_000, NATIVE_DENOM));
+        assert!(matches!(
+            bid(deps.as_mut(), env.clone(), "alice", 150_000),
+            Err(ContractError::BidTooLow {
+                min: 175_000,
+                received: 150_000
+            })
+        ));
+
+        // and stays at the end price
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(5000);
+        let res = query_auction(deps.as_ref(), later, 1).unwrap();
+        assert_eq!(res.min_bid, Coin::new(100_000, NATIVE_DENOM));
+
+        // first bid at the current price wins, excess is refunded
+        let res = bid(deps.as_mut(), env.clone(), "alice", 180_000).unwrap();
+        assert_eq!(
+            res.messages[0],
+            _refund_msg(&Addr::unchecked("alice"), Uint128::new(5000))
+        );
+        assert_eq!(
+            res.messages[1],
+            Nft::S721 {
+                contract: Addr::unchecked(S721),
+                token_id: "token".to_string(),
+            }
+            .transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "alice", ROYALTY_FEE)
+            .unwrap()
+        );
+        assert_eq!(
+            res.messages[4],
+            SubMsg::new(BankMsg::Send {
+                to_address: "seller".to_string(),
+                amount: coins(165_300, NATIVE_DENOM),
+            })
+        );
+        assert!(query_auction(deps.as_ref(), env, 1).is_err());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut deps = setup_contract();
+        create_s721(deps.as_mut(), mock_env(), english()).unwrap();
+
+        // Error: only seller can cancel
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("random", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+                ExecuteMsg::Cancel { auction_id: 1 },
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // cancel auction, seller pays to get the token back
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("seller", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+            ExecuteMsg::Cancel { auction_id: 1 },
+        )
+        .unwrap();
+        let nft = Nft::S721 {
+            contract: Addr::unchecked(S721),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(
+            res.messages,
+            vec![nft
+                .transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "seller", ROYALTY_FEE)
+                .unwrap()]
+        );
+        assert!(query_auction(deps.as_ref(), mock_env(), 1).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid auction: {reason}")]
+    InvalidAuction { reason: String },
+
+    #[error("Bid too low: minimum {min}, got {received}")]
+    BidTooLow { min: u128, received: u128 },
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction has not ended")]
+    AuctionNotEnded {},
+
+    #[error("Auction has bids")]
+    HasBids {},
+
+    #[error("Auction has no bids")]
+    NoBids {},
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
@@ -0,0 +1,83 @@
+use cosmwasm_std::{Coin, Timestamp, Uint128};
+use cw721::Cw721ReceiveMsg;
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::AuctionKind;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Auction a s721 token, sent with `SendNft` and an `AuctionMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Auction s1155 tokens, sent with `SendFrom` and an `AuctionMsg`
+    Receive(ReceiveMsg),
+    /// Auction a lot of s1155 tokens, sent with `BatchSendFrom` and an `AuctionMsg`
+    BatchReceive(BatchReceiveMsg),
+    /// Bid on an English auction, or buy a Dutch auction at the current price.
+    /// The transfer fee is deducted from the winning amount.
+    Bid { auction_id: u64 },
+    /// Transfer the tokens to the highest bidder of an ended English auction
+    Settle { auction_id: u64 },
+    /// Return the tokens of an auction without bids to the seller, paying the transfer fee in usign
+    Cancel { auction_id: u64 },
+}
+
+/// Message embedded when sending tokens to the auction contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionMsg {
+    English {
+        /// Lowest accepted first bid in usign
+        reserve_price: Uint128,
+        /// Lowest accepted increase over the highest bid in usign
+        min_increment: Uint128,
+        /// Auction length in seconds, up to 30 days
+        duration: u64,
+        /// Bids placed within this many seconds of the end extend the auction by the same window,
+        /// up to 30 days
+        time_extension: u64,
+    },
+    Dutch {
+        /// Price in usign when the auction starts
+        start_price: Uint128,
+        /// Price in usign once the auction has fully decayed
+        end_price: Uint128,
+        /// Seconds for the price to decay from `start_price` to `end_price`, up to 30 days
+        duration: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Auction {
+        auction_id: u64,
+    },
+    Auctions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionResponse {
+    pub auction_id: u64,
+    pub seller: String,
+    pub nft: Nft,
+    pub kind: AuctionKind,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    /// Lowest accepted bid, or the current price of a Dutch auction
+    pub min_bid: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionsResponse {
+    pub auctions: Vec<AuctionResponse>,
+}
@@ -0,0 +1,75 @@
+use cosmwasm_std::{Addr, StdError, StdResult, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+    pub bidder: Addr,
+    /// Amount escrowed in usign, transfer fee is deducted on settlement
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionKind {
+    English {
+        reserve_price: Uint128,
+        min_increment: Uint128,
+        time_extension: u64,
+        highest_bid: Option<Bid>,
+    },
+    Dutch {
+        start_price: Uint128,
+        end_price: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    pub seller: Addr,
+    pub nft: Nft,
+    pub kind: AuctionKind,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+}
+
+impl Auction {
+    /// Lowest amount accepted for the next bid, or the current price of a Dutch auction
+    pub fn min_bid(&self, now: Timestamp) -> StdResult<Uint128> {
+        Ok(match &self.kind {
+            AuctionKind::English {
+                reserve_price,
+                min_increment,
+                highest_bid,
+                ..
+            } => match highest_bid {
+                Some(bid) => bid
+                    .amount
+                    .checked_add(*min_increment)
+                    .map_err(StdError::overflow)?,
+                None => *reserve_price,
+            },
+            AuctionKind::Dutch {
+                start_price,
+                end_price,
+            } => {
+                // price decays linearly until end time, then stays at end price
+                let duration = self.end_time.seconds() - self.start_time.seconds();
+                let elapsed = now.seconds().saturating_sub(self.start_time.seconds());
+                if elapsed >= duration {
+                    return Ok(*end_price);
+                }
+                let decay = (*start_price - *end_price).multiply_ratio(elapsed, duration);
+                *start_price - decay
+            }
+        })
+    }
+}
+
+/// Number of auctions created, used as the id of the next auction
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+
+// Auction id -> Auction
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
@@ -0,0 +1,552 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, Coin, Deps, DepsMut, Env};
+use cosmwasm_std::{MessageInfo, Order, StdError, StdResult, Uint128};
+use cw2::set_contract_version;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+use s1::sale_payment;
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use s_std::{error::FeeError, Response, NATIVE_DENOM};
+
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, ListingMsg, ListingResponse, ListingsResponse, QueryMsg,
+};
+use crate::state::{listings, Listing, LISTING_COUNT};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_marketplace";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    LISTING_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute_batch_receive(deps, info, msg),
+        ExecuteMsg::Buy { listing_id } => execute_buy(deps, env, info, listing_id),
+        ExecuteMsg::Cancel { listing_id } => execute_cancel(deps, env, info, listing_id),
+    }
+}
+
+/// s721 token sent with `SendNft`, sender of the nft contract is the seller
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.sender)?;
+    let nft = Nft::S721 {
+        contract: info.sender,
+        token_id: msg.token_id,
+    };
+    _create_listing(deps, seller, nft, &msg.msg)
+}
+
+/// s1155 tokens sent with `SendFrom`, previous owner of the tokens is the seller
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: vec![(msg.token_id, msg.amount)],
+    };
+    _create_listing(deps, seller, nft, &msg.msg)
+}
+
+/// s1155 tokens sent with `BatchSendFrom`, listed as a single lot
+pub fn execute_batch_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: BatchReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: msg.batch,
+    };
+    _create_listing(deps, seller, nft, &msg.msg)
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Listing { seller, nft, price } = listings().load(deps.storage, listing_id)?;
+
+    // Buyer pays the price and the fee to transfer the tokens
+    let fee = nft.transfer_fee()?;
+    let expected = price
+        .checked_add(Uint128::from(fee))
+        .map_err(FeeError::from)?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != expected {
+        return Err(ContractError::IncorrectPayment {
+            expected: expected.u128(),
+            received: payment.u128(),
+        });
+    }
+
+    let royalty_address = nft.royalty_address(&deps.querier)?;
+    listings().remove(deps.storage, listing_id)?;
+
+    let mut rsp = Response::new()
+        .add_attribute("action", "buy")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("buyer", info.sender.as_str())
+        .add_attribute("seller", seller.as_str())
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string())
+        .add_attribute("royalty_address", royalty_address.as_str());
+    rsp.messages = vec![nft.transfer_msg(&env.contract.address, info.sender.as_str(), fee)?];
+    rsp.messages
+        .extend(sale_payment(price.u128(), royalty_address, seller));
+
+    Ok(rsp)
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Listing { seller, nft, .. } = listings().load(deps.storage, listing_id)?;
+    if info.sender != seller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Seller pays the fee to return the tokens
+    let fee = nft.transfer_fee()?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment.u128() != fee {
+        return Err(ContractError::IncorrectPayment {
+            expected: fee,
+            received: payment.u128(),
+        });
+    }
+
+    listings().remove(deps.storage, listing_id)?;
+
+    let mut rsp = Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("seller", seller.as_str());
+    rsp.messages = vec![nft.transfer_msg(&env.contract.address, seller.as_str(), fee)?];
+
+    Ok(rsp)
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Listing { listing_id } => to_binary(&query_listing(deps, listing_id)?),
+        QueryMsg::Listings { start_after, limit } => {
+            to_binary(&query_listings(deps, start_after, limit)?)
+        }
+        QueryMsg::ListingsBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_binary(&query_listings_by_seller(deps, seller, start_after, limit)?),
+    }
+}
+
+pub fn query_listing(deps: Deps, listing_id: u64) -> StdResult<ListingResponse> {
+    let listing = listings().load(deps.storage, listing_id)?;
+    _listing_response(listing_id, listing)
+}
+
+pub fn query_listings(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListingsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let listings = listings()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(id, listing)| _listing_response(id, listing)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListingsResponse { listings })
+}
+
+pub fn query_listings_by_seller(
+    deps: Deps,
+    seller: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListingsResponse> {
+    let seller = deps.api.addr_validate(&seller)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let listings = listings()
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(id, listing)| _listing_response(id, listing)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListingsResponse { listings })
+}
+
+/********************************* HELPERS ************************************/
+
+fn _create_listing(
+    deps: DepsMut,
+    seller: Addr,
+    nft: Nft,
+    msg: &Binary,
+) -> Result<Response, ContractError> {
+    let ListingMsg { price } = from_binary(msg)?;
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+
+    let listing_id =
+        LISTING_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    listings().save(
+        deps.storage,
+        listing_id,
+        &Listing {
+            seller: seller.clone(),
+            nft,
+            price,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("seller", seller)
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string()))
+}
+
+fn _listing_response(listing_id: u64, listing: Listing) -> StdResult<ListingResponse> {
+    let transfer_fee = listing
+        .nft
+        .transfer_fee()
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(ListingResponse {
+        listing_id,
+        seller: listing.seller.to_string(),
+        nft: listing.nft,
+        price: Coin::new(listing.price.u128(), NATIVE_DENOM),
+        transfer_fee: Coin::new(transfer_fee, NATIVE_DENOM),
+    })
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{coins, from_binary, BankMsg, OwnedDeps};
+    use s1::{ROYALTY_FEE, ROYALTY_FEE_SCHEDULE};
+    use s_nft::testing::mock_nft_querier;
+    use s_std::{create_fund_community_pool_msg, SubMsg};
+
+    const S721: &str = "s721";
+    const S1155: &str = "s1155";
+    const ROYALTY: &str = "royalty";
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        // nft contracts return the royalty address
+        deps.querier.update_wasm(mock_nft_querier(ROYALTY));
+        deps
+    }
+
+    fn list_s721(deps: DepsMut, seller: &str, token_id: &str, price: u128) -> Response {
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: seller.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary(&ListingMsg {
+                price: Uint128::new(price),
+            })
+            .unwrap(),
+        });
+        execute(deps, mock_env(), mock_info(S721, &[]), msg).unwrap()
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "instantiate")
+                .add_attribute("contract_name", CONTRACT_NAME)
+                .add_attribute("contract_version", CONTRACT_VERSION)
+        );
+        assert_eq!(LISTING_COUNT.load(&deps.storage).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_list() {
+        let mut deps = setup_contract();
+
+        // Error: zero price
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "seller".to_string(),
+            token_id: "token".to_string(),
+            msg: to_binary(&ListingMsg {
+                price: Uint128::zero(),
+            })
+            .unwrap(),
+        });
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info(S721, &[]), msg),
+            Err(ContractError::InvalidPrice {})
+        ));
+
+        // list s721 token
+        let res = list_s721(deps.as_mut(), "seller", "token", 100_000);
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "list")
+                .add_attribute("listing_id", "1")
+                .add_attribute("seller", "seller")
+                .add_attribute("price", "100000usign")
+        );
+
+        // list s1155 lot
+        let batch = vec![
+            ("token1".to_string(), Uint128::new(1)),
+            ("token2".to_string(), Uint128::new(5)),
+        ];
+        let msg = ExecuteMsg::BatchReceive(BatchReceiveMsg {
+            operator: "operator".to_string(),
+            from: Some("seller2".to_string()),
+            batch: batch.clone(),
+            msg: to_binary(&ListingMsg {
+                price: Uint128::new(50_000),
+            })
+            .unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(S1155, &[]), msg).unwrap();
+
+        // query listings
+        let res: ListingsResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Listings {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.listings,
+            vec![
+                ListingResponse {
+                    listing_id: 1,
+                    seller: "seller".to_string(),
+                    nft: Nft::S721 {
+                        contract: Addr::unchecked(S721),
+                        token_id: "token".to_string(),
+                    },
+                    price: Coin::new(100_000, NATIVE_DENOM),
+                    transfer_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
+                },
+                ListingResponse {
+                    listing_id: 2,
+                    seller: "seller2".to_string(),
+                    nft: Nft::S1155 {
+                        contract: Addr::unchecked(S1155),
+                        batch,
+                    },
+                    price: Coin::new(50_000, NATIVE_DENOM),
+                    transfer_fee: Coin::new(ROYALTY_FEE * 2, NATIVE_DENOM),
+                }
+            ]
+        );
+
+        // query listings by seller
+        let res: ListingsResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListingsBySeller {
+                    seller: "seller2".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.listings.len(), 1);
+        assert_eq!(res.listings[0].listing_id, 2);
+    }
+
+    #[test]
+    fn test_buy() {
+        let mut deps = setup_contract();
+        list_s721(deps.as_mut(), "seller", "token", 100_000);
+
+        // Error: price without transfer fee
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("buyer", &coins(100_000, NATIVE_DENOM)),
+                ExecuteMsg::Buy { listing_id: 1 },
+            ),
+            Err(ContractError::IncorrectPayment {
+                expected: 101_000,
+                received: 100_000
+            })
+        ));
+
+        // buy listing
+        let fee = ROYALTY_FEE_SCHEDULE.fee(1).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(100_000 + fee, NATIVE_DENOM)),
+            ExecuteMsg::Buy { listing_id: 1 },
+        )
+        .unwrap();
+        let nft = Nft::S721 {
+            contract: Addr::unchecked(S721),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(
+            res.messages,
+            vec![
+                nft.transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "buyer", fee)
+                    .unwrap(),
+                SubMsg::new(BankMsg::Send {
+                    to_address: ROYALTY.to_string(),
+                    amount: coins(4750, NATIVE_DENOM),
+                }),
+                SubMsg::new(create_fund_community_pool_msg(coins(250, NATIVE_DENOM))),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "seller".to_string(),
+                    amount: coins(95_000, NATIVE_DENOM),
+                }),
+            ]
+        );
+
+        // listing is removed
+        assert!(query_listing(deps.as_ref(), 1).is_err());
+        assert!(execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(100_000 + fee, NATIVE_DENOM)),
+            ExecuteMsg::Buy { listing_id: 1 },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut deps = setup_contract();
+        let msg = ExecuteMsg::Receive(ReceiveMsg {
+            operator: "seller".to_string(),
+            from: Some("seller".to_string()),
+            token_id: "token".to_string(),
+            amount: Uint128::new(10),
+            msg: to_binary(&ListingMsg {
+                price: Uint128::new(50_000),
+            })
+            .unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(S1155, &[]), msg).unwrap();
+
+        // Error: only seller can cancel
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("random", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+                ExecuteMsg::Cancel { listing_id: 1 },
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // cancel listing, seller pays to get the tokens back
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("seller", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+            ExecuteMsg::Cancel { listing_id: 1 },
+        )
+        .unwrap();
+        let nft = Nft::S1155 {
+            contract: Addr::unchecked(S1155),
+            batch: vec![("token".to_string(), Uint128::new(10))],
+        };
+        assert_eq!(
+            res.messages,
+            vec![nft
+                .transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "seller", ROYALTY_FEE)
+                .unwrap()]
+        );
+        assert!(query_listing(deps.as_ref(), 1).is_err());
+    }
+}
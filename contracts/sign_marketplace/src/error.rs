@@ -0,0 +1,25 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Price must be greater than zero")]
+    InvalidPrice {},
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
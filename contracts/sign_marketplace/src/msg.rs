@@ -0,0 +1,63 @@
+use cosmwasm_std::{Coin, Uint128};
+use cw721::Cw721ReceiveMsg;
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// List a s721 token, sent with `SendNft` and a `ListingMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// List s1155 tokens, sent with `SendFrom` and a `ListingMsg`
+    Receive(ReceiveMsg),
+    /// List a lot of s1155 tokens, sent with `BatchSendFrom` and a `ListingMsg`
+    BatchReceive(BatchReceiveMsg),
+    /// Buy a listing, paying the price and the transfer fee in usign
+    Buy { listing_id: u64 },
+    /// Return the tokens to the seller, paying the transfer fee in usign
+    Cancel { listing_id: u64 },
+}
+
+/// Message embedded when sending tokens to the marketplace
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingMsg {
+    /// Fixed price in usign
+    pub price: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Listing {
+        listing_id: u64,
+    },
+    Listings {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    ListingsBySeller {
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingResponse {
+    pub listing_id: u64,
+    pub seller: String,
+    pub nft: Nft,
+    pub price: Coin,
+    /// Fee charged by the nft contract to transfer the tokens
+    pub transfer_fee: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingsResponse {
+    pub listings: Vec<ListingResponse>,
+}
@@ -0,0 +1,39 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Listing {
+    pub seller: Addr,
+    pub nft: Nft,
+    /// Price in usign
+    pub price: Uint128,
+}
+
+/// Number of listings created, used as the id of the next listing
+pub const LISTING_COUNT: Item<u64> = Item::new("listing_count");
+
+pub struct ListingIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, Listing, u64>,
+}
+
+impl<'a> IndexList<Listing> for ListingIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+        let v: Vec<&dyn Index<Listing>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+// Listing id -> Listing
+pub fn listings<'a>() -> IndexedMap<'a, u64, Listing, ListingIndexes<'a>> {
+    let indexes = ListingIndexes {
+        seller: MultiIndex::new(
+            |d: &Listing| d.seller.clone(),
+            "listings",
+            "listings__seller",
+        ),
+    };
+    IndexedMap::new("listings", indexes)
+}
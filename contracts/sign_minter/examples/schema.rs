@@ -0,0 +1,18 @@
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use sign_minter::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, MintCountResponse, QueryMsg};
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(MintCountResponse), &out_dir);
+}
@@ -0,0 +1,903 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env};
+use cosmwasm_std::{MessageInfo, Reply, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use cw721_base::MintMsg;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+use s2::MIN_FEE;
+use s721::msg::{ExecuteMsg as S721ExecuteMsg, InstantiateMsg as S721InstantiateMsg};
+use s_std::{Response, MULTISIG, MULTISIG_SHARE, NATIVE_DENOM};
+use sha2::{Digest, Sha256};
+
+use crate::merkle::{decode_hash, leaf, verify_proof};
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, MintCountResponse, QueryMsg};
+use crate::state::{Config, CONFIG, MINTABLE, MINTED, MINT_COUNT, PRESALE_COUNT, S721};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_minter";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_S721_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let invalid = |reason: &str| ContractError::InvalidSale {
+        reason: reason.to_string(),
+    };
+    // Every mint pays the s721 minting fee out of the price
+    if msg.price.u128() < MIN_FEE {
+        return Err(invalid("price must cover the minting fee"));
+    }
+    if msg.max_supply == 0 {
+        return Err(invalid("max supply must be positive"));
+    }
+    if matches!(msg.end_time, Some(end) if end <= msg.start_time) {
+        return Err(invalid("end time must be after start time"));
+    }
+    if msg.per_wallet_limit == Some(0) {
+        return Err(invalid("per wallet limit must be positive"));
+    }
+    if let Some(presale) = &msg.presale {
+        if presale.start_time >= msg.start_time {
+            return Err(invalid("presale must start before the public sale"));
+        }
+        if presale.price.u128() < MIN_FEE {
+            return Err(invalid("presale price must cover the minting fee"));
+        }
+        decode_hash(&presale.merkle_root)?;
+    }
+
+    let config = Config {
+        creator: info.sender,
+        price: msg.price,
+        start_time: msg.start_time,
+        end_time: msg.end_time,
+        max_supply: msg.max_supply,
+        per_wallet_limit: msg.per_wallet_limit,
+        shuffle: msg.shuffle,
+        presale: msg.presale,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    MINTED.save(deps.storage, &0)?;
+
+    // Creation fee is forwarded to the s721 contract
+    let instantiate_s721_msg = WasmMsg::Instantiate {
+        code_id: msg.s721_code_id,
+        funds: info.funds,
+        admin: Some(config.creator.to_string()),
+        label: format!("s721-{}", msg.symbol),
+        msg: to_binary(&S721InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: env.contract.address.to_string(),
+            collection_info: msg.collection_info,
+            blind_box: msg.blind_box,
+            uri_policy: msg.uri_policy,
+        })?,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION)
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate_s721_msg,
+            INSTANTIATE_S721_REPLY_ID,
+        )))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Mint {} => execute_mint(deps, env, info),
+        ExecuteMsg::PresaleMint { allocation, proof } => {
+            execute_presale_mint(deps, env, info, allocation, proof)
+        }
+        ExecuteMsg::UpdateMerkleRoot { merkle_root } => {
+            execute_update_merkle_root(deps, env, info, merkle_root)
+        }
+        ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
+    }
+}
+
+pub fn execute_mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let now = env.block.time;
+    if now < config.start_time {
+        return Err(ContractError::NotStarted {});
+    }
+    if matches!(config.end_time, Some(end) if now >= end) {
+        return Err(ContractError::Ended {});
+    }
+
+    let count = MINT_COUNT
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if let Some(limit) = config.per_wallet_limit {
+        if count >= limit {
+            return Err(ContractError::WalletLimit { limit });
+        }
+    }
+
+    let sender = info.sender.clone();
+    let price = config.price;
+    let rsp = _mint(deps.branch(), env, info, config, price)?;
+    MINT_COUNT.save(deps.storage, &sender, &(count + 1))?;
+    Ok(rsp)
+}
+
+/// Mint during the presale, proving `allocation` for the sender against the merkle root
+pub fn execute_presale_mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    allocation: u32,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let presale = config.presale.clone().ok_or(ContractError::NoPresale {})?;
+
+    let now = env.block.time;
+    if now < presale.start_time {
+        return Err(ContractError::NotStarted {});
+    }
+    if now >= config.start_time {
+        return Err(ContractError::PresaleEnded {});
+    }
+
+    let root = decode_hash(&presale.merkle_root)?;
+    if !verify_proof(&root, leaf(info.sender.as_str(), allocation), &proof)? {
+        return Err(ContractError::InvalidProof {});
+    }
+
+    let count = PRESALE_COUNT
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if count >= allocation {
+        return Err(ContractError::AllocationReached { allocation });
+    }
+
+    let sender = info.sender.clone();
+    let rsp = _mint(deps.branch(), env, info, config, presale.price)?;
+    PRESALE_COUNT.save(deps.storage, &sender, &(count + 1))?;
+    Ok(rsp)
+}
+
+/// Replace the presale allow list before the public sale starts
+pub fn execute_update_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time >= config.start_time {
+        return Err(ContractError::PresaleEnded {});
+    }
+
+    decode_hash(&merkle_root)?;
+    let presale = config.presale.as_mut().ok_or(ContractError::NoPresale {})?;
+    presale.merkle_root = merkle_root.clone();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_merkle_root")
+        .add_attribute("merkle_root", merkle_root))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Contract only holds the proceeds of the sale
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, NATIVE_DENOM)?;
+    if balance.amount.is_zero() {
+        return Err(ContractError::NoProceeds {});
+    }
+
+    let multisig_amount = balance.amount.multiply_ratio(MULTISIG_SHARE, 100u64);
+    let creator_amount = balance.amount - multisig_amount;
+
+    let mut rsp = Response::new()
+        .add_attribute("action", "withdraw")
+        .add_attribute("creator", config.creator.as_str())
+        .add_attribute("amount", balance.to_string())
+        .add_attribute(
+            "multisig_amount",
+            Coin::new(multisig_amount.u128(), NATIVE_DENOM).to_string(),
+        )
+        .add_attribute(
+            "creator_amount",
+            Coin::new(creator_amount.u128(), NATIVE_DENOM).to_string(),
+        );
+    if !multisig_amount.is_zero() {
+        rsp = rsp.add_message(BankMsg::Send {
+            to_address: MULTISIG.to_string(),
+            amount: coins(multisig_amount.u128(), NATIVE_DENOM),
+        });
+    }
+    if !creator_amount.is_zero() {
+        rsp = rsp.add_message(BankMsg::Send {
+            to_address: config.creator.to_string(),
+            amount: coins(creator_amount.u128(), NATIVE_DENOM),
+        });
+    }
+    Ok(rsp)
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::MintCount { address } => to_binary(&query_mint_count(deps, address)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        creator: config.creator.to_string(),
+        s721: S721.may_load(deps.storage)?.map(String::from),
+        price: Coin::new(config.price.u128(), NATIVE_DENOM),
+        start_time: config.start_time,
+        end_time: config.end_time,
+        max_supply: config.max_supply,
+        minted: MINTED.load(deps.storage)?,
+        per_wallet_limit: config.per_wallet_limit,
+        shuffle: config.shuffle,
+        presale: config.presale,
+    })
+}
+
+pub fn query_mint_count(deps: Deps, address: String) -> StdResult<MintCountResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let count = MINT_COUNT
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    let presale_count = PRESALE_COUNT
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(MintCountResponse {
+        address,
+        count,
+        presale_count,
+    })
+}
+
+/********************************* REPLY **************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_S721_REPLY_ID {
+        return Err(ContractError::UnknownReplyId { id: msg.id });
+    };
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|_| ContractError::InstantiateS721Error {})?;
+
+    // Validate contract address
+    let s721 = deps.api.addr_validate(&res.contract_address)?;
+    S721.save(deps.storage, &s721)?;
+
+    Ok(Response::new().add_attribute("s721", s721))
+}
+
+/********************************* HELPERS ************************************/
+
+fn _mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: Config,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    let s721 = S721.load(deps.storage)?;
+
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != price {
+        return Err(ContractError::IncorrectPayment {
+            expected: price.u128(),
+            received: payment.u128(),
+        });
+    }
+
+    let minted = MINTED.load(deps.storage)?;
+    if minted >= config.max_supply {
+        return Err(ContractError::SoldOut {});
+    }
+    let token_id = _next_token_id(deps.storage, &env, &info.sender, &config, minted)?.to_string();
+    MINTED.save(deps.storage, &(minted + 1))?;
+
+    // Minting fee is paid to the multisig by the s721 contract, the rest is kept for the creator
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: s721.to_string(),
+        msg: to_binary(&S721ExecuteMsg::Mint(MintMsg {
+            token_id: token_id.clone(),
+            owner: info.sender.to_string(),
+            // Expanded from the collection base uri by s721
+            token_uri: None,
+            extension: Empty {},
+        }))?,
+        funds: coins(MIN_FEE, NATIVE_DENOM),
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender)
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string())
+        .add_message(mint_msg))
+}
+
+/// Sequential token ids start at 1. Shuffled ids are drawn from the ids left to mint,
+/// moving the last id into the drawn position so the pool stays contiguous.
+fn _next_token_id(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    config: &Config,
+    minted: u32,
+) -> StdResult<u32> {
+    if !config.shuffle {
+        return Ok(minted + 1);
+    }
+
+    let remaining = config.max_supply - minted;
+    let position = (_random_seed(env, sender) % u64::from(remaining)) as u32;
+    let last = remaining - 1;
+
+    let token_id = MINTABLE
+        .may_load(storage, position)?
+        .unwrap_or(position + 1);
+    let last_id = MINTABLE.may_load(storage, last)?.unwrap_or(last + 1);
+    MINTABLE.save(storage, position, &last_id)?;
+    MINTABLE.remove(storage, last);
+
+    Ok(token_id)
+}
+
+/// Pseudo random seed from the block and sender, not secure against validators
+fn _random_seed(env: &Env, sender: &Addr) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(env.block.height.to_be_bytes());
+    hasher.update(env.block.time.nanos().to_be_bytes());
+    if let Some(tx) = &env.transaction {
+        hasher.update(tx.index.to_be_bytes());
+    }
+    hasher.update(sender.as_bytes());
+    let hash = hasher.finalize();
+
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&hash[..8]);
+    u64::from_be_bytes(seed)
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::hash_pair;
+    use crate::state::Presale;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+    use cosmwasm_std::{from_binary, Order, Timestamp, Uint128};
+    use s721::state::CollectionInfo;
+    use std::collections::HashSet;
+
+    const CREATOR: &str = "creator";
+    const S721_ADDR: &str = "s721";
+    const PRICE: u128 = MIN_FEE * 4;
+
+    fn instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            s721_code_id: 1,
+            name: "Collection".to_string(),
+            symbol: "COL".to_string(),
+            collection_info: CollectionInfo {
+                creator: CREATOR.to_string(),
+                description: String::from("Collection"),
+                image: "https://example.com/image.png".to_string(),
+                external_link: None,
+                royalty_address: CREATOR.to_string(),
+                base_uri: Some("ipfs://hash/{id}.json".to_string()),
+            },
+            blind_box: None,
+            uri_policy: None,
+            price: Uint128::new(PRICE),
+            start_time: mock_env().block.time,
+            end_time: None,
+            max_supply: 3,
+            per_wallet_limit: None,
+            shuffle: false,
+            presale: None,
+        }
+    }
+
+    /// Merkle tree levels from the leaves up to the root, odd nodes are carried up
+    fn merkle_tree(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(level);
+        }
+        levels
+    }
+
+    fn merkle_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<String> {
+        let mut proof = vec![];
+        for level in &levels[..levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(hex::encode(sibling));
+            }
+            index /= 2;
+        }
+        proof
+    }
+
+    fn setup_contract(mut deps: DepsMut, msg: InstantiateMsg) {
+        instantiate(
+            deps.branch(),
+            mock_env(),
+            mock_info(CREATOR, &coins(MIN_FEE, NATIVE_DENOM)),
+            msg,
+        )
+        .unwrap();
+        // s721 address is saved on reply
+        S721.save(deps.storage, &Addr::unchecked(S721_ADDR))
+            .unwrap();
+    }
+
+    fn mint(deps: DepsMut, env: Env, sender: &str) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            env,
+            mock_info(sender, &coins(PRICE, NATIVE_DENOM)),
+            ExecuteMsg::Mint {},
+        )
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        // Error: price does not cover the minting fee
+        let mut msg = instantiate_msg();
+        msg.price = Uint128::new(MIN_FEE - 1);
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), msg),
+            Err(ContractError::InvalidSale { .. })
+        ));
+
+        // s721 is instantiated with the minter as minter and the creation fee forwarded
+        let msg = instantiate_msg();
+        let info = mock_info(CREATOR, &coins(MIN_FEE, NATIVE_DENOM));
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    code_id: 1,
+                    funds: coins(MIN_FEE, NATIVE_DENOM),
+                    admin: Some(CREATOR.to_string()),
+                    label: "s721-COL".to_string(),
+                    msg: to_binary(&S721InstantiateMsg {
+                        name: msg.name,
+                        symbol: msg.symbol,
+                        minter: MOCK_CONTRACT_ADDR.to_string(),
+                        collection_info: msg.collection_info,
+                        blind_box: msg.blind_box,
+                        uri_policy: msg.uri_policy,
+                    })
+                    .unwrap(),
+                },
+                INSTANTIATE_S721_REPLY_ID,
+            )]
+        );
+
+        let res: ConfigResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(res.s721, None);
+        assert_eq!(res.minted, 0);
+        assert_eq!(res.price, Coin::new(PRICE, NATIVE_DENOM));
+    }
+
+    #[test]
+    fn test_mint() {
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        let start_time = msg.start_time.plus_seconds(100);
+        msg.start_time = start_time;
+        msg.end_time = Some(start_time.plus_seconds(100));
+        msg.per_wallet_limit = Some(2);
+        setup_contract(deps.as_mut(), msg);
+
+        // Error: sale not started
+        let mut env = mock_env();
+        assert!(matches!(
+            mint(deps.as_mut(), env.clone(), "alice"),
+            Err(ContractError::NotStarted {})
+        ));
+
+        // Error: incorrect payment
+        env.block.time = start_time;
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("alice", &coins(MIN_FEE, NATIVE_DENOM)),
+                ExecuteMsg::Mint {},
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        // sequential token ids, minting fee forwarded to s721
+        let res = mint(deps.as_mut(), env.clone(), "alice").unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: S721_ADDR.to_string(),
+                msg: to_binary(&S721ExecuteMsg::Mint(MintMsg {
+                    token_id: "1".to_string(),
+                    owner: "alice".to_string(),
+                    token_uri: None,
+                    extension: Empty {},
+                }))
+                .unwrap(),
+                funds: coins(MIN_FEE, NATIVE_DENOM),
+            })]
+        );
+        let res = mint(deps.as_mut(), env.clone(), "alice").unwrap();
+        assert_eq!(res.attributes[2].value, "2");
+
+        // Error: wallet limit reached
+        assert!(matches!(
+            mint(deps.as_mut(), env.clone(), "alice"),
+            Err(ContractError::WalletLimit { limit: 2 })
+        ));
+        assert_eq!(
+            query_mint_count(deps.as_ref(), "alice".to_string())
+                .unwrap()
+                .count,
+            2
+        );
+
+        // Error: sold out
+        mint(deps.as_mut(), env.clone(), "bob").unwrap();
+        assert!(matches!(
+            mint(deps.as_mut(), env.clone(), "carol"),
+            Err(ContractError::SoldOut {})
+        ));
+
+        // Error: sale ended
+        env.block.time = start_time.plus_seconds(100);
+        assert!(matches!(
+            mint(deps.as_mut(), env, "carol"),
+            Err(ContractError::Ended {})
+        ));
+    }
+
+    #[test]
+    fn test_shuffled_mint() {
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        msg.max_supply = 20;
+        msg.shuffle = true;
+        setup_contract(deps.as_mut(), msg);
+
+        // every token id is minted once in random order
+        let mut env = mock_env();
+        let mut token_ids = vec![];
+        for i in 0..20 {
+            env.block.height += 1;
+            env.block.time = env.block.time.plus_seconds(5);
+            let res = mint(deps.as_mut(), env.clone(), &format!("minter{}", i)).unwrap();
+            token_ids.push(res.attributes[2].value.parse::<u32>().unwrap());
+        }
+        let unique: HashSet<u32> = token_ids.iter().cloned().collect();
+        assert_eq!(unique, (1..=20).collect());
+        assert_ne!(token_ids, (1..=20).collect::<Vec<_>>());
+        assert!(MINTABLE
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+
+        assert!(matches!(
+            mint(deps.as_mut(), env, "late"),
+            Err(ContractError::SoldOut {})
+        ));
+    }
+
+    #[test]
+    fn test_withdraw() {
+        let mut deps = mock_dependencies();
+        setup_contract(deps.as_mut(), instantiate_msg());
+
+        // Error: nothing to withdraw
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::Withdraw {}
+            ),
+            Err(ContractError::NoProceeds {})
+        ));
+
+        // proceeds left after minting fees
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(PRICE - MIN_FEE, NATIVE_DENOM));
+
+        // Error: only creator can withdraw
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("random", &[]),
+                ExecuteMsg::Withdraw {}
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::Withdraw {},
+        )
+        .unwrap();
+        let multisig_amount = (PRICE - MIN_FEE) * u128::from(MULTISIG_SHARE) / 100;
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: MULTISIG.to_string(),
+                    amount: coins(multisig_amount, NATIVE_DENOM),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: CREATOR.to_string(),
+                    amount: coins(PRICE - MIN_FEE - multisig_amount, NATIVE_DENOM),
+                }),
+            ]
+        );
+
+        // multisig share is rounded down, the creator gets the rest
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(1_000_003, NATIVE_DENOM));
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::Withdraw {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: MULTISIG.to_string(),
+                    amount: coins(100_000, NATIVE_DENOM),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: CREATOR.to_string(),
+                    amount: coins(900_003, NATIVE_DENOM),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_presale() {
+        let allow_list = [("alice", 2), ("bob", 1), ("carol", 1)];
+        let levels = merkle_tree(
+            allow_list
+                .iter()
+                .map(|(address, allocation)| leaf(address, *allocation))
+                .collect(),
+        );
+        let root = hex::encode(levels.last().unwrap()[0]);
+
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        let presale_start = msg.start_time;
+        msg.start_time = presale_start.plus_seconds(100);
+        msg.presale = Some(Presale {
+            merkle_root: root,
+            start_time: presale_start,
+            price: Uint128::new(PRICE / 2),
+        });
+        msg.max_supply = 10;
+        setup_contract(deps.as_mut(), msg);
+
+        let presale_mint = |deps: DepsMut, env: Env, sender: &str, allocation: u32, proof| {
+            execute(
+                deps,
+                env,
+                mock_info(sender, &coins(PRICE / 2, NATIVE_DENOM)),
+                ExecuteMsg::PresaleMint { allocation, proof },
+            )
+        };
+
+        // Error: public sale not started
+        let mut env = mock_env();
+        assert!(matches!(
+            mint(deps.as_mut(), env.clone(), "alice"),
+            Err(ContractError::NotStarted {})
+        ));
+
+        // Error: allocation not in the allow list
+        let alice_proof = merkle_proof(&levels, 0);
+        assert!(matches!(
+            presale_mint(deps.as_mut(), env.clone(), "alice", 3, alice_proof.clone()),
+            Err(ContractError::InvalidProof {})
+        ));
+        // Error: proof of another address
+        assert!(matches!(
+            presale_mint(deps.as_mut(), env.clone(), "dave", 2, alice_proof.clone()),
+            Err(ContractError::InvalidProof {})
+        ));
+
+        // mint up to the allocation at the presale price
+        let res =
+            presale_mint(deps.as_mut(), env.clone(), "alice", 2, alice_proof.clone()).unwrap();
+        assert_eq!(
+            res.attributes[3].value,
+            Coin::new(PRICE / 2, NATIVE_DENOM).to_string()
+        );
+        presale_mint(deps.as_mut(), env.clone(), "alice", 2, alice_proof.clone()).unwrap();
+        assert!(matches!(
+            presale_mint(deps.as_mut(), env.clone(), "alice", 2, alice_proof.clone()),
+            Err(ContractError::AllocationReached { allocation: 2 })
+        ));
+
+        // odd leaf proof
+        presale_mint(
+            deps.as_mut(),
+            env.clone(),
+            "carol",
+            1,
+            merkle_proof(&levels, 2),
+        )
+        .unwrap();
+
+        // Error: presale ended
+        env.block.time = env.block.time.plus_seconds(100);
+        assert!(matches!(
+            presale_mint(
+                deps.as_mut(),
+                env.clone(),
+                "bob",
+                1,
+                merkle_proof(&levels, 1)
+            ),
+            Err(ContractError::PresaleEnded {})
+        ));
+
+        // presale mints do not count towards the public sale
+        mint(deps.as_mut(), env, "alice").unwrap();
+        assert_eq!(
+            query_mint_count(deps.as_ref(), "alice".to_string()).unwrap(),
+            MintCountResponse {
+                address: "alice".to_string(),
+                count: 1,
+                presale_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_merkle_root() {
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        msg.start_time = msg.start_time.plus_seconds(100);
+        msg.presale = Some(Presale {
+            merkle_root: hex::encode(leaf("alice", 1)),
+            start_time: mock_env().block.time,
+            price: Uint128::new(PRICE),
+        });
+        setup_contract(deps.as_mut(), msg);
+
+        // single leaf tree has an empty proof
+        let bob_root = hex::encode(leaf("bob", 1));
+        let update = ExecuteMsg::UpdateMerkleRoot {
+            merkle_root: bob_root.clone(),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("bob", &[]),
+                update.clone()
+            ),
+            Err(ContractError::Unauthorized {})
+        ));
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), update).unwrap();
+        assert_eq!(
+            query_config(deps.as_ref())
+                .unwrap()
+                .presale
+                .unwrap()
+                .merkle_root,
+            bob_root
+        );
+
+        let presale_mint = ExecuteMsg::PresaleMint {
+            allocation: 1,
+            proof: vec![],
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(PRICE, NATIVE_DENOM)),
+                presale_mint.clone()
+            ),
+            Err(ContractError::InvalidProof {})
+        ));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &coins(PRICE, NATIVE_DENOM)),
+            presale_mint,
+        )
+        .unwrap();
+
+        // Error: invalid root
+        let update = ExecuteMsg::UpdateMerkleRoot {
+            merkle_root: "root".to_string(),
+        };
+        assert!(execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), update).is_err());
+    }
+
+    #[test]
+    fn test_instantiate_times() {
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        msg.end_time = Some(Timestamp::from_seconds(0));
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), msg),
+            Err(ContractError::InvalidSale { .. })
+        ));
+    }
+}
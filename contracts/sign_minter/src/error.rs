@@ -0,0 +1,54 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid sale: {reason}")]
+    InvalidSale { reason: String },
+
+    #[error("Sale has not started")]
+    NotStarted {},
+
+    #[error("Sale has ended")]
+    Ended {},
+
+    #[error("Presale has ended")]
+    PresaleEnded {},
+
+    #[error("No presale")]
+    NoPresale {},
+
+    #[error("Invalid merkle proof")]
+    InvalidProof {},
+
+    #[error("Presale allocation of {allocation} reached")]
+    AllocationReached { allocation: u32 },
+
+    #[error("Sold out")]
+    SoldOut {},
+
+    #[error("Wallet limit of {limit} reached")]
+    WalletLimit { limit: u32 },
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("Nothing to withdraw")]
+    NoProceeds {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Error instantiating s721 contract")]
+    InstantiateS721Error {},
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
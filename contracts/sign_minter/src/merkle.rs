@@ -0,0 +1,35 @@
+use cosmwasm_std::{StdError, StdResult};
+use sha2::{Digest, Sha256};
+
+/// Leaf of an address allowed to mint `allocation` tokens
+pub fn leaf(address: &str, allocation: u32) -> [u8; 32] {
+    _sha256(format!("{}:{}", address, allocation).as_bytes())
+}
+
+/// Verify a proof of hex encoded siblings, pairs are sorted before hashing
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[String]) -> StdResult<bool> {
+    let hash = proof.iter().try_fold(leaf, |hash, sibling| {
+        let sibling = decode_hash(sibling)?;
+        Ok::<_, StdError>(hash_pair(&hash, &sibling))
+    })?;
+    Ok(&hash == root)
+}
+
+/// Decode a hex encoded sha256 hash
+pub fn decode_hash(hash: &str) -> StdResult<[u8; 32]> {
+    let bytes = hex::decode(hash).map_err(|err| StdError::generic_err(err.to_string()))?;
+    bytes
+        .try_into()
+        .map_err(|_| StdError::generic_err("hash must be 32 bytes"))
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    _sha256(&[&left[..], &right[..]].concat())
+}
+
+fn _sha256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(data));
+    hash
+}
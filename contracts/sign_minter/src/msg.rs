@@ -0,0 +1,76 @@
+use crate::state::Presale;
+use cosmwasm_std::{Coin, Timestamp, Uint128};
+use s721::state::{BlindBox, CollectionInfo};
+use s_uri::UriPolicy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Instantiate the s721 collection with the minter as its minter, paying the s721 creation fee
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub s721_code_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub collection_info: CollectionInfo,
+    /// Hide the token metadata behind a placeholder until the creator reveals it on s721
+    pub blind_box: Option<BlindBox>,
+    /// Validation policy of the s721 token uris, defaults to `ipfs`, `ar` and `https` uris
+    pub uri_policy: Option<UriPolicy>,
+    /// Price per token in usign, must cover the s721 minting fee
+    pub price: Uint128,
+    pub start_time: Timestamp,
+    pub end_time: Option<Timestamp>,
+    pub max_supply: u32,
+    pub per_wallet_limit: Option<u32>,
+    /// Mint token ids in random order instead of sequentially
+    pub shuffle: bool,
+    /// Allow list phase before `start_time`
+    pub presale: Option<Presale>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Mint the next token to the sender, paying the price in usign
+    Mint {},
+    /// Mint the next token to the sender during the presale, paying the presale price in usign.
+    /// `proof` is the hex encoded merkle proof of the sender and its allocation.
+    PresaleMint { allocation: u32, proof: Vec<String> },
+    /// Replace the presale merkle root before the public sale starts
+    UpdateMerkleRoot { merkle_root: String },
+    /// Send the sale proceeds to the Sign multisig and the creator
+    Withdraw {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Number of tokens minted by an address
+    MintCount {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub creator: String,
+    pub s721: Option<String>,
+    pub price: Coin,
+    pub start_time: Timestamp,
+    pub end_time: Option<Timestamp>,
+    pub max_supply: u32,
+    pub minted: u32,
+    pub per_wallet_limit: Option<u32>,
+    pub shuffle: bool,
+    pub presale: Option<Presale>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintCountResponse {
+    pub address: String,
+    /// Tokens minted in the public sale
+    pub count: u32,
+    /// Tokens minted in the presale
+    pub presale_count: u32,
+}
@@ -0,0 +1,46 @@
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Receives the sale proceeds with `Withdraw`, minus the multisig share
+    pub creator: Addr,
+    /// Price per token in usign, including the s721 minting fee
+    pub price: Uint128,
+    pub start_time: Timestamp,
+    pub end_time: Option<Timestamp>,
+    pub max_supply: u32,
+    pub per_wallet_limit: Option<u32>,
+    /// Mint token ids in random order instead of sequentially
+    pub shuffle: bool,
+    pub presale: Option<Presale>,
+}
+
+/// Allow list phase before the public sale starts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Presale {
+    /// Hex encoded sha256 merkle root of `{address}:{allocation}` leaves
+    pub merkle_root: String,
+    pub start_time: Timestamp,
+    /// Price per token in usign, must cover the s721 minting fee
+    pub price: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// s721 contract instantiated by the minter
+pub const S721: Item<Addr> = Item::new("s721");
+
+/// Number of tokens minted
+pub const MINTED: Item<u32> = Item::new("minted");
+
+// Position -> token id of the tokens left to mint, token id is position + 1 when not stored
+pub const MINTABLE: Map<u32, u32> = Map::new("mintable");
+
+// Address -> number of tokens minted in the public sale
+pub const MINT_COUNT: Map<&Addr, u32> = Map::new("mint_count");
+
+// Address -> number of tokens minted in the presale
+pub const PRESALE_COUNT: Map<&Addr, u32> = Map::new("presale_count");
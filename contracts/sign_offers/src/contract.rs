@@ -0,0 +1,507 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, BankMsg, Binary, Coin, Deps, DepsMut};
+use cosmwasm_std::{Env, MessageInfo, Order, StdResult};
+use cw2::set_contract_version;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, Expiration};
+use s1::{sale_payment, ROYALTY_FEE_SCHEDULE};
+use s_nft::Nft;
+use s_std::{Response, NATIVE_DENOM};
+
+use crate::msg::{AcceptMsg, ExecuteMsg, InstantiateMsg, OfferResponse, OffersResponse, QueryMsg};
+use crate::state::{offers, Offer, OFFER_COUNT};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_offers";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    OFFER_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::MakeOffer {
+            collection,
+            token_id,
+            expires,
+        } => execute_make_offer(deps, env, info, collection, token_id, expires),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Cancel { offer_id } => execute_cancel(deps, env, info, offer_id),
+    }
+}
+
+pub fn execute_make_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: Option<String>,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    // Offer must cover the fee to transfer the token to the bidder
+    let price = must_pay(&info, NATIVE_DENOM)?;
+    let fee = ROYALTY_FEE_SCHEDULE.fee(1)?;
+    if price.u128() <= fee {
+        return Err(ContractError::InvalidPrice { fee });
+    }
+
+    let offer_id = OFFER_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    offers().save(
+        deps.storage,
+        offer_id,
+        &Offer {
+            bidder: info.sender.clone(),
+            collection: collection.clone(),
+            token_id: token_id.clone(),
+            price,
+            expires,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "make_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("bidder", info.sender)
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id.unwrap_or_default())
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string()))
+}
+
+/// s721 token sent with `SendNft` to accept an offer, sender of the nft contract is the seller
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&msg.sender)?;
+    let AcceptMsg { offer_id } = from_binary(&msg.msg)?;
+    let Offer {
+        bidder,
+        collection,
+        token_id,
+        price,
+        expires,
+    } = offers().load(deps.storage, offer_id)?;
+
+    // Collection offers accept any token of the collection
+    if info.sender != collection || matches!(token_id, Some(id) if id != msg.token_id) {
+        return Err(ContractError::WrongToken {
+            token_id: msg.token_id,
+        });
+    }
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let nft = Nft::S721 {
+        contract: collection,
+        token_id: msg.token_id,
+    };
+    let fee = nft.transfer_fee()?;
+    let royalty_address = nft.royalty_address(&deps.querier)?;
+    offers().remove(deps.storage, offer_id)?;
+
+    let mut rsp = Response::new()
+        .add_attribute("action", "accept_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("bidder", bidder.as_str())
+        .add_attribute("seller", seller.as_str())
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string())
+        .add_attribute("royalty_address", royalty_address.as_str());
+    rsp.messages = vec![nft.transfer_msg(&env.contract.address, bidder.as_str(), fee)?];
+    rsp.messages
+        .extend(sale_payment(price.u128() - fee, royalty_address, seller));
+
+    Ok(rsp)
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let Offer {
+        bidder,
+        price,
+        expires,
+        ..
+    } = offers().load(deps.storage, offer_id)?;
+    if info.sender != bidder && !expires.is_expired(&env.block) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    offers().remove(deps.storage, offer_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("bidder", bidder.as_str())
+        .add_message(BankMsg::Send {
+            to_address: bidder.to_string(),
+            amount: coins(price.u128(), NATIVE_DENOM),
+        }))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Offer { offer_id } => to_binary(&query_offer(deps, offer_id)?),
+        QueryMsg::OffersByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&query_offers_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OffersByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&query_offers_by_bidder(deps, bidder, start_after, limit)?),
+    }
+}
+
+pub fn query_offer(deps: Deps, offer_id: u64) -> StdResult<OfferResponse> {
+    let offer = offers().load(deps.storage, offer_id)?;
+    Ok(_offer_response(offer_id, offer))
+}
+
+pub fn query_offers_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let offers = offers()
+        .idx
+        .collection
+        .prefix(collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, offer)| _offer_response(id, offer)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_offers_by_bidder(
+    deps: Deps,
+    bidder: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let bidder = deps.api.addr_validate(&bidder)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let offers = offers()
+        .idx
+        .bidder
+        .prefix(bidder)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, offer)| _offer_response(id, offer)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(OffersResponse { offers })
+}
+
+/********************************* HELPERS ************************************/
+
+fn _offer_response(offer_id: u64, offer: Offer) -> OfferResponse {
+    OfferResponse {
+        offer_id,
+        bidder: offer.bidder.to_string(),
+        collection: offer.collection.to_string(),
+        token_id: offer.token_id,
+        price: Coin::new(offer.price.u128(), NATIVE_DENOM),
+        expires: offer.expires,
+    }
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{Addr, OwnedDeps};
+    use s1::ROYALTY_FEE;
+    use s_nft::testing::mock_nft_querier;
+    use s_std::{create_fund_community_pool_msg, SubMsg};
+
+    const S721: &str = "s721";
+    const ROYALTY: &str = "royalty";
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        // nft contract returns the royalty address
+        deps.querier.update_wasm(mock_nft_querier(ROYALTY));
+        deps
+    }
+
+    fn make_offer(
+        deps: DepsMut,
+        bidder: &str,
+        token_id: Option<&str>,
+        price: u128,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(bidder, &coins(price, NATIVE_DENOM)),
+            ExecuteMsg::MakeOffer {
+                collection: S721.to_string(),
+                token_id: token_id.map(String::from),
+                expires,
+            },
+        )
+    }
+
+    fn accept(
+        deps: DepsMut,
+        env: Env,
+        collection: &str,
+        token_id: &str,
+        offer_id: u64,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "owner".to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary(&AcceptMsg { offer_id }).unwrap(),
+        });
+        execute(deps, env, mock_info(collection, &[]), msg)
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "instantiate")
+                .add_attribute("contract_name", CONTRACT_NAME)
+                .add_attribute("contract_version", CONTRACT_VERSION)
+        );
+        assert_eq!(OFFER_COUNT.load(&deps.storage).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_make_offer() {
+        let mut deps = setup_contract();
+
+        // Error: offer does not cover the transfer fee
+        assert!(matches!(
+            make_offer(deps.as_mut(), "alice", None, ROYALTY_FEE, None),
+            Err(ContractError::InvalidPrice { fee: ROYALTY_FEE })
+        ));
+        // Error: already expired
+        let expires = Expiration::AtHeight(mock_env().block.height);
+        assert!(matches!(
+            make_offer(deps.as_mut(), "alice", None, 100_000, Some(expires)),
+            Err(ContractError::Expired {})
+        ));
+
+        let res = make_offer(deps.as_mut(), "alice", Some("token"), 100_000, None).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "make_offer")
+                .add_attribute("offer_id", "1")
+                .add_attribute("bidder", "alice")
+                .add_attribute("collection", S721)
+                .add_attribute("token_id", "token")
+                .add_attribute("price", "100000usign")
+        );
+        make_offer(deps.as_mut(), "bob", None, 50_000, None).unwrap();
+
+        // query offers by collection
+        let res: OffersResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OffersByCollection {
+                    collection: S721.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.offers,
+            vec![
+                OfferResponse {
+                    offer_id: 1,
+                    bidder: "alice".to_string(),
+                    collection: S721.to_string(),
+                    token_id: Some("token".to_string()),
+                    price: Coin::new(100_000, NATIVE_DENOM),
+                    expires: Expiration::Never {},
+                },
+                OfferResponse {
+                    offer_id: 2,
+                    bidder: "bob".to_string(),
+                    collection: S721.to_string(),
+                    token_id: None,
+                    price: Coin::new(50_000, NATIVE_DENOM),
+                    expires: Expiration::Never {},
+                }
+            ]
+        );
+
+        // query offers by bidder
+        let res = query_offers_by_bidder(deps.as_ref(), "bob".to_string(), None, None).unwrap();
+        assert_eq!(res.offers.len(), 1);
+        assert_eq!(res.offers[0].offer_id, 2);
+    }
+
+    #[test]
+    fn test_accept_offer() {
+        let mut deps = setup_contract();
+        make_offer(deps.as_mut(), "alice", Some("token"), 100_000, None).unwrap();
+        make_offer(deps.as_mut(), "bob", None, 50_000, None).unwrap();
+
+        // Error: token of another collection
+        assert!(matches!(
+            accept(deps.as_mut(), mock_env(), "other", "token", 1),
+            Err(ContractError::WrongToken { .. })
+        ));
+        // Error: offer is on another token
+        assert!(matches!(
+            accept(deps.as_mut(), mock_env(), S721, "token2", 1),
+            Err(ContractError::WrongToken { .. })
+        ));
+
+        // accept token offer, bidder receives the token and owner the offer less fee and royalties
+        let res = accept(deps.as_mut(), mock_env(), S721, "token", 1).unwrap();
+        let nft = Nft::S721 {
+            contract: Addr::unchecked(S721),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(
+            res.messages,
+            vec![
+                nft.transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "alice", ROYALTY_FEE)
+                    .unwrap(),
+                SubMsg::new(BankMsg::Send {
+                    to_address: ROYALTY.to_string(),
+                    amount: coins(4702, NATIVE_DENOM),
+                }),
+                SubMsg::new(create_fund_community_pool_msg(coins(248, NATIVE_DENOM))),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "owner".to_string(),
+                    amount: coins(94_050, NATIVE_DENOM),
+                }),
+            ]
+        );
+        assert!(query_offer(deps.as_ref(), 1).is_err());
+
+        // collection offer accepts any token
+        let res = accept(deps.as_mut(), mock_env(), S721, "token2", 2).unwrap();
+        assert_eq!(
+            res.messages[0],
+            Nft::S721 {
+                contract: Addr::unchecked(S721),
+                token_id: "token2".to_string(),
+            }
+            .transfer_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), "bob", ROYALTY_FEE)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expiry_and_cancel() {
+        let mut deps = setup_contract();
+        let mut env = mock_env();
+        let expires = Expiration::AtTime(env.block.time.plus_seconds(100));
+        make_offer(deps.as_mut(), "alice", None, 100_000, Some(expires)).unwrap();
+
+        // Error: only bidder can cancel an active offer
+        assert!(matches!(
+            execute_cancel(deps.as_mut(), env.clone(), mock_info("random", &[]), 1),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // Error: expired offer cannot be accepted
+        env.block.time = env.block.time.plus_seconds(100);
+        assert!(matches!(
+            accept(deps.as_mut(), env.clone(), S721, "token", 1),
+            Err(ContractError::Expired {})
+        ));
+
+        // anyone can refund an expired offer to the bidder
+        let res = execute_cancel(deps.as_mut(), env, mock_info("random", &[]), 1).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(100_000, NATIVE_DENOM),
+            })]
+        );
+        assert!(query_offer(deps.as_ref(), 1).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Offer must exceed the transfer fee of {fee}")]
+    InvalidPrice { fee: u128 },
+
+    #[error("Offer expired")]
+    Expired {},
+
+    #[error("Token {token_id} does not match the offer")]
+    WrongToken { token_id: String },
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
@@ -0,0 +1,64 @@
+use cosmwasm_std::Coin;
+use cw721::Cw721ReceiveMsg;
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Escrow the usign sent as an offer on `token_id`, or on any token of the collection.
+    /// The transfer fee is deducted from the offer on acceptance.
+    MakeOffer {
+        collection: String,
+        token_id: Option<String>,
+        expires: Option<Expiration>,
+    },
+    /// Accept an offer, token sent with `SendNft` and an `AcceptMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Refund the offer to the bidder, anyone can cancel an expired offer
+    Cancel { offer_id: u64 },
+}
+
+/// Message embedded when sending a token to accept an offer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AcceptMsg {
+    pub offer_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Offer {
+        offer_id: u64,
+    },
+    /// Offers on a collection, including offers on its tokens
+    OffersByCollection {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    OffersByBidder {
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferResponse {
+    pub offer_id: u64,
+    pub bidder: String,
+    pub collection: String,
+    pub token_id: Option<String>,
+    pub price: Coin,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<OfferResponse>,
+}
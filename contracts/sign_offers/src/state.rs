@@ -0,0 +1,45 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    pub bidder: Addr,
+    /// s721 contract
+    pub collection: Addr,
+    /// Token the offer is made on, any token of the collection when None
+    pub token_id: Option<String>,
+    /// Amount escrowed in usign, transfer fee is deducted on acceptance
+    pub price: Uint128,
+    pub expires: Expiration,
+}
+
+/// Number of offers created, used as the id of the next offer
+pub const OFFER_COUNT: Item<u64> = Item::new("offer_count");
+
+pub struct OfferIndexes<'a> {
+    pub bidder: MultiIndex<'a, Addr, Offer, u64>,
+    pub collection: MultiIndex<'a, Addr, Offer, u64>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.bidder, &self.collection];
+        Box::new(v.into_iter())
+    }
+}
+
+// Offer id -> Offer
+pub fn offers<'a>() -> IndexedMap<'a, u64, Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        bidder: MultiIndex::new(|d: &Offer| d.bidder.clone(), "offers", "offers__bidder"),
+        collection: MultiIndex::new(
+            |d: &Offer| d.collection.clone(),
+            "offers",
+            "offers__collection",
+        ),
+    };
+    IndexedMap::new("offers", indexes)
+}
@@ -0,0 +1,700 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut};
+use cosmwasm_std::{Env, MessageInfo, Order, StdResult, Uint128};
+use cw2::set_contract_version;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use s_std::{Response, SubMsg, NATIVE_DENOM};
+
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StakeResponse, StakerResponse,
+    StakesResponse,
+};
+use crate::state::{
+    Config, RewardPool, Stake, Staker, CONFIG, REWARD_POOL, STAKERS, STAKES, STAKE_COUNT,
+};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_staking";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let collections = msg
+        .collections
+        .iter()
+        .map(|collection| deps.api.addr_validate(collection))
+        .collect::<StdResult<Vec<_>>>()?;
+    let config = Config {
+        collections,
+        reward_per_block: msg.reward_per_block,
+        unbonding_period: msg.unbonding_period,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    REWARD_POOL.save(
+        deps.storage,
+        &RewardPool {
+            total_weight: Uint128::zero(),
+            reward_per_weight: Decimal::zero(),
+            last_height: env.block.height,
+            balance: Uint128::zero(),
+        },
+    )?;
+    STAKE_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute_batch_receive(deps, env, info, msg),
+        ExecuteMsg::Fund {} => execute_fund(deps, env, info),
+        ExecuteMsg::Unstake { stake_id } => execute_unstake(deps, env, info, stake_id),
+        ExecuteMsg::Withdraw { stake_id } => execute_withdraw(deps, env, info, stake_id),
+        ExecuteMsg::ClaimRewards {} => execute_claim_rewards(deps, env, info),
+    }
+}
+
+/// s721 token sent with `SendNft`, sender of the nft contract is the staker
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let staker = deps.api.addr_validate(&msg.sender)?;
+    let nft = Nft::S721 {
+        contract: info.sender,
+        token_id: msg.token_id,
+    };
+    _stake(deps, env, staker, nft)
+}
+
+/// s1155 tokens sent with `SendFrom`, previous owner of the tokens is the staker
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let staker = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: vec![(msg.token_id, msg.amount)],
+    };
+    _stake(deps, env, staker, nft)
+}
+
+/// s1155 tokens sent with `BatchSendFrom`, staked and withdrawn together
+pub fn execute_batch_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: BatchReceiveMsg,
+) -> Result<Response, ContractError> {
+    let staker = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    let nft = Nft::S1155 {
+        contract: info.sender,
+        batch: msg.batch,
+    };
+    _stake(deps, env, staker, nft)
+}
+
+/// Anyone can fund the reward pool
+pub fn execute_fund(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let amount = must_pay(&info, NATIVE_DENOM)?;
+
+    // Distribute past blocks first so the funds only reward future blocks
+    let mut pool = _update_pool(deps.as_ref(), &env)?;
+    pool.balance = pool.balance.checked_add(amount)?;
+    REWARD_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("amount", Coin::new(amount.u128(), NATIVE_DENOM).to_string())
+        .add_attribute(
+            "reward_pool",
+            Coin::new(pool.balance.u128(), NATIVE_DENOM).to_string(),
+        ))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stake_id: u64,
+) -> Result<Response, ContractError> {
+    let mut stake = STAKES.load(deps.storage, (&info.sender, stake_id))?;
+    if stake.unbonding.is_some() {
+        return Err(ContractError::AlreadyUnbonding {});
+    }
+
+    // Unbonding tokens stop earning rewards
+    let (mut pool, mut staker) = _settle(deps.as_ref(), &env, &info.sender)?;
+    staker.weight = staker.weight.checked_sub(stake.weight)?;
+    pool.total_weight = pool.total_weight.checked_sub(stake.weight)?;
+    REWARD_POOL.save(deps.storage, &pool)?;
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+
+    let unbonding = CONFIG
+        .load(deps.storage)?
+        .unbonding_period
+        .after(&env.block);
+    stake.unbonding = Some(unbonding);
+    STAKES.save(deps.storage, (&info.sender, stake_id), &stake)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unstake")
+        .add_attribute("stake_id", stake_id.to_string())
+        .add_attribute("staker", info.sender.as_str())
+        .add_attribute("unbonding", unbonding.to_string()))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stake_id: u64,
+) -> Result<Response, ContractError> {
+    let stake = STAKES.load(deps.storage, (&info.sender, stake_id))?;
+    match stake.unbonding {
+        Some(unbonding) if unbonding.is_expired(&env.block) => {}
+        _ => return Err(ContractError::StillBonded {}),
+    }
+
+    // Staker pays the fee to get the tokens back
+    let fee = stake.nft.transfer_fee()?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment.u128() != fee {
+        return Err(ContractError::IncorrectPayment {
+            expected: fee,
+            received: payment.u128(),
+        });
+    }
+
+    STAKES.remove(deps.storage, (&info.sender, stake_id));
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw")
+        .add_attribute("stake_id", stake_id.to_string())
+        .add_attribute("staker", info.sender.as_str())
+        .add_submessage(stake.nft.transfer_msg(
+            &env.contract.address,
+            info.sender.as_str(),
+            fee,
+        )?))
+}
+
+pub fn execute_claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let (pool, mut staker) = _settle(deps.as_ref(), &env, &info.sender)?;
+    if staker.pending.is_zero() {
+        return Err(ContractError::NoRewards {});
+    }
+    let rewards = staker.pending;
+    staker.pending = Uint128::zero();
+    REWARD_POOL.save(deps.storage, &pool)?;
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("staker", info.sender.as_str())
+        .add_attribute(
+            "amount",
+            Coin::new(rewards.u128(), NATIVE_DENOM).to_string(),
+        )
+        .add_submessage(SubMsg::new(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(rewards.u128(), NATIVE_DENOM),
+        })))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Staker { address } => to_binary(&query_staker(deps, env, address)?),
+        QueryMsg::Stakes {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_stakes(deps, owner, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = REWARD_POOL.load(deps.storage)?;
+    Ok(ConfigResponse {
+        collections: config
+            .collections
+            .into_iter()
+            .map(|collection| collection.to_string())
+            .collect(),
+        reward_per_block: Coin::new(config.reward_per_block.u128(), NATIVE_DENOM),
+        unbonding_period: config.unbonding_period,
+        total_weight: pool.total_weight,
+        reward_pool: Coin::new(pool.balance.u128(), NATIVE_DENOM),
+    })
+}
+
+/// Pending rewards include the blocks since the last update
+pub fn query_staker(deps: Deps, env: Env, address: String) -> StdResult<StakerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let (_, staker) = _settle(deps, &env, &address)?;
+    Ok(StakerResponse {
+        address: address.to_string(),
+        weight: staker.weight,
+        pending_rewards: Coin::new(staker.pending.u128(), NATIVE_DENOM),
+    })
+}
+
+pub fn query_stakes(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StakesResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let stakes = STAKES
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(stake_id, stake)| StakeResponse {
+                stake_id,
+                nft: stake.nft,
+                weight: stake.weight,
+                unbonding: stake.unbonding,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(StakesResponse { stakes })
+}
+
+/********************************* HELPERS ************************************/
+
+fn _stake(deps: DepsMut, env: Env, owner: Addr, nft: Nft) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.collections.contains(nft.contract()) {
+        return Err(ContractError::CollectionNotAllowed {
+            collection: nft.contract().to_string(),
+        });
+    }
+
+    // Every s721 token or s1155 unit weighs 1
+    let weight = match &nft {
+        Nft::S721 { .. } => Uint128::new(1),
+        Nft::S1155 { batch, .. } => batch
+            .iter()
+            .try_fold(Uint128::zero(), |total, (_, value)| {
+                total.checked_add(*value)
+            })?,
+    };
+    if weight.is_zero() {
+        return Err(ContractError::EmptyStake {});
+    }
+
+    let (mut pool, mut staker) = _settle(deps.as_ref(), &env, &owner)?;
+    staker.weight = staker.weight.checked_add(weight)?;
+    pool.total_weight = pool.total_weight.checked_add(weight)?;
+    REWARD_POOL.save(deps.storage, &pool)?;
+    STAKERS.save(deps.storage, &owner, &staker)?;
+
+    let stake_id = STAKE_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    let stake = Stake {
+        nft,
+        weight,
+        unbonding: None,
+    };
+    STAKES.save(deps.storage, (&owner, stake_id), &stake)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake")
+        .add_attribute("stake_id", stake_id.to_string())
+        .add_attribute("staker", owner)
+        .add_attribute("weight", weight))
+}
+
+fn _update_pool(deps: Deps, env: &Env) -> StdResult<RewardPool> {
+    let reward_per_block = CONFIG.load(deps.storage)?.reward_per_block;
+    let mut pool = REWARD_POOL.load(deps.storage)?;
+    pool.update(reward_per_block, env.block.height)?;
+    Ok(pool)
+}
+
+/// Updated pool and staker with the rewards up to the current block, not saved
+fn _settle(deps: Deps, env: &Env, address: &Addr) -> StdResult<(RewardPool, Staker)> {
+    let pool = _update_pool(deps, env)?;
+    let mut staker = STAKERS.may_load(deps.storage, address)?.unwrap_or_default();
+    staker.settle(&pool)?;
+    Ok((pool, staker))
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{from_binary, OwnedDeps, WasmMsg};
+    use cw_utils::{Duration, Expiration};
+    use s1::ROYALTY_FEE;
+    use s1155::msg::ExecuteMsg as S1155ExecuteMsg;
+    use s721::msg::ExecuteMsg as S721ExecuteMsg;
+    use s_nft::Nft;
+
+    const S721: &str = "s721";
+    const S1155: &str = "s1155";
+    const REWARD_PER_BLOCK: u128 = 1_000;
+    const UNBONDING_BLOCKS: u64 = 100;
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                collections: vec![S721.to_string(), S1155.to_string()],
+                reward_per_block: Uint128::new(REWARD_PER_BLOCK),
+                unbonding_period: Duration::Height(UNBONDING_BLOCKS),
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    fn env_at(blocks: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height += blocks;
+        env
+    }
+
+    fn fund(deps: DepsMut, env: Env, amount: u128) {
+        execute(
+            deps,
+            env,
+            mock_info("funder", &coins(amount, NATIVE_DENOM)),
+            ExecuteMsg::Fund {},
+        )
+        .unwrap();
+    }
+
+    fn stake_s721(deps: DepsMut, env: Env, owner: &str, token_id: &str) {
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: owner.to_string(),
+            token_id: token_id.to_string(),
+            msg: Binary::default(),
+        });
+        execute(deps, env, mock_info(S721, &[]), msg).unwrap();
+    }
+
+    fn stake_s1155(deps: DepsMut, env: Env, owner: &str, amount: u128) {
+        let msg = ExecuteMsg::Receive(ReceiveMsg {
+            operator: owner.to_string(),
+            from: Some(owner.to_string()),
+            token_id: "token".to_string(),
+            amount: Uint128::new(amount),
+            msg: Binary::default(),
+        });
+        execute(deps, env, mock_info(S1155, &[]), msg).unwrap();
+    }
+
+    fn pending_rewards(deps: Deps, env: Env, address: &str) -> u128 {
+        let msg = QueryMsg::Staker {
+            address: address.to_string(),
+        };
+        let res: StakerResponse = from_binary(&query(deps, env, msg).unwrap()).unwrap();
+        res.pending_rewards.amount.u128()
+    }
+
+    #[test]
+    fn test_stake() {
+        let mut deps = setup_contract();
+
+        // Error: collection not accepted
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "1".to_string(),
+            msg: Binary::default(),
+        });
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info("other", &[]), msg),
+            Err(ContractError::CollectionNotAllowed { .. })
+        ));
+
+        stake_s721(deps.as_mut(), mock_env(), "alice", "1");
+        stake_s1155(deps.as_mut(), mock_env(), "alice", 3);
+
+        let res: StakesResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Stakes {
+                    owner: "alice".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.stakes,
+            vec![
+                StakeResponse {
+                    stake_id: 1,
+                    nft: Nft::S721 {
+                        contract: Addr::unchecked(S721),
+                        token_id: "1".to_string(),
+                    },
+                    weight: Uint128::new(1),
+                    unbonding: None,
+                },
+                StakeResponse {
+                    stake_id: 2,
+                    nft: Nft::S1155 {
+                        contract: Addr::unchecked(S1155),
+                        batch: vec![("token".to_string(), Uint128::new(3))],
+                    },
+                    weight: Uint128::new(3),
+                    unbonding: None,
+                },
+            ]
+        );
+
+        let res = query_config(deps.as_ref()).unwrap();
+        assert_eq!(res.total_weight, Uint128::new(4));
+    }
+
+    #[test]
+    fn test_rewards() {
+        let mut deps = setup_contract();
+        fund(deps.as_mut(), mock_env(), 100_000);
+
+        // alice earns every reward while staking alone
+        stake_s721(deps.as_mut(), mock_env(), "alice", "1");
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(10), "alice"), 10_000);
+
+        // bob stakes 3 units after 10 blocks, rewards are then shared 1:3
+        stake_s1155(deps.as_mut(), env_at(10), "bob", 3);
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(20), "alice"), 12_500);
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(20), "bob"), 7_500);
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(20),
+            mock_info("bob", &[]),
+            ExecuteMsg::ClaimRewards {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(7_500, NATIVE_DENOM),
+            })]
+        );
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(20), "bob"), 0);
+
+        // Error: nothing left to claim
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env_at(20),
+                mock_info("bob", &[]),
+                ExecuteMsg::ClaimRewards {},
+            ),
+            Err(ContractError::NoRewards {})
+        ));
+
+        // distribution stops once the pool is empty
+        assert_eq!(
+            pending_rewards(deps.as_ref(), env_at(1000), "alice"),
+            32_500
+        );
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(1000), "bob"), 60_000);
+    }
+
+    #[test]
+    fn test_unstake() {
+        let mut deps = setup_contract();
+        fund(deps.as_mut(), mock_env(), 100_000);
+        stake_s721(deps.as_mut(), mock_env(), "alice", "1");
+
+        // Error: only the staker can unstake
+        assert!(execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("bob", &[]),
+            ExecuteMsg::Unstake { stake_id: 1 },
+        )
+        .is_err());
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &[]),
+            ExecuteMsg::Unstake { stake_id: 1 },
+        )
+        .unwrap();
+        let unbonding = Expiration::AtHeight(env_at(10).block.height + UNBONDING_BLOCKS);
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "unbonding" && attr.value == unbonding.to_string()));
+
+        // unbonding tokens stop earning rewards
+        assert_eq!(pending_rewards(deps.as_ref(), env_at(50), "alice"), 10_000);
+        assert_eq!(
+            query_config(deps.as_ref()).unwrap().total_weight,
+            Uint128::zero()
+        );
+
+        // Error: already unbonding
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env_at(20),
+                mock_info("alice", &[]),
+                ExecuteMsg::Unstake { stake_id: 1 },
+            ),
+            Err(ContractError::AlreadyUnbonding {})
+        ));
+
+        // Error: unbonding period not over
+        let fee = coins(ROYALTY_FEE, NATIVE_DENOM);
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env_at(50),
+                mock_info("alice", &fee),
+                ExecuteMsg::Withdraw { stake_id: 1 },
+            ),
+            Err(ContractError::StillBonded {})
+        ));
+
+        // Error: transfer fee not paid
+        let env = env_at(10 + UNBONDING_BLOCKS);
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("alice", &coins(1, NATIVE_DENOM)),
+                ExecuteMsg::Withdraw { stake_id: 1 },
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("alice", &fee),
+            ExecuteMsg::Withdraw { stake_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: S721.to_string(),
+                msg: to_binary(&S721ExecuteMsg::TransferNft {
+                    recipient: "alice".to_string(),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: fee,
+            })]
+        );
+        assert!(STAKES
+            .may_load(deps.as_ref().storage, (&Addr::unchecked("alice"), 1))
+            .unwrap()
+            .is_none());
+
+        // rewards earned before unstaking can still be claimed
+        execute(
+            deps.as_mut(),
+            env_at(200),
+            mock_info("alice", &[]),
+            ExecuteMsg::ClaimRewards {},
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_withdraw_s1155() {
+        let mut deps = setup_contract();
+        stake_s1155(deps.as_mut(), mock_env(), "alice", 2);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::Unstake { stake_id: 1 },
+        )
+        .unwrap();
+
+        let fee = coins(ROYALTY_FEE, NATIVE_DENOM);
+        let res = execute(
+            deps.as_mut(),
+            env_at(UNBONDING_BLOCKS),
+            mock_info("alice", &fee),
+            ExecuteMsg::Withdraw { stake_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: S1155.to_string(),
+                msg: to_binary(&S1155ExecuteMsg::SendFrom {
+                    from: MOCK_CONTRACT_ADDR.to_string(),
+                    to: "alice".to_string(),
+                    token_id: "token".to_string(),
+                    value: Uint128::new(2),
+                    msg: None,
+                })
+                .unwrap(),
+                funds: fee,
+            })]
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::{OverflowError, StdError};
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Collection cannot be staked: {collection}")]
+    CollectionNotAllowed { collection: String },
+
+    #[error("Nothing to stake")]
+    EmptyStake {},
+
+    #[error("Stake is already unbonding")]
+    AlreadyUnbonding {},
+
+    #[error("Stake is not unbonded yet")]
+    StillBonded {},
+
+    #[error("No rewards to claim")]
+    NoRewards {},
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
@@ -0,0 +1,81 @@
+use cosmwasm_std::{Coin, Uint128};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::{Duration, Expiration};
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// s721 and s1155 contracts accepted for staking
+    pub collections: Vec<String>,
+    /// usign shared every block by all staked tokens
+    pub reward_per_block: Uint128,
+    /// Time or blocks before unstaked tokens can be withdrawn
+    pub unbonding_period: Duration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Stake a s721 token, sent with `SendNft`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Stake s1155 tokens, sent with `SendFrom`
+    Receive(ReceiveMsg),
+    /// Stake a batch of s1155 tokens as a single stake, sent with `BatchSendFrom`
+    BatchReceive(BatchReceiveMsg),
+    /// Add the usign sent to the reward pool
+    Fund {},
+    /// Stop earning rewards on a stake and start its unbonding period
+    Unstake { stake_id: u64 },
+    /// Return the tokens of an unbonded stake, paying the transfer fee in usign
+    Withdraw { stake_id: u64 },
+    /// Send the pending rewards of the sender
+    ClaimRewards {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Staked weight and rewards of an address up to the current block
+    Staker {
+        address: String,
+    },
+    Stakes {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub collections: Vec<String>,
+    pub reward_per_block: Coin,
+    pub unbonding_period: Duration,
+    pub total_weight: Uint128,
+    /// Rewards left to distribute
+    pub reward_pool: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerResponse {
+    pub address: String,
+    pub weight: Uint128,
+    pub pending_rewards: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakeResponse {
+    pub stake_id: u64,
+    pub nft: Nft,
+    pub weight: Uint128,
+    pub unbonding: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakesResponse {
+    pub stakes: Vec<StakeResponse>,
+}
@@ -0,0 +1,84 @@
+use cosmwasm_std::{Addr, Decimal, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// s721 and s1155 contracts accepted for staking
+    pub collections: Vec<Addr>,
+    /// usign shared every block by all staked tokens
+    pub reward_per_block: Uint128,
+    pub unbonding_period: Duration,
+}
+
+/// Rewards accumulated per staked token since the contract was instantiated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardPool {
+    /// Staked tokens, a s721 token or a s1155 unit each weigh 1
+    pub total_weight: Uint128,
+    pub reward_per_weight: Decimal,
+    pub last_height: u64,
+    /// Funded usign not yet distributed
+    pub balance: Uint128,
+}
+
+impl RewardPool {
+    /// Distribute the rewards of the blocks since the last update, up to the pool balance
+    pub fn update(&mut self, reward_per_block: Uint128, height: u64) -> StdResult<()> {
+        if height <= self.last_height {
+            return Ok(());
+        }
+        // Rewards stay in the pool while nothing is staked
+        if !self.total_weight.is_zero() {
+            let reward = reward_per_block
+                .checked_mul((height - self.last_height).into())?
+                .min(self.balance);
+            self.reward_per_weight += Decimal::from_ratio(reward, self.total_weight);
+            self.balance -= reward;
+        }
+        self.last_height = height;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Staker {
+    pub weight: Uint128,
+    /// Pool `reward_per_weight` when the rewards were last settled
+    pub reward_per_weight: Decimal,
+    pub pending: Uint128,
+}
+
+impl Staker {
+    /// Add the rewards accrued since the last settlement to the pending rewards
+    pub fn settle(&mut self, pool: &RewardPool) -> StdResult<()> {
+        let accrued = self.weight * (pool.reward_per_weight - self.reward_per_weight);
+        self.pending = self.pending.checked_add(accrued)?;
+        self.reward_per_weight = pool.reward_per_weight;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stake {
+    pub nft: Nft,
+    pub weight: Uint128,
+    /// Set on unstake, the tokens can be withdrawn once expired
+    pub unbonding: Option<Expiration>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const REWARD_POOL: Item<RewardPool> = Item::new("reward_pool");
+
+/// Number of stakes created, used as the id of the next stake
+pub const STAKE_COUNT: Item<u64> = Item::new("stake_count");
+
+// Staker address -> Staker
+pub const STAKERS: Map<&Addr, Staker> = Map::new("stakers");
+
+// (Staker address, stake id) -> Stake
+pub const STAKES: Map<(&Addr, u64), Stake> = Map::new("stakes");
@@ -0,0 +1,681 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, BankMsg, Binary, Coin, Decimal, Deps};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Reply, StdError, StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+use s_nft::Nft;
+use s_std::{Response, SubMsg, NATIVE_DENOM};
+
+use crate::msg::{
+    BuyoutPriceResponse, BuyoutResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    ReceiveMsg, TokenInstantiateMsg,
+};
+use crate::state::{Buyout, Config, BUYOUT, CONFIG, NFT, SHARE_TOKEN};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_vault";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_SHARE_TOKEN_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.supply.is_zero() {
+        return Err(ContractError::InvalidVault {
+            reason: "supply must be greater than 0".to_string(),
+        });
+    }
+    if msg.buyout_threshold.is_zero() || msg.buyout_threshold > Decimal::one() {
+        return Err(ContractError::InvalidVault {
+            reason: "buyout threshold must be between 0 and 1".to_string(),
+        });
+    }
+
+    let config = Config {
+        curator: info.sender,
+        collection: deps.api.addr_validate(&msg.collection)?,
+        supply: msg.supply,
+        reserve_price: msg.reserve_price,
+        buyout_threshold: msg.buyout_threshold,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    // Shares are minted by the vault once the token is locked
+    let instantiate_share_token_msg = WasmMsg::Instantiate {
+        code_id: msg.token_code_id,
+        funds: vec![],
+        admin: Some(env.contract.address.to_string()),
+        label: format!("shares-{}", msg.symbol),
+        msg: to_binary(&TokenInstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            initial_balances: vec![],
+            mint: Some(MinterResponse {
+                minter: env.contract.address.to_string(),
+                cap: Some(msg.supply),
+            }),
+        })?,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION)
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate_share_token_msg,
+            INSTANTIATE_SHARE_TOKEN_REPLY_ID,
+        )))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info, msg),
+        ExecuteMsg::Buyout { shares } => execute_buyout(deps, env, info, shares),
+    }
+}
+
+/// s721 token sent with `SendNft` by the curator, who receives the whole supply of shares
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.collection {
+        return Err(ContractError::CollectionNotAllowed {
+            collection: info.sender.to_string(),
+        });
+    }
+    if msg.sender != config.curator {
+        return Err(ContractError::Unauthorized {});
+    }
+    if NFT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyLocked {});
+    }
+
+    NFT.save(
+        deps.storage,
+        &Nft::S721 {
+            contract: info.sender,
+            token_id: msg.token_id.clone(),
+        },
+    )?;
+
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: SHARE_TOKEN.load(deps.storage)?.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: config.curator.to_string(),
+            amount: config.supply,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("token_id", msg.token_id)
+        .add_attribute("curator", config.curator)
+        .add_attribute("shares", config.supply)
+        .add_submessage(SubMsg::new(mint_msg)))
+}
+
+/// Shares sent with `Send`
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != SHARE_TOKEN.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    match from_binary(&msg.msg)? {
+        ReceiveMsg::Claim {} => _claim(deps, info, msg),
+    }
+}
+
+pub fn execute_buyout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let nft = NFT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotLocked {})?;
+    if BUYOUT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::BoughtOut {});
+    }
+
+    let required = config.supply * config.buyout_threshold;
+    if shares < required || shares > config.supply {
+        return Err(ContractError::InsufficientShares { required });
+    }
+
+    // Buyer pays the other holders at the reserve price and the fee to get the token
+    let price = _buyout_price(&config, shares);
+    let fee = nft.transfer_fee()?;
+    let expected = price
+        .checked_add(Uint128::new(fee))
+        .map_err(StdError::overflow)?
+        .u128();
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment.u128() != expected {
+        return Err(ContractError::IncorrectPayment {
+            expected,
+            received: payment.u128(),
+        });
+    }
+
+    let buyout = Buyout {
+        buyer: info.sender.clone(),
+        proceeds: price,
+        outstanding: config.supply - shares,
+    };
+    BUYOUT.save(deps.storage, &buyout)?;
+
+    // Shares of the buyer are burnt from the allowance given to the vault
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: SHARE_TOKEN.load(deps.storage)?.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
+            owner: info.sender.to_string(),
+            amount: shares,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "buyout")
+        .add_attribute("buyer", info.sender.as_str())
+        .add_attribute("shares", shares)
+        .add_attribute("price", Coin::new(price.u128(), NATIVE_DENOM).to_string())
+        .add_submessage(SubMsg::new(burn_msg))
+        .add_submessage(nft.transfer_msg(&env.contract.address, info.sender.as_str(), fee)?))
+}
+
+/********************************* REPLY **************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_SHARE_TOKEN_REPLY_ID {
+        return Err(ContractError::UnknownReplyId { id: msg.id });
+    };
+    let res = parse_reply_instantiate_data(msg)
+        .map_err(|_| ContractError::InstantiateShareTokenError {})?;
+
+    // Validate contract address
+    let share_token = deps.api.addr_validate(&res.contract_address)?;
+    SHARE_TOKEN.save(deps.storage, &share_token)?;
+
+    Ok(Response::new().add_attribute("share_token", share_token))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Buyout {} => to_binary(&query_buyout(deps)?),
+        QueryMsg::BuyoutPrice { shares } => to_binary(&query_buyout_price(deps, shares)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        curator: config.curator.to_string(),
+        collection: config.collection.to_string(),
+        share_token: SHARE_TOKEN
+            .may_load(deps.storage)?
+            .map(|share_token| share_token.to_string()),
+        supply: config.supply,
+        reserve_price: Coin::new(config.reserve_price.u128(), NATIVE_DENOM),
+        buyout_threshold: config.buyout_threshold,
+        nft: NFT.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_buyout(deps: Deps) -> StdResult<Option<BuyoutResponse>> {
+    Ok(BUYOUT.may_load(deps.storage)?.map(|buyout| BuyoutResponse {
+        buyer: buyout.buyer.to_string(),
+        proceeds: Coin::new(buyout.proceeds.u128(), NATIVE_DENOM),
+        outstanding_shares: buyout.outstanding,
+    }))
+}
+
+pub fn query_buyout_price(deps: Deps, shares: Uint128) -> StdResult<BuyoutPriceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee = match NFT.may_load(deps.storage)? {
+        Some(nft) => nft
+            .transfer_fee()
+            .map_err(|err| StdError::generic_err(err.to_string()))?,
+        None => 0,
+    };
+    Ok(BuyoutPriceResponse {
+        price: Coin::new(_buyout_price(&config, shares).u128(), NATIVE_DENOM),
+        transfer_fee: Coin::new(fee, NATIVE_DENOM),
+    })
+}
+
+/********************************* HELPERS ************************************/
+
+/// Reserve price of the shares not held by the buyer
+fn _buyout_price(config: &Config, shares: Uint128) -> Uint128 {
+    config
+        .reserve_price
+        .multiply_ratio(config.supply.saturating_sub(shares), config.supply)
+}
+
+/// Burn the received shares and send their part of the proceeds to the holder
+fn _claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let mut buyout = BUYOUT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotBoughtOut {})?;
+    let amount = buyout.claim(msg.amount)?;
+    BUYOUT.save(deps.storage, &buyout)?;
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn { amount: msg.amount })?,
+        funds: vec![],
+    };
+    let mut res = Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("holder", msg.sender.as_str())
+        .add_attribute("shares", msg.amount)
+        .add_attribute("amount", Coin::new(amount.u128(), NATIVE_DENOM).to_string())
+        .add_submessage(SubMsg::new(burn_msg));
+    if !amount.is_zero() {
+        res = res.add_submessage(SubMsg::new(BankMsg::Send {
+            to_address: msg.sender,
+            amount: coins(amount.u128(), NATIVE_DENOM),
+        }));
+    }
+    Ok(res)
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{Addr, OwnedDeps};
+    use s1::ROYALTY_FEE;
+    use s721::msg::ExecuteMsg as S721ExecuteMsg;
+
+    const CURATOR: &str = "curator";
+    const S721: &str = "s721";
+    const SHARE_TOKEN_ADDR: &str = "shares";
+    const SUPPLY: u128 = 1_000;
+    const RESERVE_PRICE: u128 = 100_000;
+
+    fn instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            collection: S721.to_string(),
+            token_code_id: 1,
+            name: "Vault Shares".to_string(),
+            symbol: "VAULT".to_string(),
+            decimals: 0,
+            supply: Uint128::new(SUPPLY),
+            reserve_price: Uint128::new(RESERVE_PRICE),
+            buyout_threshold: Decimal::percent(60),
+        }
+    }
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CURATOR, &[]),
+            instantiate_msg(),
+        )
+        .unwrap();
+        // share token address is saved on reply
+        SHARE_TOKEN
+            .save(deps.as_mut().storage, &Addr::unchecked(SHARE_TOKEN_ADDR))
+            .unwrap();
+        deps
+    }
+
+    fn lock(deps: DepsMut) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: CURATOR.to_string(),
+            token_id: "1".to_string(),
+            msg: Binary::default(),
+        });
+        execute(deps, mock_env(), mock_info(S721, &[]), msg)
+    }
+
+    fn buyout(deps: DepsMut, buyer: &str, shares: u128, payment: u128) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(buyer, &coins(payment, NATIVE_DENOM)),
+            ExecuteMsg::Buyout {
+                shares: Uint128::new(shares),
+            },
+        )
+        .unwrap();
+    }
+
+    fn claim(
+        deps: DepsMut,
+        sender: &str,
+        holder: &str,
+        shares: u128,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: holder.to_string(),
+            amount: Uint128::new(shares),
+            msg: to_binary(&ReceiveMsg::Claim {}).unwrap(),
+        });
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        // Error: threshold above the supply
+        let mut msg = instantiate_msg();
+        msg.buyout_threshold = Decimal::percent(101);
+        assert!(matches!(
+            instantiate(deps.as_mut(), mock_env(), mock_info(CURATOR, &[]), msg),
+            Err(ContractError::InvalidVault { .. })
+        ));
+
+        // share token is instantiated with the vault as minter and admin, capped at the supply
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CURATOR, &[]),
+            instantiate_msg(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    code_id: 1,
+                    funds: vec![],
+                    admin: Some(MOCK_CONTRACT_ADDR.to_string()),
+                    label: "shares-VAULT".to_string(),
+                    msg: to_binary(&TokenInstantiateMsg {
+                        name: "Vault Shares".to_string(),
+                        symbol: "VAULT".to_string(),
+                        decimals: 0,
+                        initial_balances: vec![],
+                        mint: Some(MinterResponse {
+                            minter: MOCK_CONTRACT_ADDR.to_string(),
+                            cap: Some(Uint128::new(SUPPLY)),
+                        }),
+                    })
+                    .unwrap(),
+                },
+                INSTANTIATE_SHARE_TOKEN_REPLY_ID,
+            )]
+        );
+
+        let res = query_config(deps.as_ref()).unwrap();
+        assert_eq!(res.curator, CURATOR);
+        assert_eq!(res.share_token, None);
+        assert_eq!(res.nft, None);
+    }
+
+    #[test]
+    fn test_lock() {
+        let mut deps = setup_contract();
+
+        // Error: token from another collection
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: CURATOR.to_string(),
+            token_id: "1".to_string(),
+            msg: Binary::default(),
+        });
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info("other", &[]), msg),
+            Err(ContractError::CollectionNotAllowed { .. })
+        ));
+
+        // Error: only the curator can lock a token
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "1".to_string(),
+            msg: Binary::default(),
+        });
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info(S721, &[]), msg),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        // curator receives the whole supply
+        let res = lock(deps.as_mut()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: SHARE_TOKEN_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: CURATOR.to_string(),
+                    amount: Uint128::new(SUPPLY),
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+        assert_eq!(
+            query_config(deps.as_ref()).unwrap().nft,
+            Some(Nft::S721 {
+                contract: Addr::unchecked(S721),
+                token_id: "1".to_string(),
+            })
+        );
+
+        // Error: a single token per vault
+        assert!(matches!(
+            lock(deps.as_mut()),
+            Err(ContractError::AlreadyLocked {})
+        ));
+    }
+
+    #[test]
+    fn test_buyout() {
+        let mut deps = setup_contract();
+
+        // Error: nothing to buy out
+        let msg = ExecuteMsg::Buyout {
+            shares: Uint128::new(SUPPLY),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+                msg,
+            ),
+            Err(ContractError::NotLocked {})
+        ));
+        lock(deps.as_mut()).unwrap();
+
+        // Error: below the threshold
+        let msg = ExecuteMsg::Buyout {
+            shares: Uint128::new(599),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(RESERVE_PRICE, NATIVE_DENOM)),
+                msg,
+            ),
+            Err(ContractError::InsufficientShares { .. })
+        ));
+
+        // alice holds 60% and pays 40% of the reserve price with the transfer fee
+        let res = query_buyout_price(deps.as_ref(), Uint128::new(600)).unwrap();
+        assert_eq!(res.price, Coin::new(40_000, NATIVE_DENOM));
+        assert_eq!(res.transfer_fee, Coin::new(ROYALTY_FEE, NATIVE_DENOM));
+
+        // Error: transfer fee not paid
+        let msg = ExecuteMsg::Buyout {
+            shares: Uint128::new(600),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(40_000, NATIVE_DENOM)),
+                msg.clone(),
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        let payment = coins(40_000 + ROYALTY_FEE, NATIVE_DENOM);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &payment),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: SHARE_TOKEN_ADDR.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
+                        owner: "alice".to_string(),
+                        amount: Uint128::new(600),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: S721.to_string(),
+                    msg: to_binary(&S721ExecuteMsg::TransferNft {
+                        recipient: "alice".to_string(),
+                        token_id: "1".to_string(),
+                    })
+                    .unwrap(),
+                    funds: coins(ROYALTY_FEE, NATIVE_DENOM),
+                }),
+            ]
+        );
+        assert_eq!(
+            query_buyout(deps.as_ref()).unwrap(),
+            Some(BuyoutResponse {
+                buyer: "alice".to_string(),
+                proceeds: Coin::new(40_000, NATIVE_DENOM),
+                outstanding_shares: Uint128::new(400),
+            })
+        );
+
+        // Error: already bought out
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info("bob", &payment), msg),
+            Err(ContractError::BoughtOut {})
+        ));
+    }
+
+    #[test]
+    fn test_buyout_overflow() {
+        let mut deps = mock_dependencies();
+        let mut msg = instantiate_msg();
+        msg.supply = Uint128::new(1);
+        msg.buyout_threshold = Decimal::percent(50);
+        msg.reserve_price = Uint128::MAX;
+        instantiate(deps.as_mut(), mock_env(), mock_info(CURATOR, &[]), msg).unwrap();
+        SHARE_TOKEN
+            .save(deps.as_mut().storage, &Addr::unchecked(SHARE_TOKEN_ADDR))
+            .unwrap();
+        lock(deps.as_mut()).unwrap();
+
+        // Error: the reserve price with the transfer fee overflows
+        let msg = ExecuteMsg::Buyout {
+            shares: Uint128::zero(),
+        };
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(ROYALTY_FEE, NATIVE_DENOM)),
+                msg,
+            ),
+            Err(ContractError::Std(StdError::Overflow { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_claim() {
+        let mut deps = setup_contract();
+        lock(deps.as_mut()).unwrap();
+
+        // Error: no buyout yet
+        assert!(matches!(
+            claim(deps.as_mut(), SHARE_TOKEN_ADDR, "bob", 100),
+            Err(ContractError::NotBoughtOut {})
+        ));
+
+        buyout(deps.as_mut(), "alice", 700, 30_000 + ROYALTY_FEE);
+
+        // Error: shares must be sent by the share token
+        assert!(matches!(
+            claim(deps.as_mut(), "other", "bob", 100),
+            Err(ContractError::Unauthorized {})
+        ));
+
+        let res = claim(deps.as_mut(), SHARE_TOKEN_ADDR, "bob", 100).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: SHARE_TOKEN_ADDR.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Burn {
+                        amount: Uint128::new(100),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "bob".to_string(),
+                    amount: coins(10_000, NATIVE_DENOM),
+                }),
+            ]
+        );
+
+        // the rest of the proceeds go to the last holder
+        claim(deps.as_mut(), SHARE_TOKEN_ADDR, "carol", 200).unwrap();
+        let buyout = BUYOUT.load(deps.as_ref().storage).unwrap();
+        assert!(buyout.proceeds.is_zero());
+        assert!(buyout.outstanding.is_zero());
+    }
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Error instantiating the share token")]
+    InstantiateShareTokenError {},
+
+    #[error("Invalid vault: {reason}")]
+    InvalidVault { reason: String },
+
+    #[error("Collection cannot be locked: {collection}")]
+    CollectionNotAllowed { collection: String },
+
+    #[error("A token is already locked")]
+    AlreadyLocked {},
+
+    #[error("No token is locked")]
+    NotLocked {},
+
+    #[error("Token was bought out")]
+    BoughtOut {},
+
+    #[error("Token was not bought out")]
+    NotBoughtOut {},
+
+    #[error("Not enough shares to buy out: {required} required")]
+    InsufficientShares { required: Uint128 },
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
@@ -0,0 +1,93 @@
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, MinterResponse};
+use cw721::Cw721ReceiveMsg;
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// s721 contract of the token to lock
+    pub collection: String,
+    /// Code id of the cw20-base contract instantiated as the share token
+    pub token_code_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Shares minted to the curator when the token is locked
+    pub supply: Uint128,
+    /// usign paid for all the shares on buyout
+    pub reserve_price: Uint128,
+    /// Part of the supply a holder needs to buy out the token
+    pub buyout_threshold: Decimal,
+}
+
+/// Instantiate message of cw20-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Lock a s721 token sent by the curator with `SendNft` and mint the shares
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Shares sent with `Send`
+    Receive(Cw20ReceiveMsg),
+    /// Burn `shares` of the sender, allowed to the vault, and buy the remaining shares
+    /// at the reserve price. The payment includes the transfer fee of the token.
+    Buyout { shares: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Burn the shares for their part of the buyout proceeds
+    Claim {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Returns `Option<BuyoutResponse>`
+    Buyout {},
+    /// usign to pay to buy out holding `shares`
+    BuyoutPrice {
+        shares: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub curator: String,
+    pub collection: String,
+    pub share_token: Option<String>,
+    pub supply: Uint128,
+    pub reserve_price: Coin,
+    pub buyout_threshold: Decimal,
+    /// Locked token
+    pub nft: Option<Nft>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuyoutResponse {
+    pub buyer: String,
+    /// Proceeds not claimed yet
+    pub proceeds: Coin,
+    /// Shares not claimed yet
+    pub outstanding_shares: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuyoutPriceResponse {
+    /// Paid to the other holders
+    pub price: Coin,
+    /// Paid to the nft contract
+    pub transfer_fee: Coin,
+}
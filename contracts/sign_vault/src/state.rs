@@ -0,0 +1,43 @@
+use cosmwasm_std::{Addr, Decimal, StdResult, Uint128};
+use cw_storage_plus::Item;
+use s_nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Instantiator of the vault, the only one who can lock a token
+    pub curator: Addr,
+    pub collection: Addr,
+    pub supply: Uint128,
+    pub reserve_price: Uint128,
+    pub buyout_threshold: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Buyout {
+    pub buyer: Addr,
+    /// Proceeds not claimed yet
+    pub proceeds: Uint128,
+    /// Shares not burnt by the buyer or claimed yet
+    pub outstanding: Uint128,
+}
+
+impl Buyout {
+    /// Part of the proceeds paid for `shares`
+    pub fn claim(&mut self, shares: Uint128) -> StdResult<Uint128> {
+        let amount = self.proceeds.multiply_ratio(shares, self.outstanding);
+        self.proceeds -= amount;
+        self.outstanding = self.outstanding.checked_sub(shares)?;
+        Ok(amount)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// cw20 share token, saved on reply
+pub const SHARE_TOKEN: Item<Addr> = Item::new("share_token");
+
+pub const NFT: Item<Nft> = Item::new("nft");
+
+pub const BUYOUT: Item<Buyout> = Item::new("buyout");
@@ -0,0 +1,617 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply};
+use cosmwasm_std::{StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, MinterResponse};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use s_nft::Nft;
+use s_std::{Response, SubMsg, NATIVE_DENOM};
+
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, TokenInstantiateMsg, TokenResponse,
+    TokensResponse,
+};
+use crate::state::{Config, PendingWrap, CONFIG, PENDING, PENDING_COUNT, TOKENS};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:sign_wrapper";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// cw20-base limits the name to 50 bytes and the symbol to 12 letters or `-`, the symbol
+// prefix leaves room for `-` and five letters of the cw20 token index
+const MAX_TOKEN_NAME_LENGTH: usize = 50;
+const MIN_SYMBOL_LENGTH: usize = 3;
+const MAX_SYMBOL_LENGTH: usize = 6;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.symbol.len() < MIN_SYMBOL_LENGTH
+        || msg.symbol.len() > MAX_SYMBOL_LENGTH
+        || !msg
+            .symbol
+            .bytes()
+            .all(|b| b.is_ascii_alphabetic() || b == b'-')
+    {
+        return Err(ContractError::InvalidWrapper {
+            reason: "symbol must be 3 to 6 letters or '-'".to_string(),
+        });
+    }
+
+    let config = Config {
+        s1155: deps.api.addr_validate(&msg.s1155)?,
+        token_code_id: msg.token_code_id,
+        name: msg.name,
+        symbol: msg.symbol,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute_batch_receive(deps, env, info, msg),
+        ExecuteMsg::Unwrap { token_id, amount } => {
+            execute_unwrap(deps, env, info, token_id, amount)
+        }
+    }
+}
+
+/// s1155 units sent with `SendFrom`, previous owner of the units receives the cw20 tokens
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    _wrap(deps, env, info, recipient, vec![(msg.token_id, msg.amount)])
+}
+
+/// s1155 units sent with `BatchSendFrom`
+pub fn execute_batch_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: BatchReceiveMsg,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&msg.from.unwrap_or(msg.operator))?;
+    _wrap(deps, env, info, recipient, msg.batch)
+}
+
+pub fn execute_unwrap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::EmptyWrap {});
+    }
+    let cw20 =
+        TOKENS
+            .may_load(deps.storage, &token_id)?
+            .ok_or_else(|| ContractError::NotWrapped {
+                token_id: token_id.clone(),
+            })?;
+    let nft = Nft::S1155 {
+        contract: CONFIG.load(deps.storage)?.s1155,
+        batch: vec![(token_id.clone(), amount)],
+    };
+
+    // Holder pays the fee to get the units back
+    let fee = nft.transfer_fee()?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment.u128() != fee {
+        return Err(ContractError::IncorrectPayment {
+            expected: fee,
+            received: payment.u128(),
+        });
+    }
+
+    // cw20 tokens are burnt from the allowance given to the wrapper
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: cw20.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
+            owner: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "unwrap")
+        .add_attribute("token_id", token_id)
+        .add_attribute("owner", info.sender.as_str())
+        .add_attribute("amount", amount)
+        .add_submessage(SubMsg::new(burn_msg))
+        .add_submessage(nft.transfer_msg(&env.contract.address, info.sender.as_str(), fee)?))
+}
+
+/********************************* REPLY **************************************/
+
+/// Mint the pending wrap once the cw20 token of the token id is instantiated
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let pending = PENDING
+        .may_load(deps.storage, msg.id)?
+        .ok_or(ContractError::UnknownReplyId { id: msg.id })?;
+    PENDING.remove(deps.storage, msg.id);
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|_| ContractError::InstantiateTokenError {})?;
+
+    // Validate contract address
+    let cw20 = deps.api.addr_validate(&res.contract_address)?;
+    TOKENS.save(deps.storage, &pending.token_id, &cw20)?;
+
+    Ok(Response::new()
+        .add_attribute("token_id", pending.token_id)
+        .add_attribute("cw20", cw20.as_str())
+        .add_submessage(_mint_msg(&cw20, &pending.recipient, pending.amount)?))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Token { token_id } => to_binary(&query_token(deps, token_id)?),
+        QueryMsg::Tokens { start_after, limit } => {
+            to_binary(&query_tokens(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        s1155: config.s1155.to_string(),
+        token_code_id: config.token_code_id,
+        name: config.name,
+        symbol: config.symbol,
+    })
+}
+
+pub fn query_token(deps: Deps, token_id: String) -> StdResult<TokenResponse> {
+    let cw20 = TOKENS.may_load(deps.storage, &token_id)?;
+    Ok(TokenResponse {
+        token_id,
+        cw20: cw20.map(|cw20| cw20.to_string()),
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = TOKENS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(token_id, cw20)| TokenResponse {
+                token_id,
+                cw20: Some(cw20.to_string()),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+/********************************* HELPERS ************************************/
+
+/// Mint a cw20 token for every unit, instantiating the cw20 contract of new token ids
+fn _wrap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    batch: Vec<(String, Uint128)>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.s1155 {
+        return Err(ContractError::CollectionNotAllowed {
+            collection: info.sender.to_string(),
+        });
+    }
+
+    // A token id is instantiated once even if repeated in the batch
+    let mut wraps: Vec<(String, Uint128)> = vec![];
+    for (token_id, amount) in batch {
+        match wraps.iter_mut().find(|(id, _)| *id == token_id) {
+            Some((_, total)) => *total = total.checked_add(amount)?,
+            None => wraps.push((token_id, amount)),
+        }
+    }
+    if wraps.iter().all(|(_, amount)| amount.is_zero()) {
+        return Err(ContractError::EmptyWrap {});
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "wrap")
+        .add_attribute("recipient", recipient.as_str());
+    for (token_id, amount) in wraps {
+        if amount.is_zero() {
+            continue;
+        }
+        res = res.add_attribute("token_id", token_id.as_str());
+        if let Some(cw20) = TOKENS.may_load(deps.storage, &token_id)? {
+            res = res.add_submessage(_mint_msg(&cw20, &recipient, amount)?);
+            continue;
+        }
+
+        // Minted on reply, once the cw20 address is known
+        let id = PENDING_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+        let instantiate_token_msg = WasmMsg::Instantiate {
+            code_id: config.token_code_id,
+            funds: vec![],
+            admin: Some(env.contract.address.to_string()),
+            label: format!("{}-{}", config.symbol, token_id),
+            msg: to_binary(&TokenInstantiateMsg {
+                name: _token_name(&config.name, &token_id),
+                symbol: _token_symbol(&config.symbol, id),
+                decimals: 0,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None,
+                }),
+            })?,
+        };
+        PENDING.save(
+            deps.storage,
+            id,
+            &PendingWrap {
+                token_id,
+                recipient: recipient.clone(),
+                amount,
+            },
+        )?;
+        res = res.add_submessage(SubMsg::reply_on_success(instantiate_token_msg, id));
+    }
+    Ok(res)
+}
+
+/// `{name} #{token_id}`, with the name shortened to fit the cw20-base limit
+fn _token_name(name: &str, token_id: &str) -> String {
+    let suffix = format!(" #{}", token_id);
+    let prefix = _truncate(name, MAX_TOKEN_NAME_LENGTH.saturating_sub(suffix.len()));
+    _truncate(
+        &(prefix.trim_end().to_string() + &suffix),
+        MAX_TOKEN_NAME_LENGTH,
+    )
+}
+
+/// `{symbol}-A` for the first cw20 token, then `{symbol}-B`, ..., `{symbol}-Z`, `{symbol}-AA`
+fn _token_symbol(symbol: &str, index: u64) -> String {
+    let mut letters = vec![];
+    let mut n = index;
+    while n > 0 {
+        n -= 1;
+        letters.push(char::from(b'A' + (n % 26) as u8));
+        n /= 26;
+    }
+    format!("{}-{}", symbol, letters.iter().rev().collect::<String>())
+}
+
+fn _truncate(s: &str, max_len: usize) -> String {
+    let mut end = max_len.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s[..end].to_string()
+}
+
+fn _mint_msg(cw20: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<SubMsg> {
+    Ok(SubMsg::new(WasmMsg::Execute {
+        contract_addr: cw20.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }))
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{coins, OwnedDeps, SubMsgResponse, SubMsgResult};
+    use s1::ROYALTY_FEE;
+    use s1155::msg::ExecuteMsg as S1155ExecuteMsg;
+
+    const S1155: &str = "s1155";
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                s1155: S1155.to_string(),
+                token_code_id: 1,
+                name: "Wrapped Edition".to_string(),
+                symbol: "WRAP".to_string(),
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    fn wrap(deps: DepsMut, sender: &str, token_id: &str, amount: u128) -> Response {
+        let msg = ExecuteMsg::Receive(ReceiveMsg {
+            operator: "alice".to_string(),
+            from: Some("alice".to_string()),
+            token_id: token_id.to_string(),
+            amount: Uint128::new(amount),
+            msg: Binary::default(),
+        });
+        execute(deps, mock_env(), mock_info(sender, &[]), msg).unwrap()
+    }
+
+    /// Reply of a successful `WasmMsg::Instantiate`, protobuf encoded
+    fn instantiate_reply(id: u64, contract_address: &str) -> Reply {
+        let mut data = vec![0x0a, contract_address.len() as u8];
+        data.extend_from_slice(contract_address.as_bytes());
+        Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data)),
+            }),
+        }
+    }
+
+    fn mint_msg(cw20: &str, amount: u128) -> SubMsg {
+        _mint_msg(
+            &Addr::unchecked(cw20),
+            &Addr::unchecked("alice"),
+            Uint128::new(amount),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_wrap() {
+        let mut deps = setup_contract();
+
+        // Error: units from another contract
+        let msg = ExecuteMsg::Receive(ReceiveMsg {
+            operator: "alice".to_string(),
+            from: Some("alice".to_string()),
+            token_id: "1".to_string(),
+            amount: Uint128::new(5),
+            msg: Binary::default(),
+        });
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), mock_info("other", &[]), msg),
+            Err(ContractError::CollectionNotAllowed { .. })
+        ));
+
+        // first wrap of a token id instantiates its cw20 token
+        let res = wrap(deps.as_mut(), S1155, "1", 5);
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    code_id: 1,
+                    funds: vec![],
+                    admin: Some(MOCK_CONTRACT_ADDR.to_string()),
+                    label: "WRAP-1".to_string(),
+                    msg: to_binary(&TokenInstantiateMsg {
+                        name: "Wrapped Edition #1".to_string(),
+                        symbol: "WRAP-A".to_string(),
+                        decimals: 0,
+                        initial_balances: vec![],
+                        mint: Some(MinterResponse {
+                            minter: MOCK_CONTRACT_ADDR.to_string(),
+                            cap: None,
+                        }),
+                    })
+                    .unwrap(),
+                },
+                1,
+            )]
+        );
+        assert_eq!(
+            query_token(deps.as_ref(), "1".to_string()).unwrap().cw20,
+            None
+        );
+
+        // Error: no pending wrap for the reply
+        assert!(matches!(
+            reply(deps.as_mut(), mock_env(), instantiate_reply(2, "cw20-1")),
+            Err(ContractError::UnknownReplyId { id: 2 })
+        ));
+
+        // units are minted once the cw20 address is known
+        let res = reply(deps.as_mut(), mock_env(), instantiate_reply(1, "cw20-1")).unwrap();
+        assert_eq!(res.messages, vec![mint_msg("cw20-1", 5)]);
+        assert_eq!(
+            query_token(deps.as_ref(), "1".to_string()).unwrap().cw20,
+            Some("cw20-1".to_string())
+        );
+
+        // later wraps mint directly
+        let res = wrap(deps.as_mut(), S1155, "1", 3);
+        assert_eq!(res.messages, vec![mint_msg("cw20-1", 3)]);
+
+        // a batch mints known token ids and instantiates new ones once
+        let msg = ExecuteMsg::BatchReceive(BatchReceiveMsg {
+            operator: "alice".to_string(),
+            from: Some("alice".to_string()),
+            batch: vec![
+                ("1".to_string(), Uint128::new(1)),
+                ("2".to_string(), Uint128::new(2)),
+                ("2".to_string(), Uint128::new(4)),
+            ],
+            msg: Binary::default(),
+        });
+        let res = execute(deps.as_mut(), mock_env(), mock_info(S1155, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[0], mint_msg("cw20-1", 1));
+        assert_eq!(res.messages[1].id, 2);
+        let res = reply(deps.as_mut(), mock_env(), instantiate_reply(2, "cw20-2")).unwrap();
+        assert_eq!(res.messages, vec![mint_msg("cw20-2", 6)]);
+
+        let res = query_tokens(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            res.tokens,
+            vec![
+                TokenResponse {
+                    token_id: "1".to_string(),
+                    cw20: Some("cw20-1".to_string()),
+                },
+                TokenResponse {
+                    token_id: "2".to_string(),
+                    cw20: Some("cw20-2".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_name_and_symbol() {
+        let mut deps = mock_dependencies();
+
+        // Error: symbol not allowed by cw20-base with the suffix
+        for symbol in ["WR", "WRAPPED", "WRAP1"] {
+            let msg = InstantiateMsg {
+                s1155: S1155.to_string(),
+                token_code_id: 1,
+                name: "Wrapped Edition".to_string(),
+                symbol: symbol.to_string(),
+            };
+            assert!(matches!(
+                instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg),
+                Err(ContractError::InvalidWrapper { .. })
+            ));
+        }
+
+        // name is shortened to keep the token id
+        let name = "Wrapped Edition of a very long collection name";
+        assert_eq!(
+            _token_name(name, "123456"),
+            "Wrapped Edition of a very long collection #123456"
+        );
+        assert_eq!(
+            _token_name("é".repeat(30).as_str(), "1"),
+            "é".repeat(23) + " #1"
+        );
+        let token_id = "9".repeat(60);
+        assert_eq!(
+            _token_name(name, &token_id),
+            format!(" #{}", &token_id[..MAX_TOKEN_NAME_LENGTH - 2])
+        );
+
+        // every cw20 token has its own symbol
+        assert_eq!(_token_symbol("WRAP", 1), "WRAP-A");
+        assert_eq!(_token_symbol("WRAP", 26), "WRAP-Z");
+        assert_eq!(_token_symbol("WRAP", 27), "WRAP-AA");
+        assert_eq!(_token_symbol("WRAP", 702), "WRAP-ZZ");
+        assert_eq!(_token_symbol("WRAP", 703), "WRAP-AAA");
+    }
+
+    #[test]
+    fn test_unwrap() {
+        let mut deps = setup_contract();
+        let fee = coins(ROYALTY_FEE, NATIVE_DENOM);
+        let msg = ExecuteMsg::Unwrap {
+            token_id: "1".to_string(),
+            amount: Uint128::new(2),
+        };
+
+        // Error: token id never wrapped
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &fee),
+                msg.clone()
+            ),
+            Err(ContractError::NotWrapped { .. })
+        ));
+
+        wrap(deps.as_mut(), S1155, "1", 5);
+        reply(deps.as_mut(), mock_env(), instantiate_reply(1, "cw20-1")).unwrap();
+
+        // Error: transfer fee not paid
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &coins(1, NATIVE_DENOM)),
+                msg.clone(),
+            ),
+            Err(ContractError::IncorrectPayment { .. })
+        ));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &fee), msg).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: "cw20-1".to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
+                        owner: "alice".to_string(),
+                        amount: Uint128::new(2),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: S1155.to_string(),
+                    msg: to_binary(&S1155ExecuteMsg::SendFrom {
+                        from: MOCK_CONTRACT_ADDR.to_string(),
+                        to: "alice".to_string(),
+                        token_id: "1".to_string(),
+                        value: Uint128::new(2),
+                        msg: None,
+                    })
+                    .unwrap(),
+                    funds: fee,
+                }),
+            ]
+        );
+    }
+}
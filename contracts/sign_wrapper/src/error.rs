@@ -0,0 +1,40 @@
+use cosmwasm_std::{OverflowError, StdError};
+use cw_utils::PaymentError;
+use s_std::error::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Error instantiating the cw20 token")]
+    InstantiateTokenError {},
+
+    #[error("Invalid wrapper: {reason}")]
+    InvalidWrapper { reason: String },
+
+    #[error("Collection cannot be wrapped: {collection}")]
+    CollectionNotAllowed { collection: String },
+
+    #[error("Nothing to wrap")]
+    EmptyWrap {},
+
+    #[error("Token id was never wrapped: {token_id}")]
+    NotWrapped { token_id: String },
+
+    #[error("Incorrect payment: expected {expected}, got {received}")]
+    IncorrectPayment { expected: u128, received: u128 },
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
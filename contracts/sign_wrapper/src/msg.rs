@@ -0,0 +1,74 @@
+use cosmwasm_std::Uint128;
+use cw20::{Cw20Coin, MinterResponse};
+use s1155::msg::{BatchReceiveMsg, ReceiveMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// s1155 contract of the wrapped tokens
+    pub s1155: String,
+    /// Code id of the cw20-base contract instantiated for every token id
+    pub token_code_id: u64,
+    /// cw20 tokens are named `{name} #{token_id}`, the name is shortened to fit in 50 bytes
+    pub name: String,
+    /// 3 to 6 letters or `-`, cw20 tokens are symbolized `{symbol}-A`, `{symbol}-B`, ...
+    /// in the order of their first wrap
+    pub symbol: String,
+}
+
+/// Instantiate message of cw20-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Wrap s1155 units sent with `SendFrom`, minting as many cw20 tokens to the sender
+    Receive(ReceiveMsg),
+    /// Wrap a batch of s1155 units sent with `BatchSendFrom`
+    BatchReceive(BatchReceiveMsg),
+    /// Burn `amount` cw20 tokens of the sender, allowed to the wrapper, and send back
+    /// the s1155 units. The transfer fee of the s1155 contract is paid in usign.
+    Unwrap { token_id: String, amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// cw20 token of a token id
+    Token {
+        token_id: String,
+    },
+    Tokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub s1155: String,
+    pub token_code_id: u64,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenResponse {
+    pub token_id: String,
+    /// None until the token id is first wrapped
+    pub cw20: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokensResponse {
+    pub tokens: Vec<TokenResponse>,
+}
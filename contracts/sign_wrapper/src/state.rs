@@ -0,0 +1,31 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub s1155: Addr,
+    pub token_code_id: u64,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Units wrapped before the cw20 token of the token id was instantiated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingWrap {
+    pub token_id: String,
+    pub recipient: Addr,
+    pub amount: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// s1155 token id -> cw20 token
+pub const TOKENS: Map<&str, Addr> = Map::new("tokens");
+
+/// Number of cw20 tokens instantiated, used as the reply id of the next one
+pub const PENDING_COUNT: Item<u64> = Item::new("pending_count");
+
+// Reply id -> wrap minted on reply
+pub const PENDING: Map<u64, PendingWrap> = Map::new("pending");
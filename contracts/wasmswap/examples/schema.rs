@@ -5,8 +5,8 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use cw20::BalanceResponse;
 use wasmswap::msg::{
-    ExecuteMsg, InfoResponse, InstantiateMsg, QueryMsg, Token1ForToken2PriceResponse,
-    Token2ForToken1PriceResponse,
+    ExecuteMsg, InfoResponse, InstantiateMsg, QueryMsg, ReceiveMsg, ReverseSimulateSwapResponse,
+    SimulateSwapResponse, Token1ForToken2PriceResponse, Token2ForToken1PriceResponse, TwapResponse,
 };
 use wasmswap::state::Token;
 
@@ -18,10 +18,14 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(ReceiveMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(Token), &out_dir);
     export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(InfoResponse), &out_dir);
     export_schema(&schema_for!(Token1ForToken2PriceResponse), &out_dir);
     export_schema(&schema_for!(Token2ForToken1PriceResponse), &out_dir);
+    export_schema(&schema_for!(TwapResponse), &out_dir);
+    export_schema(&schema_for!(SimulateSwapResponse), &out_dir);
+    export_schema(&schema_for!(ReverseSimulateSwapResponse), &out_dir);
 }
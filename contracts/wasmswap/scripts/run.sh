@@ -36,7 +36,7 @@ SUN=$($(echo $BINARY) query wasm list-contract-by-code 1 --output json | jq -r '
 
 
 # Init USIGN<->MOON contract
-export INIT=$(jq -n --arg USIGN $DENOM --arg MOON $MOON '{"token1_denom": {"native": $USIGN }, "token2_denom": {"cw20":$MOON}, "lp_token_code_id": 1 }')
+export INIT=$(jq -n --arg USIGN $DENOM --arg MOON $MOON '{"token1_denom": {"native": $USIGN }, "token2_denom": {"cw20":$MOON}, "lp_token_code_id": 1, "swap_fee": "0.003" }')
 echo "\n\nInstantiating USIGN<->MOON swap contract..."
 $(echo $BINARY) tx wasm instantiate 2 "$INIT" --label "USIGN MOON SWAP" --admin $USER1  --gas=auto --gas-adjustment=1.15 --from user1 -y
 sleep 2
@@ -54,7 +54,7 @@ sleep 2
 
 
 # Init USIGN<->SUN contract
-export INIT=$(jq -n --arg USIGN $DENOM --arg SUN $SUN '{"token1_denom": {"native": $USIGN }, "token2_denom": {"cw20":$SUN}, "lp_token_code_id": 1 }')
+export INIT=$(jq -n --arg USIGN $DENOM --arg SUN $SUN '{"token1_denom": {"native": $USIGN }, "token2_denom": {"cw20":$SUN}, "lp_token_code_id": 1, "swap_fee": "0.003" }')
 echo "\n\nInstantiating USIGN<->SUN swap contract..."
 $(echo $BINARY) tx wasm instantiate 2 "$INIT" --label "USIGN SUN SWAP" --admin $USER1  --gas=auto --gas-adjustment=1.15 --from user1 -y
 sleep 2
@@ -72,7 +72,7 @@ sleep 2
 
 
 # Init MOON<->SUN contract
-export INIT=$(jq -n --arg MOON $MOON --arg SUN $SUN '{"token1_denom": {"cw20": $MOON }, "token2_denom": {"cw20":$SUN}, "lp_token_code_id": 1 }')
+export INIT=$(jq -n --arg MOON $MOON --arg SUN $SUN '{"token1_denom": {"cw20": $MOON }, "token2_denom": {"cw20":$SUN}, "lp_token_code_id": 1, "swap_fee": "0.003" }')
 echo "\n\nInstantiating SUN<->MOON swap contract..."
 $(echo $BINARY) tx wasm instantiate 2 "$INIT" --label "MOON SUN SWAP" --admin $USER1  --gas=auto --gas-adjustment=1.15 --from user1 -y
 sleep 2
@@ -1,25 +1,60 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Addr, Binary, BlockInfo, Coin, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Addr, Api, Binary, BlockInfo, Coin, Decimal, Decimal256,
+    Deps, DepsMut, Env, MessageInfo, Order, Reply, StdError, StdResult, Storage, Uint128, Uint256,
+    Uint512, WasmMsg,
 };
 use cw0::parse_reply_instantiate_data;
 use cw2::set_contract_version;
 use cw20::Denom::Cw20;
-use cw20::{Cw20ExecuteMsg, Denom, Expiration, MinterResponse};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom, Expiration, MinterResponse};
 use cw20_base::contract::query_balance;
+use cw_storage_plus::Bound;
+use s_std::{create_fund_community_pool_msg, CosmosMsg, Response, SubMsg};
+use std::convert::TryInto;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InfoResponse, InstantiateMsg, QueryMsg, Token1ForToken2PriceResponse,
-    Token2ForToken1PriceResponse, TokenSelect,
+    ExecuteMsg, InfoResponse, InstantiateMsg, LpTokenInfo, PoolType, ProtocolFee, QueryMsg,
+    ReceiveMsg, ReverseSimulateSwapResponse, SimulateSwapResponse, Token1ForToken2PriceResponse,
+    Token2ForToken1PriceResponse, TokenSelect, TwapResponse,
+};
+use crate::stableswap;
+use crate::state::{
+    Amplification, Config, CumulativePrices, Token, AMPLIFICATION, CONFIG, CUMULATIVE_PRICES,
+    LP_TOKEN, OBSERVATIONS, TOKEN1, TOKEN2,
 };
-use crate::state::{Token, LP_TOKEN, TOKEN1, TOKEN2};
 
 // Version info for migration info
 pub const CONTRACT_NAME: &str = "crates.io:wasmswap";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_LP_TOKEN_REPLY_ID: u64 = 0;
+
+// Liquidity minted to the pool on the first deposit and locked forever, so that the supply never
+// goes back to zero and a unit of liquidity is too expensive to inflate
+const MINIMUM_LIQUIDITY: u128 = 1_000;
+
+const DEFAULT_LP_TOKEN_DECIMALS: u8 = 6;
+// Letters of each token in the default lp token symbol, cw20 symbols have 12 at most
+const LP_TOKEN_SYMBOL_PART_LENGTH: usize = 5;
+// Characters of each token in the default lp token name, cw20 names have 50 at most
+const LP_TOKEN_NAME_PART_LENGTH: usize = 20;
+
+const MAX_SWAP_FEE_PERCENT: u64 = 10;
+// Swap fees are applied with a precision of 0.0001%
+const FEE_SCALE_FACTOR: u128 = 1_000_000;
+
+// Cumulative prices are scaled by 10^18
+const PRICE_SCALE_FACTOR: u128 = 1_000_000_000_000_000_000;
+// Longest twap window, one day
+const MAX_TWAP_WINDOW: u64 = 86_400;
+
+// Stableswap amplification bounds, a ramp changes it by a factor of up to 10 over a day at least
+const MAX_AMP: u64 = 1_000_000;
+const MAX_AMP_CHANGE: u64 = 10;
+const MIN_RAMP_TIME: u64 = 86_400;
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -45,22 +80,51 @@ pub fn instantiate(
 
     TOKEN2.save(deps.storage, &token2)?;
 
+    let config = validate_config(deps.api, msg.owner, msg.swap_fee, msg.protocol_fee)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    if let Some(PoolType::Stableswap { amp }) = msg.pool_type {
+        validate_amp(amp)?;
+        let now = env.block.time.seconds();
+        AMPLIFICATION.save(
+            deps.storage,
+            &Amplification {
+                initial_amp: amp,
+                initial_time: now,
+                future_amp: amp,
+                future_time: now,
+            },
+        )?;
+    }
+
+    let prices = CumulativePrices {
+        price1_cumulative: Uint256::zero(),
+        price2_cumulative: Uint256::zero(),
+        last_updated: env.block.time.seconds(),
+    };
+    CUMULATIVE_PRICES.save(deps.storage, &prices)?;
+    OBSERVATIONS.save(deps.storage, prices.last_updated, &prices)?;
+
+    let lp_token_msg = get_lp_token_instantiate_msg(
+        deps.as_ref(),
+        &env,
+        &token1.denom,
+        &token2.denom,
+        config.owner.as_ref(),
+        msg.lp_token,
+    )?;
+    // The owner of the pool can migrate its lp token, the pool itself is the admin without owner
     let instantiate_lp_token_msg = WasmMsg::Instantiate {
         code_id: msg.lp_token_code_id,
         funds: vec![],
-        admin: None,
+        admin: Some(
+            config
+                .owner
+                .unwrap_or_else(|| env.contract.address.clone())
+                .to_string(),
+        ),
         label: "lp_token".to_string(),
-        msg: to_binary(&cw20_base::msg::InstantiateMsg {
-            name: "WasmSwap_Liquidity_Token".into(),
-            symbol: "wslpt".into(),
-            decimals: 6,
-            initial_balances: vec![],
-            mint: Some(MinterResponse {
-                minter: env.contract.address.into(),
-                cap: None,
-            }),
-            marketing: None,
-        })?,
+        msg: to_binary(&lp_token_msg)?,
     };
 
     let reply_msg =
@@ -91,6 +155,7 @@ pub fn execute(
             token1_amount,
             max_token2,
             expiration,
+            None,
         ),
         ExecuteMsg::RemoveLiquidity {
             amount,
@@ -113,6 +178,7 @@ pub fn execute(
             info.sender.to_string(),
             min_output,
             expiration,
+            false,
         ),
         ExecuteMsg::PassThroughSwap {
             output_amm_address,
@@ -145,10 +211,411 @@ pub fn execute(
             recipient,
             min_token,
             expiration,
+            false,
+        ),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            swap_fee,
+            protocol_fee,
+        } => execute_update_config(deps, info, owner, swap_fee, protocol_fee),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info, env, msg),
+        ExecuteMsg::SwapExactOutput {
+            input_token,
+            output_amount,
+            max_input,
+            recipient,
+            expiration,
+        } => execute_swap_exact_output(
+            deps,
+            info,
+            env,
+            input_token,
+            output_amount,
+            max_input,
+            recipient,
+            expiration,
+        ),
+        ExecuteMsg::RampAmp {
+            future_amp,
+            future_time,
+        } => execute_ramp_amp(deps, info, env, future_amp, future_time),
+        ExecuteMsg::StopRampAmp {} => execute_stop_ramp_amp(deps, info, env),
+        ExecuteMsg::ZapIn {
+            input_token,
+            amount,
+            min_liquidity,
+            expiration,
+        } => execute_zap_in(
+            deps,
+            &info,
+            env,
+            input_token,
+            amount,
+            min_liquidity,
+            expiration,
+            false,
+        ),
+        ExecuteMsg::ZapOut {
+            amount,
+            output_token,
+            min_output,
+            expiration,
+        } => execute_zap_out(
+            deps,
+            info,
+            env,
+            amount,
+            output_token,
+            min_output,
+            expiration,
+        ),
+    }
+}
+
+/// Tokens of the pool sent with `Send`, no allowance needed
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let received = if TOKEN1.load(deps.storage)?.denom == Cw20(info.sender.clone()) {
+        TokenSelect::Token1
+    } else if TOKEN2.load(deps.storage)?.denom == Cw20(info.sender.clone()) {
+        TokenSelect::Token2
+    } else {
+        return Err(ContractError::InvalidCw20Token {
+            token: info.sender.to_string(),
+        });
+    };
+    // The hook acts for the sender of the tokens
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&wrapper.sender)?,
+        funds: info.funds,
+    };
+
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::Swap {
+            min_output,
+            recipient,
+            expiration,
+        } => {
+            let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+            execute_swap(
+                deps,
+                &info,
+                wrapper.amount,
+                env,
+                received,
+                recipient,
+                min_output,
+                expiration,
+                true,
+            )
+        }
+        ReceiveMsg::AddLiquidity {
+            token1_amount,
+            min_liquidity,
+            max_token2,
+            expiration,
+        } => {
+            let amount = match received {
+                TokenSelect::Token1 => token1_amount,
+                TokenSelect::Token2 => max_token2,
+            };
+            if wrapper.amount != amount {
+                return Err(ContractError::InsufficientFunds {});
+            }
+            execute_add_liquidity(
+                deps,
+                &info,
+                env,
+                min_liquidity,
+                token1_amount,
+                max_token2,
+                expiration,
+                Some(received),
+            )
+        }
+        ReceiveMsg::ZapIn {
+            min_liquidity,
+            expiration,
+        } => execute_zap_in(
+            deps,
+            &info,
+            env,
+            received,
+            wrapper.amount,
+            min_liquidity,
+            expiration,
+            true,
         ),
     }
 }
 
+/// Symbol of cw20 tokens and denom of native tokens
+fn get_denom_label(deps: Deps, denom: &Denom) -> StdResult<String> {
+    match denom {
+        Denom::Native(denom) => Ok(denom.clone()),
+        Denom::Cw20(addr) => {
+            let info: cw20::TokenInfoResponse = deps
+                .querier
+                .query_wasm_smart(addr, &cw20::Cw20QueryMsg::TokenInfo {})?;
+            Ok(info.symbol)
+        }
+    }
+}
+
+/// Instantiate message of the lp token, the metadata not provided is derived from the pair
+fn get_lp_token_instantiate_msg(
+    deps: Deps,
+    env: &Env,
+    token1_denom: &Denom,
+    token2_denom: &Denom,
+    owner: Option<&Addr>,
+    lp_token: Option<LpTokenInfo>,
+) -> StdResult<cw20_base::msg::InstantiateMsg> {
+    let lp_token = lp_token.unwrap_or(LpTokenInfo {
+        name: None,
+        symbol: None,
+        decimals: None,
+        marketing: None,
+    });
+    let label1 = get_denom_label(deps, token1_denom)?;
+    let label2 = get_denom_label(deps, token2_denom)?;
+
+    let name = lp_token.name.unwrap_or_else(|| {
+        let part =
+            |label: &str| -> String { label.chars().take(LP_TOKEN_NAME_PART_LENGTH).collect() };
+        format!("{}-{} LP", part(&label1), part(&label2))
+    });
+    // Symbols only have letters and dashes, ibc denoms keep the letters of their hash
+    let symbol = lp_token.symbol.unwrap_or_else(|| {
+        let part = |label: &str| -> String {
+            label
+                .chars()
+                .filter(char::is_ascii_alphabetic)
+                .take(LP_TOKEN_SYMBOL_PART_LENGTH)
+                .collect::<String>()
+                .to_uppercase()
+        };
+        format!("{}-{}", part(&label1), part(&label2))
+    });
+    let marketing =
+        lp_token
+            .marketing
+            .unwrap_or_else(|| cw20_base::msg::InstantiateMarketingInfo {
+                project: None,
+                description: Some(format!(
+                    "WasmSwap liquidity token of the {} and {} pool",
+                    label1, label2
+                )),
+                marketing: owner.map(|addr| addr.to_string()),
+                logo: None,
+            });
+
+    let msg = cw20_base::msg::InstantiateMsg {
+        name,
+        symbol,
+        decimals: lp_token.decimals.unwrap_or(DEFAULT_LP_TOKEN_DECIMALS),
+        initial_balances: vec![],
+        mint: Some(MinterResponse {
+            minter: env.contract.address.to_string(),
+            cap: None,
+        }),
+        marketing: Some(marketing),
+    };
+    // Fail before instantiating the lp token
+    msg.validate()?;
+    Ok(msg)
+}
+
+fn validate_config(
+    api: &dyn Api,
+    owner: Option<String>,
+    swap_fee: Decimal,
+    protocol_fee: Option<ProtocolFee>,
+) -> Result<Config, ContractError> {
+    let max_swap_fee = Decimal::percent(MAX_SWAP_FEE_PERCENT);
+    if swap_fee > max_swap_fee {
+        return Err(ContractError::SwapFeeTooHigh {
+            max: max_swap_fee,
+            provided: swap_fee,
+        });
+    }
+
+    let (protocol_fee_share, protocol_fee_recipient) = match protocol_fee {
+        Some(ProtocolFee { share, recipient }) => {
+            if share > Decimal::one() {
+                return Err(ContractError::InvalidProtocolFeeShare { share });
+            }
+            let recipient = recipient.map(|addr| api.addr_validate(&addr)).transpose()?;
+            (share, recipient)
+        }
+        None => (Decimal::zero(), None),
+    };
+
+    Ok(Config {
+        owner: owner.map(|addr| api.addr_validate(&addr)).transpose()?,
+        swap_fee,
+        protocol_fee_share,
+        protocol_fee_recipient,
+    })
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    swap_fee: Decimal,
+    protocol_fee: Option<ProtocolFee>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = validate_config(deps.api, owner, swap_fee, protocol_fee)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_config"),
+        attr(
+            "owner",
+            config
+                .owner
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+        ),
+        attr("swap_fee", config.swap_fee.to_string()),
+        attr("protocol_fee_share", config.protocol_fee_share.to_string()),
+    ]))
+}
+
+fn validate_amp(amp: u64) -> Result<(), ContractError> {
+    if amp == 0 || amp > MAX_AMP {
+        return Err(ContractError::InvalidAmp { amp, max: MAX_AMP });
+    }
+    Ok(())
+}
+
+/// Amplification at `now`, interpolated along the ramp
+fn current_amp(amplification: &Amplification, now: u64) -> u64 {
+    let Amplification {
+        initial_amp,
+        initial_time,
+        future_amp,
+        future_time,
+    } = *amplification;
+    if now >= future_time {
+        return future_amp;
+    }
+    let (elapsed, duration) = (now - initial_time, future_time - initial_time);
+    if future_amp > initial_amp {
+        initial_amp + (future_amp - initial_amp) * elapsed / duration
+    } else {
+        initial_amp - (initial_amp - future_amp) * elapsed / duration
+    }
+}
+
+/// Current amplification of a stableswap pool, None for a constant product pool
+fn load_amp(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Option<u64>> {
+    Ok(AMPLIFICATION
+        .may_load(storage)?
+        .map(|amplification| current_amp(&amplification, block.time.seconds())))
+}
+
+/// Load the amplification of a stableswap pool for its owner
+fn load_amplification_for_owner(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<Amplification, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    AMPLIFICATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotStableswap {})
+}
+
+pub fn execute_ramp_amp(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    future_amp: u64,
+    future_time: u64,
+) -> Result<Response, ContractError> {
+    let amplification = load_amplification_for_owner(deps.as_ref(), &info)?;
+    validate_amp(future_amp)?;
+
+    let now = env.block.time.seconds();
+    if now < amplification.initial_time + MIN_RAMP_TIME {
+        return Err(ContractError::InvalidAmpRamp {
+            reason: "the last ramp started less than a day ago".to_string(),
+        });
+    }
+    if future_time < now + MIN_RAMP_TIME {
+        return Err(ContractError::InvalidAmpRamp {
+            reason: "the ramp must last a day at least".to_string(),
+        });
+    }
+    let initial_amp = current_amp(&amplification, now);
+    if future_amp > initial_amp * MAX_AMP_CHANGE || future_amp * MAX_AMP_CHANGE < initial_amp {
+        return Err(ContractError::InvalidAmpRamp {
+            reason: format!(
+                "the amplification can change by a factor of {} at most",
+                MAX_AMP_CHANGE
+            ),
+        });
+    }
+
+    // Swaps before the ramp are priced with the previous amplification
+    update_cumulative_prices(deps.storage, &env.block)?;
+    AMPLIFICATION.save(
+        deps.storage,
+        &Amplification {
+            initial_amp,
+            initial_time: now,
+            future_amp,
+            future_time,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "ramp_amp"),
+        attr("initial_amp", initial_amp.to_string()),
+        attr("future_amp", future_amp.to_string()),
+        attr("future_time", future_time.to_string()),
+    ]))
+}
+
+pub fn execute_stop_ramp_amp(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let amplification = load_amplification_for_owner(deps.as_ref(), &info)?;
+
+    update_cumulative_prices(deps.storage, &env.block)?;
+    let now = env.block.time.seconds();
+    let amp = current_amp(&amplification, now);
+    AMPLIFICATION.save(
+        deps.storage,
+        &Amplification {
+            initial_amp: amp,
+            initial_time: now,
+            future_amp: amp,
+            future_time: now,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "stop_ramp_amp"),
+        attr("amp", amp.to_string()),
+    ]))
+}
+
 fn check_expiration(
     expiration: &Option<Expiration>,
     block: &BlockInfo,
@@ -164,6 +631,74 @@ fn check_expiration(
     }
 }
 
+/// Accumulate the prices of the reserves before they change, and save a snapshot at most once
+/// per block
+fn update_cumulative_prices(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    let now = block.time.seconds();
+    let prices = CUMULATIVE_PRICES.load(storage)?;
+    if prices.last_updated == now {
+        return Ok(());
+    }
+    let token1 = TOKEN1.load(storage)?;
+    let token2 = TOKEN2.load(storage)?;
+    let amp = load_amp(storage, block)?;
+    let prices = accumulate_prices(prices, now, amp, token1.reserve, token2.reserve)?;
+    CUMULATIVE_PRICES.save(storage, &prices)?;
+    OBSERVATIONS.save(storage, now, &prices)?;
+
+    // Snapshots older than the longest window are removed, except the last one starting it
+    let expired = OBSERVATIONS
+        .range(
+            storage,
+            None,
+            Some(Bound::exclusive_int(now.saturating_sub(MAX_TWAP_WINDOW))),
+            Order::Descending,
+        )
+        .skip(1)
+        .map(|item| item.map(|(_, prices)| prices.last_updated))
+        .collect::<StdResult<Vec<_>>>()?;
+    for timestamp in expired {
+        OBSERVATIONS.remove(storage, timestamp);
+    }
+    Ok(())
+}
+
+/// Add the prices of the reserves for the seconds since the last update, Uniswap v2 style
+fn accumulate_prices(
+    mut prices: CumulativePrices,
+    now: u64,
+    amp: Option<u64>,
+    token1_reserve: Uint128,
+    token2_reserve: Uint128,
+) -> StdResult<CumulativePrices> {
+    let elapsed = Uint256::from(now.saturating_sub(prices.last_updated));
+    if !token1_reserve.is_zero() && !token2_reserve.is_zero() {
+        let (price1, price2) = match amp {
+            // Spot prices of the invariant, Decimal has the precision of the cumulative prices
+            Some(amp) => (
+                Uint256::from(
+                    stableswap::spot_price(amp, token1_reserve, token2_reserve)?.atomics(),
+                ),
+                Uint256::from(
+                    stableswap::spot_price(amp, token2_reserve, token1_reserve)?.atomics(),
+                ),
+            ),
+            None => (
+                Uint256::from(token2_reserve).multiply_ratio(PRICE_SCALE_FACTOR, token1_reserve),
+                Uint256::from(token1_reserve).multiply_ratio(PRICE_SCALE_FACTOR, token2_reserve),
+            ),
+        };
+        prices.price1_cumulative = prices
+            .price1_cumulative
+            .checked_add(price1.checked_mul(elapsed)?)?;
+        prices.price2_cumulative = prices
+            .price2_cumulative
+            .checked_add(price2.checked_mul(elapsed)?)?;
+    }
+    prices.last_updated = now;
+    Ok(prices)
+}
+
 fn get_lp_token_amount_to_mint(
     token1_amount: Uint128,
     liquidity_supply: Uint128,
@@ -200,6 +735,8 @@ fn get_token2_amount_required(
     }
 }
 
+/// `received` is the token already sent with the cw20 hook
+#[allow(clippy::too_many_arguments)]
 pub fn execute_add_liquidity(
     deps: DepsMut,
     info: &MessageInfo,
@@ -208,8 +745,10 @@ pub fn execute_add_liquidity(
     token1_amount: Uint128,
     max_token2: Uint128,
     expiration: Option<Expiration>,
+    received: Option<TokenSelect>,
 ) -> Result<Response, ContractError> {
     check_expiration(&expiration, &env.block)?;
+    update_cumulative_prices(deps.storage, &env.block)?;
 
     let token1 = TOKEN1.load(deps.storage)?;
     let token2 = TOKEN2.load(deps.storage)?;
@@ -220,9 +759,26 @@ pub fn execute_add_liquidity(
     validate_input_amount(&info.funds, max_token2, &token2.denom)?;
 
     let lp_token_supply = get_lp_token_supply(deps.as_ref(), &lp_token_addr)?;
-    let liquidity_amount =
+    let mut liquidity_amount =
         get_lp_token_amount_to_mint(token1_amount, lp_token_supply, token1.reserve)?;
 
+    // The minimum liquidity of the first deposit is locked in the pool
+    let mut locked_liquidity = Uint128::zero();
+    if lp_token_supply.is_zero() {
+        locked_liquidity = Uint128::new(MINIMUM_LIQUIDITY);
+        if liquidity_amount <= locked_liquidity {
+            return Err(ContractError::InitialLiquidityTooLow {
+                minimum: locked_liquidity + Uint128::new(1),
+                provided: liquidity_amount,
+            });
+        }
+        liquidity_amount -= locked_liquidity;
+    }
+    // A deposit worth less than a unit of liquidity would be a donation to the pool
+    if liquidity_amount.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+
     let token2_amount = get_token2_amount_required(
         max_token2,
         token1_amount,
@@ -248,20 +804,31 @@ pub fn execute_add_liquidity(
     // Generate cw20 transfer messages if necessary
     let mut transfer_msgs: Vec<CosmosMsg> = vec![];
     if let Cw20(addr) = token1.denom {
-        transfer_msgs.push(get_cw20_transfer_from_msg(
-            &info.sender,
-            &env.contract.address,
-            &addr,
-            token1_amount,
-        )?)
+        if received != Some(TokenSelect::Token1) {
+            transfer_msgs.push(get_cw20_transfer_from_msg(
+                &info.sender,
+                &env.contract.address,
+                &addr,
+                token1_amount,
+            )?)
+        }
     }
     if let Cw20(addr) = token2.denom.clone() {
-        transfer_msgs.push(get_cw20_transfer_from_msg(
-            &info.sender,
-            &env.contract.address,
-            &addr,
-            token2_amount,
-        )?)
+        if received != Some(TokenSelect::Token2) {
+            transfer_msgs.push(get_cw20_transfer_from_msg(
+                &info.sender,
+                &env.contract.address,
+                &addr,
+                token2_amount,
+            )?)
+        } else if token2_amount < max_token2 {
+            // Refund token 2 sent with the hook and not spent
+            transfer_msgs.push(get_cw20_transfer_to_msg(
+                &info.sender,
+                &addr,
+                max_token2 - token2_amount,
+            )?)
+        }
     }
 
     // Refund token 2 if is a native token and not all is spent
@@ -284,15 +851,26 @@ pub fn execute_add_liquidity(
         Ok(token2)
     })?;
 
-    let mint_msg = mint_lp_tokens(&info.sender, liquidity_amount, &lp_token_addr)?;
+    transfer_msgs.push(mint_lp_tokens(
+        &info.sender,
+        liquidity_amount,
+        &lp_token_addr,
+    )?);
+    if !locked_liquidity.is_zero() {
+        transfer_msgs.push(mint_lp_tokens(
+            &env.contract.address,
+            locked_liquidity,
+            &lp_token_addr,
+        )?);
+    }
 
     Ok(Response::new()
         .add_messages(transfer_msgs)
-        .add_message(mint_msg)
         .add_attributes(vec![
             attr("token1_amount", token1_amount),
             attr("token2_amount", token2_amount),
             attr("liquidity_received", liquidity_amount),
+            attr("liquidity_locked", locked_liquidity),
         ]))
 }
 
@@ -404,6 +982,7 @@ pub fn execute_remove_liquidity(
     expiration: Option<Expiration>,
 ) -> Result<Response, ContractError> {
     check_expiration(&expiration, &env.block)?;
+    update_cumulative_prices(deps.storage, &env.block)?;
 
     let lp_token_addr = LP_TOKEN.load(deps.storage)?;
     let balance = get_token_balance(deps.as_ref(), &lp_token_addr, &info.sender)?;
@@ -482,6 +1061,318 @@ pub fn execute_remove_liquidity(
         ]))
 }
 
+/// Part of `amount` to swap so that the rest and the output match the reserves after the swap,
+/// and the output of the swap
+fn get_zap_swap_amount(
+    amp: Option<u64>,
+    amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+    protocol_fee_rate: Decimal,
+) -> StdResult<(Uint128, Uint128)> {
+    let swap_output =
+        |swap_amount| get_swap_output(amp, swap_amount, input_reserve, output_reserve, swap_fee);
+    // The rest of the input matches at least the output in the pool after the swap
+    let leaves_matching_input = |swap_amount: Uint128, bought: Uint128| -> StdResult<bool> {
+        let input_reserve_after = input_reserve + swap_amount - swap_amount * protocol_fee_rate;
+        let output_reserve_after = output_reserve.checked_sub(bought)?;
+        Ok((amount - swap_amount).full_mul(output_reserve_after)
+            >= bought.full_mul(input_reserve_after))
+    };
+
+    match amp {
+        None => {
+            let mut swap_amount = get_constant_product_zap_amount(
+                amount,
+                input_reserve,
+                swap_fee,
+                protocol_fee_rate,
+            )?;
+            let mut bought = swap_output(swap_amount)?;
+            // The protocol fee is rounded down, leaving slightly more input in the pool
+            if !swap_amount.is_zero() && !leaves_matching_input(swap_amount, bought)? {
+                swap_amount -= Uint128::new(1);
+                bought = swap_output(swap_amount)?;
+            }
+            // The root is rounded down, the smallest swap buying one more unit may still leave
+            // the input matching
+            let next_bought = bought + Uint128::new(1);
+            if next_bought < output_reserve {
+                let next_swap_amount =
+                    get_swap_input(amp, next_bought, input_reserve, output_reserve, swap_fee)?;
+                if next_swap_amount <= amount {
+                    let next_bought = swap_output(next_swap_amount)?;
+                    if leaves_matching_input(next_swap_amount, next_bought)? {
+                        return Ok((next_swap_amount, next_bought));
+                    }
+                }
+            }
+            Ok((swap_amount, bought))
+        }
+        Some(_) => {
+            // Largest swap leaving the input matching. The range is halved every round, so the
+            // bisection ends after at most 128 rounds, one per bit of `amount`
+            let (mut low, mut high) = (Uint128::zero(), amount);
+            while low < high {
+                let swap_amount = low + (high - low + Uint128::new(1)) / Uint128::new(2);
+                if leaves_matching_input(swap_amount, swap_output(swap_amount)?)? {
+                    low = swap_amount;
+                } else {
+                    high = swap_amount - Uint128::new(1);
+                }
+            }
+            Ok((low, swap_output(low)?))
+        }
+    }
+}
+
+/// Zap swap of a constant product pool, rounded down. With `r` the input reserve, `g` the part
+/// of the input left by the swap fee and `q` the part left in the pool by the protocol fee, the
+/// rest matches the output when `g * q * s^2 + (1 + g) * r * s - amount * r = 0`.
+fn get_constant_product_zap_amount(
+    amount: Uint128,
+    input_reserve: Uint128,
+    swap_fee: Decimal,
+    protocol_fee_rate: Decimal,
+) -> StdResult<Uint128> {
+    // g and q are scaled like in `get_input_price` and `Decimal`
+    let fee_scale = Uint512::from(FEE_SCALE_FACTOR);
+    let decimal_scale = Uint512::from(Decimal::one().atomics());
+    let g = fee_scale - Uint512::from(Uint128::new(FEE_SCALE_FACTOR) * swap_fee);
+    let q = Uint512::from((Decimal::one() - protocol_fee_rate).atomics());
+    let r = Uint512::from(input_reserve);
+
+    let a = g * q;
+    let b = (g + fee_scale) * decimal_scale * r;
+    let c = Uint512::from(amount) * r * fee_scale * decimal_scale;
+    let swap_amount = if a.is_zero() {
+        c.checked_div(b).map_err(StdError::divide_by_zero)?
+    } else {
+        (isqrt(b * b + Uint512::from(4u128) * a * c) - b) / (Uint512::from(2u128) * a)
+    };
+    Ok(swap_amount.try_into()?)
+}
+
+/// Square root rounded down, with Newton's method from a power of two above the root
+fn isqrt(n: Uint512) -> Uint512 {
+    let bytes = n.to_be_bytes();
+    let bits = match bytes.iter().position(|byte| *byte != 0) {
+        Some(i) => (64 - i as u32) * 8 - bytes[i].leading_zeros(),
+        None => return n,
+    };
+    let mut x = Uint512::from(2u128).pow((bits + 1) / 2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// `received` is true when the input was sent with the cw20 hook
+#[allow(clippy::too_many_arguments)]
+pub fn execute_zap_in(
+    deps: DepsMut,
+    info: &MessageInfo,
+    env: Env,
+    input_token_enum: TokenSelect,
+    amount: Uint128,
+    min_liquidity: Uint128,
+    expiration: Option<Expiration>,
+    received: bool,
+) -> Result<Response, ContractError> {
+    check_expiration(&expiration, &env.block)?;
+    update_cumulative_prices(deps.storage, &env.block)?;
+
+    let input_token_item = match input_token_enum {
+        TokenSelect::Token1 => TOKEN1,
+        TokenSelect::Token2 => TOKEN2,
+    };
+    let input_token = input_token_item.load(deps.storage)?;
+    let output_token = match input_token_enum {
+        TokenSelect::Token1 => TOKEN2,
+        TokenSelect::Token2 => TOKEN1,
+    }
+    .load(deps.storage)?;
+    validate_input_amount(&info.funds, amount, &input_token.denom)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // cw20 protocol fees without a recipient stay in the pool
+    let protocol_fee_rate = match (&config.protocol_fee_recipient, &input_token.denom) {
+        (None, Denom::Cw20(_)) => Decimal::zero(),
+        _ => config.swap_fee * config.protocol_fee_share,
+    };
+    let (swap_amount, token_bought) = get_zap_swap_amount(
+        load_amp(deps.storage, &env.block)?,
+        amount,
+        input_token.reserve,
+        output_token.reserve,
+        config.swap_fee,
+        protocol_fee_rate,
+    )?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if let Denom::Cw20(addr) = &input_token.denom {
+        if !received {
+            msgs.push(get_cw20_transfer_from_msg(
+                &info.sender,
+                &env.contract.address,
+                addr,
+                amount,
+            )?)
+        }
+    }
+    // Protocol fee is not added to the reserve
+    let mut protocol_fee_amount = Uint128::zero();
+    if let Some((fee_amount, msg)) = get_protocol_fee_msg(&config, swap_amount, &input_token.denom)?
+    {
+        protocol_fee_amount = fee_amount;
+        msgs.push(msg);
+    }
+
+    // The output is deposited back, only the input reserve changes
+    let input_reserve = input_token.reserve + swap_amount - protocol_fee_amount;
+    let output_reserve = output_token.reserve - token_bought;
+    let lp_token_addr = LP_TOKEN.load(deps.storage)?;
+    let lp_token_supply = get_lp_token_supply(deps.as_ref(), &lp_token_addr)?;
+    let liquidity_amount = std::cmp::min(
+        (amount - swap_amount).multiply_ratio(lp_token_supply, input_reserve),
+        token_bought.multiply_ratio(lp_token_supply, output_reserve),
+    );
+    if liquidity_amount.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+    if liquidity_amount < min_liquidity {
+        return Err(ContractError::MinLiquidityError {
+            min_liquidity,
+            liquidity_available: liquidity_amount,
+        });
+    }
+
+    input_token_item.update(deps.storage, |mut token| -> Result<_, ContractError> {
+        token.reserve = token
+            .reserve
+            .checked_add(amount - protocol_fee_amount)
+            .map_err(StdError::overflow)?;
+        Ok(token)
+    })?;
+    msgs.push(mint_lp_tokens(
+        &info.sender,
+        liquidity_amount,
+        &lp_token_addr,
+    )?);
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        attr("input_amount", amount),
+        attr("swap_amount", swap_amount),
+        attr("token_bought", token_bought),
+        attr("protocol_fee_amount", protocol_fee_amount),
+        attr("liquidity_received", liquidity_amount),
+    ]))
+}
+
+pub fn execute_zap_out(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    amount: Uint128,
+    output_token_enum: TokenSelect,
+    min_output: Uint128,
+    expiration: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    check_expiration(&expiration, &env.block)?;
+    update_cumulative_prices(deps.storage, &env.block)?;
+
+    let lp_token_addr = LP_TOKEN.load(deps.storage)?;
+    let balance = get_token_balance(deps.as_ref(), &lp_token_addr, &info.sender)?;
+    let lp_token_supply = get_lp_token_supply(deps.as_ref(), &lp_token_addr)?;
+    if amount > balance {
+        return Err(ContractError::InsufficientLiquidityError {
+            requested: amount,
+            available: balance,
+        });
+    }
+
+    let (output_token_item, swapped_token_item) = match output_token_enum {
+        TokenSelect::Token1 => (TOKEN1, TOKEN2),
+        TokenSelect::Token2 => (TOKEN2, TOKEN1),
+    };
+    let output_token = output_token_item.load(deps.storage)?;
+    let swapped_token = swapped_token_item.load(deps.storage)?;
+
+    let output_removed = amount
+        .checked_mul(output_token.reserve)
+        .map_err(StdError::overflow)?
+        .checked_div(lp_token_supply)
+        .map_err(StdError::divide_by_zero)?;
+    let swapped_removed = amount
+        .checked_mul(swapped_token.reserve)
+        .map_err(StdError::overflow)?
+        .checked_div(lp_token_supply)
+        .map_err(StdError::divide_by_zero)?;
+
+    // The other token removed is swapped with the reserves left
+    let config = CONFIG.load(deps.storage)?;
+    let output_reserve = output_token.reserve - output_removed;
+    let token_bought = if swapped_removed.is_zero() {
+        Uint128::zero()
+    } else {
+        get_swap_output(
+            load_amp(deps.storage, &env.block)?,
+            swapped_removed,
+            swapped_token.reserve - swapped_removed,
+            output_reserve,
+            config.swap_fee,
+        )?
+    };
+    let output_amount = output_removed + token_bought;
+    if output_amount < min_output {
+        return Err(ContractError::SwapMinError {
+            min: min_output,
+            available: output_amount,
+        });
+    }
+
+    let mut msgs = vec![match &output_token.denom {
+        Denom::Cw20(addr) => get_cw20_transfer_to_msg(&info.sender, addr, output_amount)?,
+        Denom::Native(denom) => get_bank_transfer_to_msg(&info.sender, denom, output_amount),
+    }];
+    // Protocol fee is not added to the reserve
+    let mut protocol_fee_amount = Uint128::zero();
+    if let Some((fee_amount, msg)) =
+        get_protocol_fee_msg(&config, swapped_removed, &swapped_token.denom)?
+    {
+        protocol_fee_amount = fee_amount;
+        msgs.push(msg);
+    }
+    msgs.push(get_burn_msg(&lp_token_addr, &info.sender, amount)?);
+
+    output_token_item.update(deps.storage, |mut token| -> Result<_, ContractError> {
+        token.reserve = output_reserve
+            .checked_sub(token_bought)
+            .map_err(StdError::overflow)?;
+        Ok(token)
+    })?;
+    swapped_token_item.update(deps.storage, |mut token| -> Result<_, ContractError> {
+        token.reserve = token
+            .reserve
+            .checked_sub(protocol_fee_amount)
+            .map_err(StdError::overflow)?;
+        Ok(token)
+    })?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        attr("liquidity_burned", amount),
+        attr("token_removed", output_removed),
+        attr("token_swapped", swapped_removed),
+        attr("token_bought", token_bought),
+        attr("protocol_fee_amount", protocol_fee_amount),
+        attr("output_amount", output_amount),
+    ]))
+}
+
 fn get_burn_msg(contract: &Addr, owner: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
     let msg = cw20_base::msg::ExecuteMsg::BurnFrom {
         owner: owner.to_string(),
@@ -531,26 +1422,156 @@ fn get_input_price(
     input_amount: Uint128,
     input_reserve: Uint128,
     output_reserve: Uint128,
+    swap_fee: Decimal,
 ) -> StdResult<Uint128> {
     if input_reserve == Uint128::zero() || output_reserve == Uint128::zero() {
         return Err(StdError::generic_err("No liquidity"));
     };
 
-    let input_amount_with_fee = input_amount
-        .checked_mul(Uint128::new(997))
-        .map_err(StdError::overflow)?;
+    let fee = Uint128::new(FEE_SCALE_FACTOR) * swap_fee;
+    let input_amount_with_fee = Uint512::from(input_amount.full_mul(FEE_SCALE_FACTOR - fee.u128()));
     let numerator = input_amount_with_fee
-        .checked_mul(output_reserve)
+        .checked_mul(output_reserve.into())
         .map_err(StdError::overflow)?;
-    let denominator = input_reserve
-        .checked_mul(Uint128::new(1000))
-        .map_err(StdError::overflow)?
+    let denominator = Uint512::from(input_reserve.full_mul(FEE_SCALE_FACTOR))
         .checked_add(input_amount_with_fee)
         .map_err(StdError::overflow)?;
 
-    numerator
+    Ok(numerator
         .checked_div(denominator)
-        .map_err(StdError::divide_by_zero)
+        .map_err(StdError::divide_by_zero)?
+        .try_into()?)
+}
+
+/// Input required to buy `output_amount`, rounded up
+fn get_output_price(
+    output_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+) -> StdResult<Uint128> {
+    if input_reserve == Uint128::zero() || output_reserve == Uint128::zero() {
+        return Err(StdError::generic_err("No liquidity"));
+    };
+    if output_amount >= output_reserve {
+        return Err(StdError::generic_err("Output exceeds the reserve"));
+    }
+
+    let fee = Uint128::new(FEE_SCALE_FACTOR) * swap_fee;
+    let numerator = Uint512::from(input_reserve.full_mul(output_amount))
+        .checked_mul(Uint512::from(FEE_SCALE_FACTOR))
+        .map_err(StdError::overflow)?;
+    let denominator =
+        Uint512::from((output_reserve - output_amount).full_mul(FEE_SCALE_FACTOR - fee.u128()));
+
+    let input_amount: Uint128 = numerator
+        .checked_div(denominator)
+        .map_err(StdError::divide_by_zero)?
+        .try_into()?;
+    input_amount
+        .checked_add(Uint128::new(1))
+        .map_err(StdError::overflow)
+}
+
+/// Output of swapping `input_amount`, with the invariant of the pool
+fn get_swap_output(
+    amp: Option<u64>,
+    input_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+) -> StdResult<Uint128> {
+    match amp {
+        Some(amp) => stableswap::get_input_price(
+            amp,
+            input_amount,
+            input_reserve,
+            output_reserve,
+            swap_fee,
+            FEE_SCALE_FACTOR,
+        ),
+        None => get_input_price(input_amount, input_reserve, output_reserve, swap_fee),
+    }
+}
+
+/// Input required to buy `output_amount`, with the invariant of the pool
+fn get_swap_input(
+    amp: Option<u64>,
+    output_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+) -> StdResult<Uint128> {
+    match amp {
+        Some(amp) => stableswap::get_output_price(
+            amp,
+            output_amount,
+            input_reserve,
+            output_reserve,
+            swap_fee,
+            FEE_SCALE_FACTOR,
+        ),
+        None => get_output_price(output_amount, input_reserve, output_reserve, swap_fee),
+    }
+}
+
+/// Swap fee paid on the input, spot price of the input token in the output token and price
+/// impact of a swap of `input_amount` for `output_amount`
+fn get_swap_costs(
+    amp: Option<u64>,
+    input_amount: Uint128,
+    output_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+) -> StdResult<(Uint128, Decimal, Decimal)> {
+    let fee_amount = input_amount * swap_fee;
+    // Part of the output lost to the move of the price, net of the fee
+    let net_input = input_amount - fee_amount;
+    match amp {
+        Some(amp) => {
+            let spot_price = stableswap::spot_price(amp, input_reserve, output_reserve)?;
+            let spot_output = net_input * spot_price;
+            let price_impact = if spot_output <= output_amount {
+                Decimal::zero()
+            } else {
+                Decimal::from_ratio(spot_output - output_amount, spot_output)
+            };
+            Ok((fee_amount, spot_price, price_impact))
+        }
+        None => {
+            let spot_price = Decimal::checked_from_ratio(output_reserve, input_reserve)
+                .map_err(|_| StdError::generic_err("Invalid spot price"))?;
+            let price_impact = if net_input.is_zero() {
+                Decimal::zero()
+            } else {
+                Decimal::from_ratio(net_input, input_reserve + net_input)
+            };
+            Ok((fee_amount, spot_price, price_impact))
+        }
+    }
+}
+
+/// Protocol part of the swap fee and the message sending it, None when kept by the pool
+fn get_protocol_fee_msg(
+    config: &Config,
+    input_amount: Uint128,
+    input_denom: &Denom,
+) -> StdResult<Option<(Uint128, CosmosMsg)>> {
+    let amount = input_amount * (config.swap_fee * config.protocol_fee_share);
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    let msg = match (&config.protocol_fee_recipient, input_denom) {
+        (Some(recipient), Denom::Cw20(addr)) => get_cw20_transfer_to_msg(recipient, addr, amount)?,
+        (Some(recipient), Denom::Native(denom)) => {
+            get_bank_transfer_to_msg(recipient, denom, amount)
+        }
+        (None, Denom::Native(denom)) => create_fund_community_pool_msg(coins(amount.u128(), denom)),
+        // cw20 tokens cannot fund the community pool
+        (None, Denom::Cw20(_)) => return Ok(None),
+    };
+    Ok(Some((amount, msg)))
 }
 
 fn get_amount_for_denom(coins: &[Coin], denom: &str) -> Coin {
@@ -575,8 +1596,10 @@ pub fn execute_swap(
     recipient: String,
     min_token: Uint128,
     expiration: Option<Expiration>,
+    received: bool,
 ) -> Result<Response, ContractError> {
     check_expiration(&expiration, &_env.block)?;
+    update_cumulative_prices(deps.storage, &_env.block)?;
 
     let input_token_item = match input_token_enum {
         TokenSelect::Token1 => TOKEN1,
@@ -592,7 +1615,14 @@ pub fn execute_swap(
     // validate input_amount if native input token
     validate_input_amount(&info.funds, input_amount, &input_token.denom)?;
 
-    let token_bought = get_input_price(input_amount, input_token.reserve, output_token.reserve)?;
+    let config = CONFIG.load(deps.storage)?;
+    let token_bought = get_swap_output(
+        load_amp(deps.storage, &_env.block)?,
+        input_amount,
+        input_token.reserve,
+        output_token.reserve,
+        config.swap_fee,
+    )?;
 
     if min_token > token_bought {
         return Err(ContractError::SwapMinError {
@@ -601,12 +1631,13 @@ pub fn execute_swap(
         });
     }
 
-    // Create transfer from message
-    let mut transfer_msgs = match input_token.denom {
+    // Create transfer from message, unless the input was sent with the cw20 hook
+    let mut transfer_msgs = match &input_token.denom {
+        Denom::Cw20(_) if received => vec![],
         Denom::Cw20(addr) => vec![get_cw20_transfer_from_msg(
             &info.sender,
             &_env.contract.address,
-            &addr,
+            addr,
             input_amount,
         )?],
         Denom::Native(_) => vec![],
@@ -619,12 +1650,19 @@ pub fn execute_swap(
         Denom::Native(denom) => get_bank_transfer_to_msg(&recipient, &denom, token_bought),
     });
 
+    // Protocol fee is not added to the reserve
+    let mut protocol_fee_amount = Uint128::zero();
+    if let Some((amount, msg)) = get_protocol_fee_msg(&config, input_amount, &input_token.denom)? {
+        protocol_fee_amount = amount;
+        transfer_msgs.push(msg);
+    }
+
     input_token_item.update(
         deps.storage,
         |mut input_token| -> Result<_, ContractError> {
             input_token.reserve = input_token
                 .reserve
-                .checked_add(input_amount)
+                .checked_add(input_amount - protocol_fee_amount)
                 .map_err(StdError::overflow)?;
             Ok(input_token)
         },
@@ -646,9 +1684,76 @@ pub fn execute_swap(
         .add_attributes(vec![
             attr("native_sold", input_amount),
             attr("token_bought", token_bought),
+            attr("protocol_fee_amount", protocol_fee_amount),
         ]))
 }
 
+/// Swap for exactly `output_amount`, native input not required is refunded
+#[allow(clippy::too_many_arguments)]
+pub fn execute_swap_exact_output(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    input_token_enum: TokenSelect,
+    output_amount: Uint128,
+    max_input: Uint128,
+    recipient: Option<String>,
+    expiration: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let (input_token, output_token) = match input_token_enum {
+        TokenSelect::Token1 => (TOKEN1.load(deps.storage)?, TOKEN2.load(deps.storage)?),
+        TokenSelect::Token2 => (TOKEN2.load(deps.storage)?, TOKEN1.load(deps.storage)?),
+    };
+    validate_input_amount(&info.funds, max_input, &input_token.denom)?;
+
+    let swap_fee = CONFIG.load(deps.storage)?.swap_fee;
+    let input_amount = get_swap_input(
+        load_amp(deps.storage, &env.block)?,
+        output_amount,
+        input_token.reserve,
+        output_token.reserve,
+        swap_fee,
+    )?;
+    if input_amount > max_input {
+        return Err(ContractError::SwapMaxError {
+            max: max_input,
+            required: input_amount,
+        });
+    }
+
+    // The swap takes the input required, rounding up the input never buys less than the output
+    let swap_info = MessageInfo {
+        sender: info.sender.clone(),
+        funds: match &input_token.denom {
+            Denom::Native(denom) => coins(input_amount.u128(), denom),
+            Denom::Cw20(_) => vec![],
+        },
+    };
+    let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+    let mut res = execute_swap(
+        deps,
+        &swap_info,
+        input_amount,
+        env,
+        input_token_enum,
+        recipient,
+        output_amount,
+        expiration,
+        false,
+    )?;
+
+    if let Denom::Native(denom) = input_token.denom {
+        if input_amount < max_input {
+            res = res.add_message(get_bank_transfer_to_msg(
+                &info.sender,
+                &denom,
+                max_input - input_amount,
+            ));
+        }
+    }
+    Ok(res.add_attribute("input_refunded", max_input - input_amount))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_pass_through_swap(
     deps: DepsMut,
@@ -661,6 +1766,7 @@ pub fn execute_pass_through_swap(
     expiration: Option<Expiration>,
 ) -> Result<Response, ContractError> {
     check_expiration(&expiration, &_env.block)?;
+    update_cumulative_prices(deps.storage, &_env.block)?;
 
     let input_token_state = match input_token_enum {
         TokenSelect::Token1 => TOKEN1,
@@ -675,10 +1781,13 @@ pub fn execute_pass_through_swap(
 
     validate_input_amount(&info.funds, input_token_amount, &input_token.denom)?;
 
-    let amount_to_transfer = get_input_price(
+    let config = CONFIG.load(deps.storage)?;
+    let amount_to_transfer = get_swap_output(
+        load_amp(deps.storage, &_env.block)?,
         input_token_amount,
         input_token.reserve,
         transfer_token.reserve,
+        config.swap_fee,
     )?;
 
     // Transfer tokens to contract
@@ -730,10 +1839,19 @@ pub fn execute_pass_through_swap(
         .into(),
     );
 
+    // Protocol fee is not added to the reserve
+    let mut protocol_fee_amount = Uint128::zero();
+    if let Some((amount, msg)) =
+        get_protocol_fee_msg(&config, input_token_amount, &input_token.denom)?
+    {
+        protocol_fee_amount = amount;
+        msgs.push(msg);
+    }
+
     input_token_state.update(deps.storage, |mut token| -> Result<_, ContractError> {
         token.reserve = token
             .reserve
-            .checked_add(input_token_amount)
+            .checked_add(input_token_amount - protocol_fee_amount)
             .map_err(StdError::overflow)?;
         Ok(token)
     })?;
@@ -749,27 +1867,173 @@ pub fn execute_pass_through_swap(
     Ok(Response::new().add_messages(msgs).add_attributes(vec![
         attr("input_token_amount", input_token_amount),
         attr("native_transferred", amount_to_transfer),
+        attr("protocol_fee_amount", protocol_fee_amount),
     ]))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
-        QueryMsg::Info {} => to_binary(&query_info(deps)?),
+        QueryMsg::Info {} => to_binary(&query_info(deps, env)?),
         QueryMsg::Token1ForToken2Price { token1_amount } => {
-            to_binary(&query_token1_for_token2_price(deps, token1_amount)?)
+            to_binary(&query_token1_for_token2_price(deps, env, token1_amount)?)
         }
         QueryMsg::Token2ForToken1Price { token2_amount } => {
-            to_binary(&query_token2_for_token1_price(deps, token2_amount)?)
+            to_binary(&query_token2_for_token1_price(deps, env, token2_amount)?)
         }
+        QueryMsg::Twap { window } => to_binary(&query_twap(deps, env, window)?),
+        QueryMsg::SimulateSwap {
+            input_token,
+            input_amount,
+        } => to_binary(&query_simulate_swap(deps, env, input_token, input_amount)?),
+        QueryMsg::ReverseSimulateSwap {
+            input_token,
+            output_amount,
+        } => to_binary(&query_reverse_simulate_swap(
+            deps,
+            env,
+            input_token,
+            output_amount,
+        )?),
+    }
+}
+
+fn load_reserves(deps: Deps, input_token: TokenSelect) -> StdResult<(Uint128, Uint128)> {
+    let token1 = TOKEN1.load(deps.storage)?;
+    let token2 = TOKEN2.load(deps.storage)?;
+    Ok(match input_token {
+        TokenSelect::Token1 => (token1.reserve, token2.reserve),
+        TokenSelect::Token2 => (token2.reserve, token1.reserve),
+    })
+}
+
+pub fn query_simulate_swap(
+    deps: Deps,
+    env: Env,
+    input_token: TokenSelect,
+    input_amount: Uint128,
+) -> StdResult<SimulateSwapResponse> {
+    let (input_reserve, output_reserve) = load_reserves(deps, input_token)?;
+    let swap_fee = CONFIG.load(deps.storage)?.swap_fee;
+    let amp = load_amp(deps.storage, &env.block)?;
+    let output_amount =
+        get_swap_output(amp, input_amount, input_reserve, output_reserve, swap_fee)?;
+    let (fee_amount, spot_price, price_impact) = get_swap_costs(
+        amp,
+        input_amount,
+        output_amount,
+        input_reserve,
+        output_reserve,
+        swap_fee,
+    )?;
+    Ok(SimulateSwapResponse {
+        output_amount,
+        fee_amount,
+        spot_price,
+        price_impact,
+    })
+}
+
+pub fn query_reverse_simulate_swap(
+    deps: Deps,
+    env: Env,
+    input_token: TokenSelect,
+    output_amount: Uint128,
+) -> StdResult<ReverseSimulateSwapResponse> {
+    let (input_reserve, output_reserve) = load_reserves(deps, input_token)?;
+    let swap_fee = CONFIG.load(deps.storage)?.swap_fee;
+    let amp = load_amp(deps.storage, &env.block)?;
+    let input_amount = get_swap_input(amp, output_amount, input_reserve, output_reserve, swap_fee)?;
+    let (fee_amount, spot_price, price_impact) = get_swap_costs(
+        amp,
+        input_amount,
+        output_amount,
+        input_reserve,
+        output_reserve,
+        swap_fee,
+    )?;
+    Ok(ReverseSimulateSwapResponse {
+        input_amount,
+        fee_amount,
+        spot_price,
+        price_impact,
+    })
+}
+
+pub fn query_twap(deps: Deps, env: Env, window: u64) -> StdResult<TwapResponse> {
+    if window == 0 || window > MAX_TWAP_WINDOW {
+        return Err(StdError::generic_err(format!(
+            "Invalid twap window: {}, max: {}",
+            window, MAX_TWAP_WINDOW
+        )));
     }
+    let now = env.block.time.seconds();
+    let start = now
+        .checked_sub(window)
+        .ok_or_else(|| StdError::generic_err("Window longer than the price history"))?;
+
+    let token1 = TOKEN1.load(deps.storage)?;
+    let token2 = TOKEN2.load(deps.storage)?;
+    let current = accumulate_prices(
+        CUMULATIVE_PRICES.load(deps.storage)?,
+        now,
+        load_amp(deps.storage, &env.block)?,
+        token1.reserve,
+        token2.reserve,
+    )?;
+
+    // Prices are constant between two snapshots, the cumulative prices at the start of the
+    // window are interpolated from the snapshots around it
+    let before = OBSERVATIONS
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive_int(start)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, prices)| prices)
+        .ok_or_else(|| StdError::generic_err("Window longer than the price history"))?;
+    let after = OBSERVATIONS
+        .range(
+            deps.storage,
+            Some(Bound::exclusive_int(start)),
+            None,
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, prices)| prices)
+        .unwrap_or_else(|| current.clone());
+    let (elapsed, period) = (
+        start - before.last_updated,
+        after.last_updated - before.last_updated,
+    );
+    let interpolate =
+        |from: Uint256, to: Uint256| from + (to - from).multiply_ratio(elapsed, period);
+    let window_scale = Uint256::from(window).checked_mul(PRICE_SCALE_FACTOR.into())?;
+
+    Ok(TwapResponse {
+        token1_price: Decimal256::from_ratio(
+            current.price1_cumulative
+                - interpolate(before.price1_cumulative, after.price1_cumulative),
+            window_scale,
+        ),
+        token2_price: Decimal256::from_ratio(
+            current.price2_cumulative
+                - interpolate(before.price2_cumulative, after.price2_cumulative),
+            window_scale,
+        ),
+    })
 }
 
-pub fn query_info(deps: Deps) -> StdResult<InfoResponse> {
+pub fn query_info(deps: Deps, env: Env) -> StdResult<InfoResponse> {
     let token1 = TOKEN1.load(deps.storage)?;
     let token2 = TOKEN2.load(deps.storage)?;
     let lp_token_address = LP_TOKEN.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
     // TODO get total supply
     Ok(InfoResponse {
         token1_reserve: token1.reserve,
@@ -778,26 +2042,53 @@ pub fn query_info(deps: Deps) -> StdResult<InfoResponse> {
         token2_denom: token2.denom,
         lp_token_supply: get_lp_token_supply(deps, &lp_token_address)?,
         lp_token_address: lp_token_address.to_string(),
+        owner: config.owner.map(|addr| addr.to_string()),
+        swap_fee: config.swap_fee,
+        protocol_fee: if config.protocol_fee_share.is_zero() {
+            None
+        } else {
+            Some(ProtocolFee {
+                share: config.protocol_fee_share,
+                recipient: config.protocol_fee_recipient.map(|addr| addr.to_string()),
+            })
+        },
+        amp: load_amp(deps.storage, &env.block)?,
     })
 }
 
 pub fn query_token1_for_token2_price(
     deps: Deps,
+    env: Env,
     token1_amount: Uint128,
 ) -> StdResult<Token1ForToken2PriceResponse> {
     let token1 = TOKEN1.load(deps.storage)?;
     let token2 = TOKEN2.load(deps.storage)?;
-    let token2_amount = get_input_price(token1_amount, token1.reserve, token2.reserve)?;
+    let swap_fee = CONFIG.load(deps.storage)?.swap_fee;
+    let token2_amount = get_swap_output(
+        load_amp(deps.storage, &env.block)?,
+        token1_amount,
+        token1.reserve,
+        token2.reserve,
+        swap_fee,
+    )?;
     Ok(Token1ForToken2PriceResponse { token2_amount })
 }
 
 pub fn query_token2_for_token1_price(
     deps: Deps,
+    env: Env,
     token2_amount: Uint128,
 ) -> StdResult<Token2ForToken1PriceResponse> {
     let token1 = TOKEN1.load(deps.storage)?;
     let token2 = TOKEN2.load(deps.storage)?;
-    let token1_amount = get_input_price(token2_amount, token2.reserve, token1.reserve)?;
+    let swap_fee = CONFIG.load(deps.storage)?.swap_fee;
+    let token1_amount = get_swap_output(
+        load_amp(deps.storage, &env.block)?,
+        token2_amount,
+        token2.reserve,
+        token1.reserve,
+        swap_fee,
+    )?;
     Ok(Token2ForToken1PriceResponse { token1_amount })
 }
 
@@ -865,22 +2156,137 @@ mod tests {
     fn test_get_input_price() {
         // Base case
         assert_eq!(
-            get_input_price(Uint128::new(10), Uint128::new(100), Uint128::new(100)).unwrap(),
+            get_input_price(
+                Uint128::new(10),
+                Uint128::new(100),
+                Uint128::new(100),
+                Decimal::permille(3)
+            )
+            .unwrap(),
             Uint128::new(9)
         );
 
         // No input reserve error
-        let err =
-            get_input_price(Uint128::new(10), Uint128::new(0), Uint128::new(100)).unwrap_err();
+        let err = get_input_price(
+            Uint128::new(10),
+            Uint128::new(0),
+            Uint128::new(100),
+            Decimal::permille(3),
+        )
+        .unwrap_err();
         assert_eq!(err, StdError::generic_err("No liquidity"));
 
         // No output reserve error
-        let err =
-            get_input_price(Uint128::new(10), Uint128::new(100), Uint128::new(0)).unwrap_err();
+        let err = get_input_price(
+            Uint128::new(10),
+            Uint128::new(100),
+            Uint128::new(0),
+            Decimal::permille(3),
+        )
+        .unwrap_err();
         assert_eq!(err, StdError::generic_err("No liquidity"));
 
         // No reserve error
-        let err = get_input_price(Uint128::new(10), Uint128::new(0), Uint128::new(0)).unwrap_err();
+        let err = get_input_price(
+            Uint128::new(10),
+            Uint128::new(0),
+            Uint128::new(0),
+            Decimal::permille(3),
+        )
+        .unwrap_err();
         assert_eq!(err, StdError::generic_err("No liquidity"));
     }
+
+    #[test]
+    fn test_get_output_price() {
+        let fee = Decimal::permille(3);
+        // Base case, rounded up
+        let input =
+            get_output_price(Uint128::new(9), Uint128::new(100), Uint128::new(100), fee).unwrap();
+        assert_eq!(input, Uint128::new(10));
+        assert!(
+            get_input_price(input, Uint128::new(100), Uint128::new(100), fee).unwrap()
+                >= Uint128::new(9)
+        );
+
+        // The input always buys the output
+        for output in 1..1000u128 {
+            let (input_reserve, output_reserve) = (Uint128::new(12_345), Uint128::new(6_789));
+            let input = get_output_price(Uint128::new(output), input_reserve, output_reserve, fee);
+            if output >= output_reserve.u128() {
+                assert!(input.is_err());
+                continue;
+            }
+            let bought = get_input_price(input.unwrap(), input_reserve, output_reserve, fee);
+            assert!(bought.unwrap() >= Uint128::new(output));
+        }
+
+        // Error: output takes the whole reserve
+        let err = get_output_price(Uint128::new(100), Uint128::new(100), Uint128::new(100), fee)
+            .unwrap_err();
+        assert_eq!(err, StdError::generic_err("Output exceeds the reserve"));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 100, u128::MAX] {
+            let root = isqrt(Uint512::from(n));
+            assert!(root * root <= Uint512::from(n));
+            let next = root + Uint512::from(1u128);
+            assert!(next * next > Uint512::from(n));
+        }
+        let max = Uint512::from(u128::MAX);
+        assert_eq!(isqrt(max * max * max * max), max * max);
+    }
+
+    #[test]
+    fn test_constant_product_zap_amount() {
+        for (amount, input_reserve, output_reserve, protocol_fee_rate) in [
+            (1_000u128, 10_000u128, 10_000u128, Decimal::zero()),
+            (30, 1_150, 1_151, Decimal::zero()),
+            (500, 3_000, 7, Decimal::permille(1)),
+            (777, 13, 100_000_000_000, Decimal::permille(2)),
+        ] {
+            let (amount, input_reserve, output_reserve) = (
+                Uint128::new(amount),
+                Uint128::new(input_reserve),
+                Uint128::new(output_reserve),
+            );
+            let swap_fee = Decimal::permille(3);
+            let (swap_amount, bought) = get_zap_swap_amount(
+                None,
+                amount,
+                input_reserve,
+                output_reserve,
+                swap_fee,
+                protocol_fee_rate,
+            )
+            .unwrap();
+
+            // Largest output of a swap leaving the input matching, by trying every swap
+            let best = (0..=amount.u128())
+                .map(Uint128::new)
+                .filter_map(|swap_amount| {
+                    let bought =
+                        get_input_price(swap_amount, input_reserve, output_reserve, swap_fee)
+                            .unwrap();
+                    let input_reserve_after =
+                        input_reserve + swap_amount - swap_amount * protocol_fee_rate;
+                    let matching = (amount - swap_amount).full_mul(output_reserve - bought)
+                        >= bought.full_mul(input_reserve_after);
+                    if matching {
+                        Some(bought)
+                    } else {
+                        None
+                    }
+                })
+                .max()
+                .unwrap();
+            assert_eq!(bought, best);
+            assert_eq!(
+                get_input_price(swap_amount, input_reserve, output_reserve, swap_fee).unwrap(),
+                bought
+            );
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, Uint128};
+use cosmwasm_std::{Decimal, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -52,6 +52,9 @@ pub enum ContractError {
     #[error("Swap min error: min: {min}, available: {available}")]
     SwapMinError { min: Uint128, available: Uint128 },
 
+    #[error("Swap max error: max: {max}, required: {required}")]
+    SwapMaxError { max: Uint128, required: Uint128 },
+
     #[error("MsgExpirationError")]
     MsgExpirationError {},
 
@@ -63,4 +66,28 @@ pub enum ContractError {
 
     #[error("Failed to instantiate lp token")]
     InstantiateLpTokenError {},
+
+    #[error("Swap fee too high: max: {max}, provided: {provided}")]
+    SwapFeeTooHigh { max: Decimal, provided: Decimal },
+
+    #[error("Invalid protocol fee share: {share}")]
+    InvalidProtocolFeeShare { share: Decimal },
+
+    #[error("Cw20 token not in the pool: {token}")]
+    InvalidCw20Token { token: String },
+
+    #[error("Invalid amplification: {amp}, must be between 1 and {max}")]
+    InvalidAmp { amp: u64, max: u64 },
+
+    #[error("Invalid amplification ramp: {reason}")]
+    InvalidAmpRamp { reason: String },
+
+    #[error("Not a stableswap pool")]
+    NotStableswap {},
+
+    #[error("Initial liquidity too low: minimum: {minimum}, provided: {provided}")]
+    InitialLiquidityTooLow { minimum: Uint128, provided: Uint128 },
+
+    #[error("Deposit too small to mint liquidity")]
+    ZeroLiquidity {},
 }
@@ -1,21 +1,109 @@
 #![cfg(test)]
 
 use std::borrow::BorrowMut;
-
-use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
+use std::str::FromStr;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, to_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomQuery, Decimal,
+    Decimal256, Empty, Querier, StdResult, Storage, Uint128,
+};
 use cw0::Expiration;
 
 use crate::error::ContractError;
-use cw20::{Cw20Coin, Cw20Contract, Cw20ExecuteMsg, Denom};
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw20::{
+    Cw20Coin, Cw20Contract, Cw20ExecuteMsg, Cw20QueryMsg, Denom, MarketingInfoResponse,
+    TokenInfoResponse,
+};
+use cw_multi_test::{
+    App, AppBuilder, AppResponse, BankKeeper, Contract, ContractWrapper, CosmosRouter, Executor,
+    Module, WasmKeeper,
+};
+use s_std::{SignMsg, SignMsgWrapper};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use crate::msg::{
+    ExecuteMsg, InfoResponse, InstantiateMsg, LpTokenInfo, PoolType, ProtocolFee, QueryMsg,
+    ReceiveMsg, ReverseSimulateSwapResponse, SimulateSwapResponse, TokenSelect, TwapResponse,
+};
+
+const COMMUNITY_POOL: &str = "community_pool";
+
+/// Sends the funds of `FundCommunityPool` messages to the `COMMUNITY_POOL` address
+struct CommunityPool;
+
+impl Module for CommunityPool {
+    type ExecT = SignMsgWrapper;
+    type QueryT = Empty;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: SignMsgWrapper,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg.msg_data {
+            SignMsg::FundCommunityPool { amount } => router.execute(
+                api,
+                storage,
+                block,
+                sender,
+                BankMsg::Send {
+                    to_address: COMMUNITY_POOL.to_string(),
+                    amount,
+                }
+                .into(),
+            ),
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Unexpected sudo msg {:?}", msg)
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Empty,
+    ) -> AnyResult<Binary> {
+        bail!("Unexpected custom query {:?}", request)
+    }
+}
 
-use crate::msg::{ExecuteMsg, InfoResponse, InstantiateMsg, QueryMsg, TokenSelect};
+type SignApp =
+    App<BankKeeper, MockApi, MockStorage, CommunityPool, WasmKeeper<SignMsgWrapper, Empty>>;
 
-fn mock_app() -> App {
-    App::default()
+fn mock_app() -> SignApp {
+    AppBuilder::new_custom()
+        .with_custom(CommunityPool)
+        .build(|_, _, _| {})
 }
 
-pub fn contract_amm() -> Box<dyn Contract<Empty>> {
+pub fn contract_amm() -> Box<dyn Contract<SignMsgWrapper>> {
     let contract = ContractWrapper::new(
         crate::contract::execute,
         crate::contract::instantiate,
@@ -25,8 +113,8 @@ pub fn contract_amm() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
-pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(
+pub fn contract_cw20() -> Box<dyn Contract<SignMsgWrapper>> {
+    let contract = ContractWrapper::new_with_empty(
         cw20_base::contract::execute,
         cw20_base::contract::instantiate,
         cw20_base::contract::query,
@@ -34,14 +122,19 @@ pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
-fn get_info(router: &App, contract_addr: &Addr) -> InfoResponse {
+fn get_info(router: &SignApp, contract_addr: &Addr) -> InfoResponse {
     router
         .wrap()
         .query_wasm_smart(contract_addr, &QueryMsg::Info {})
         .unwrap()
 }
 
-fn create_amm(router: &mut App, owner: &Addr, cash: &Cw20Contract, native_denom: String) -> Addr {
+fn create_amm(
+    router: &mut SignApp,
+    owner: &Addr,
+    cash: &Cw20Contract,
+    native_denom: String,
+) -> Addr {
     // set up amm contract
     let cw20_id = router.store_code(contract_cw20());
     let amm_id = router.store_code(contract_amm());
@@ -49,6 +142,11 @@ fn create_amm(router: &mut App, owner: &Addr, cash: &Cw20Contract, native_denom:
         token1_denom: Denom::Native(native_denom),
         token2_denom: Denom::Cw20(cash.addr()),
         lp_token_code_id: cw20_id,
+        owner: Some(owner.to_string()),
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
     };
     router
         .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
@@ -57,7 +155,7 @@ fn create_amm(router: &mut App, owner: &Addr, cash: &Cw20Contract, native_denom:
 
 // CreateCW20 create new cw20 with given initial balance belonging to owner
 fn create_cw20(
-    router: &mut App,
+    router: &mut SignApp,
     owner: &Addr,
     name: String,
     symbol: String,
@@ -82,7 +180,7 @@ fn create_cw20(
     Cw20Contract(addr)
 }
 
-fn bank_balance(router: &mut App, addr: &Addr, denom: String) -> Coin {
+fn bank_balance(router: &mut SignApp, addr: &Addr, denom: String) -> Coin {
     router
         .wrap()
         .query_balance(addr.to_string(), denom)
@@ -115,7 +213,79 @@ fn test_instantiate() {
     assert_ne!(cw20_token.addr(), amm_addr);
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.lp_token_address, "contract2".to_string())
+    assert_eq!(info.lp_token_address, "contract2".to_string());
+
+    // Metadata derived from the pair, the owner is the admin and marketing of the lp token
+    let lp_token = Addr::unchecked(info.lp_token_address);
+    let token_info: TokenInfoResponse = router
+        .wrap()
+        .query_wasm_smart(&lp_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(token_info.name, "juno-CWTOKEN LP");
+    assert_eq!(token_info.symbol, "JUNO-CWTOK");
+    assert_eq!(token_info.decimals, 6);
+    let marketing_info: MarketingInfoResponse = router
+        .wrap()
+        .query_wasm_smart(&lp_token, &Cw20QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(
+        marketing_info.description,
+        Some("WasmSwap liquidity token of the juno and CWTOKEN pool".to_string())
+    );
+    assert_eq!(marketing_info.marketing, Some(owner.clone()));
+    assert_eq!(
+        router.contract_data(&lp_token).unwrap().admin,
+        Some(owner.clone())
+    );
+
+    // Metadata passed in the instantiate message
+    let cw20_id = router.store_code(contract_cw20());
+    let amm_id = router.store_code(contract_amm());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Cw20(cw20_token.addr()),
+        lp_token_code_id: cw20_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: Some(LpTokenInfo {
+            name: Some("Juno Token LP".to_string()),
+            symbol: Some("jtlp".to_string()),
+            decimals: Some(12),
+            marketing: None,
+        }),
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+    let lp_token = Addr::unchecked(get_info(&router, &amm_addr).lp_token_address);
+    let token_info: TokenInfoResponse = router
+        .wrap()
+        .query_wasm_smart(&lp_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(token_info.name, "Juno Token LP");
+    assert_eq!(token_info.symbol, "jtlp");
+    assert_eq!(token_info.decimals, 12);
+    // The pool is the admin of the lp token without owner
+    assert_eq!(
+        router.contract_data(&lp_token).unwrap().admin,
+        Some(amm_addr.clone())
+    );
+
+    // Error: invalid symbol
+    let msg = InstantiateMsg {
+        lp_token: Some(LpTokenInfo {
+            name: None,
+            symbol: Some("jt".to_string()),
+            decimals: None,
+            marketing: None,
+        }),
+        ..msg
+    };
+    router
+        .instantiate_contract(amm_id, owner, &msg, &[], "amm", None)
+        .unwrap_err();
 }
 
 #[test]
@@ -154,17 +324,18 @@ fn amm_add_and_remove_liquidity() {
     // send tokens to contract address
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
         spender: amm_addr.to_string(),
-        amount: Uint128::new(100u128),
+        amount: Uint128::new(1_100u128),
         expires: None,
     };
     let _res = router
         .execute_contract(owner.clone(), cw20_token.addr(), &allowance_msg, &[])
         .unwrap();
 
+    // The first 1000 of liquidity are locked in the pool
     let add_liquidity_msg = ExecuteMsg::AddLiquidity {
-        token1_amount: Uint128::new(100),
+        token1_amount: Uint128::new(1_100),
         min_liquidity: Uint128::new(100),
-        max_token2: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
         expiration: None,
     };
     let _res = router
@@ -174,16 +345,16 @@ fn amm_add_and_remove_liquidity() {
             &add_liquidity_msg,
             &[Coin {
                 denom: NATIVE_TOKEN_DENOM.into(),
-                amount: Uint128::new(100),
+                amount: Uint128::new(1_100),
             }],
         )
         .unwrap();
 
     // ensure balances updated
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(4900));
+    assert_eq!(owner_balance, Uint128::new(3900));
     let amm_balance = cw20_token.balance(&router, amm_addr.clone()).unwrap();
-    assert_eq!(amm_balance, Uint128::new(100));
+    assert_eq!(amm_balance, Uint128::new(1_100));
     let crust_balance = lp_token.balance(&router, owner.clone()).unwrap();
     assert_eq!(crust_balance, Uint128::new(100));
 
@@ -217,9 +388,9 @@ fn amm_add_and_remove_liquidity() {
 
     // ensure balances updated
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(4849));
+    assert_eq!(owner_balance, Uint128::new(3849));
     let amm_balance = cw20_token.balance(&router, amm_addr.clone()).unwrap();
-    assert_eq!(amm_balance, Uint128::new(151));
+    assert_eq!(amm_balance, Uint128::new(1_151));
     let crust_balance = lp_token.balance(&router, owner.clone()).unwrap();
     assert_eq!(crust_balance, Uint128::new(150));
 
@@ -394,9 +565,9 @@ fn amm_add_and_remove_liquidity() {
 
     // ensure balances updated
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(4899));
+    assert_eq!(owner_balance, Uint128::new(3899));
     let amm_balance = cw20_token.balance(&router, amm_addr.clone()).unwrap();
-    assert_eq!(amm_balance, Uint128::new(101));
+    assert_eq!(amm_balance, Uint128::new(1_101));
     let crust_balance = lp_token.balance(&router, owner.clone()).unwrap();
     assert_eq!(crust_balance, Uint128::new(100));
 
@@ -430,11 +601,14 @@ fn amm_add_and_remove_liquidity() {
 
     // ensure balances updated
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(5000));
-    let amm_balance = cw20_token.balance(&router, amm_addr).unwrap();
-    assert_eq!(amm_balance, Uint128::new(0));
+    assert_eq!(owner_balance, Uint128::new(3999));
+    let amm_balance = cw20_token.balance(&router, amm_addr.clone()).unwrap();
+    assert_eq!(amm_balance, Uint128::new(1001));
     let crust_balance = lp_token.balance(&router, owner.clone()).unwrap();
     assert_eq!(crust_balance, Uint128::new(0));
+    // The locked liquidity stays in the pool
+    let locked_balance = lp_token.balance(&router, amm_addr).unwrap();
+    assert_eq!(locked_balance, Uint128::new(1000));
 }
 
 #[test]
@@ -473,17 +647,18 @@ fn swap_tokens_happy_path() {
     // send tokens to contract address
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
         spender: amm_addr.to_string(),
-        amount: Uint128::new(100u128),
+        amount: Uint128::new(1_100u128),
         expires: None,
     };
     let _res = router
         .execute_contract(owner.clone(), cw20_token.addr(), &allowance_msg, &[])
         .unwrap();
 
+    // The first 1000 of liquidity are locked in the pool
     let add_liquidity_msg = ExecuteMsg::AddLiquidity {
-        token1_amount: Uint128::new(100),
+        token1_amount: Uint128::new(1_100),
         min_liquidity: Uint128::new(100),
-        max_token2: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
         expiration: None,
     };
     let _res = router
@@ -493,14 +668,14 @@ fn swap_tokens_happy_path() {
             &add_liquidity_msg,
             &[Coin {
                 denom: NATIVE_TOKEN_DENOM.into(),
-                amount: Uint128::new(100),
+                amount: Uint128::new(1_100),
             }],
         )
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(100));
-    assert_eq!(info.token2_reserve, Uint128::new(100));
+    assert_eq!(info.token1_reserve, Uint128::new(1_100));
+    assert_eq!(info.token2_reserve, Uint128::new(1_100));
 
     let buyer = Addr::unchecked("buyer");
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
@@ -527,8 +702,8 @@ fn swap_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(110));
-    assert_eq!(info.token2_reserve, Uint128::new(91));
+    assert_eq!(info.token1_reserve, Uint128::new(1_110));
+    assert_eq!(info.token2_reserve, Uint128::new(1_091));
 
     // ensure balances updated
     let buyer_balance = cw20_token.balance(&router, buyer.clone()).unwrap();
@@ -557,12 +732,12 @@ fn swap_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(120));
-    assert_eq!(info.token2_reserve, Uint128::new(84));
+    assert_eq!(info.token1_reserve, Uint128::new(1_120));
+    assert_eq!(info.token2_reserve, Uint128::new(1_082));
 
     // ensure balances updated
     let buyer_balance = cw20_token.balance(&router, buyer.clone()).unwrap();
-    assert_eq!(buyer_balance, Uint128::new(16));
+    assert_eq!(buyer_balance, Uint128::new(18));
 
     // Check balances of owner and buyer reflect the sale transaction
     let balance: Coin = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
@@ -573,7 +748,7 @@ fn swap_tokens_happy_path() {
     // send tokens to contract address
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
         spender: amm_addr.to_string(),
-        amount: Uint128::new(16),
+        amount: Uint128::new(18),
         expires: None,
     };
     let _res = router
@@ -582,8 +757,8 @@ fn swap_tokens_happy_path() {
 
     let swap_msg = ExecuteMsg::Swap {
         input_token: TokenSelect::Token2,
-        input_amount: Uint128::new(16),
-        min_output: Uint128::new(19),
+        input_amount: Uint128::new(18),
+        min_output: Uint128::new(18),
         expiration: None,
     };
     let _res = router
@@ -591,8 +766,8 @@ fn swap_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(101));
-    assert_eq!(info.token2_reserve, Uint128::new(100));
+    assert_eq!(info.token1_reserve, Uint128::new(1_102));
+    assert_eq!(info.token2_reserve, Uint128::new(1_100));
 
     // ensure balances updated
     let buyer_balance = cw20_token.balance(&router, buyer.clone()).unwrap();
@@ -600,11 +775,11 @@ fn swap_tokens_happy_path() {
 
     // Check balances of owner and buyer reflect the sale transaction
     let balance: Coin = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(balance.amount, Uint128::new(1999));
+    assert_eq!(balance.amount, Uint128::new(1998));
 
     // check owner balance
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(4900));
+    assert_eq!(owner_balance, Uint128::new(3900));
 
     let swap_msg = ExecuteMsg::SwapAndSendTo {
         input_token: TokenSelect::Token1,
@@ -626,16 +801,16 @@ fn swap_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(111));
-    assert_eq!(info.token2_reserve, Uint128::new(92));
+    assert_eq!(info.token1_reserve, Uint128::new(1_112));
+    assert_eq!(info.token2_reserve, Uint128::new(1_091));
 
     // ensure balances updated
     let owner_balance = cw20_token.balance(&router, owner.clone()).unwrap();
-    assert_eq!(owner_balance, Uint128::new(4908));
+    assert_eq!(owner_balance, Uint128::new(3909));
 
     // Check balances of owner and buyer reflect the sale transaction
     let balance = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(balance.amount, Uint128::new(1989));
+    assert_eq!(balance.amount, Uint128::new(1988));
 }
 
 #[test]
@@ -666,16 +841,21 @@ fn swap_native_to_native_tokens_happy_path() {
         token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
         token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
         lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
     };
     let amm_addr = router
         .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
         .unwrap();
 
-    // send tokens to contract address
+    // The first 1000 of liquidity are locked in the pool
     let add_liquidity_msg = ExecuteMsg::AddLiquidity {
-        token1_amount: Uint128::new(100),
+        token1_amount: Uint128::new(1_100),
         min_liquidity: Uint128::new(100),
-        max_token2: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
         expiration: None,
     };
     let _res = router
@@ -686,19 +866,19 @@ fn swap_native_to_native_tokens_happy_path() {
             &[
                 Coin {
                     denom: NATIVE_TOKEN_DENOM.into(),
-                    amount: Uint128::new(100),
+                    amount: Uint128::new(1_100),
                 },
                 Coin {
                     denom: IBC_TOKEN_DENOM.into(),
-                    amount: Uint128::new(100),
+                    amount: Uint128::new(1_100),
                 },
             ],
         )
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(100));
-    assert_eq!(info.token2_reserve, Uint128::new(100));
+    assert_eq!(info.token1_reserve, Uint128::new(1_100));
+    assert_eq!(info.token2_reserve, Uint128::new(1_100));
 
     let buyer = Addr::unchecked("buyer");
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
@@ -725,8 +905,8 @@ fn swap_native_to_native_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(110));
-    assert_eq!(info.token2_reserve, Uint128::new(91));
+    assert_eq!(info.token1_reserve, Uint128::new(1_110));
+    assert_eq!(info.token2_reserve, Uint128::new(1_091));
 
     // Check balances of owner and buyer reflect the sale transaction
     let native_balance: Coin = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
@@ -753,20 +933,20 @@ fn swap_native_to_native_tokens_happy_path() {
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(120));
-    assert_eq!(info.token2_reserve, Uint128::new(84));
+    assert_eq!(info.token1_reserve, Uint128::new(1_120));
+    assert_eq!(info.token2_reserve, Uint128::new(1_082));
 
     // Check balances of owner and buyer reflect the sale transaction
     let native_balance: Coin = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
     assert_eq!(native_balance.amount, Uint128::new(1980));
     let ibc_balance: Coin = bank_balance(&mut router, &buyer, IBC_TOKEN_DENOM.to_string());
-    assert_eq!(ibc_balance.amount, Uint128::new(16));
+    assert_eq!(ibc_balance.amount, Uint128::new(18));
 
     // Swap token for native
     let swap_msg = ExecuteMsg::Swap {
         input_token: TokenSelect::Token2,
-        input_amount: Uint128::new(16),
-        min_output: Uint128::new(19),
+        input_amount: Uint128::new(18),
+        min_output: Uint128::new(18),
         expiration: None,
     };
     let _res = router
@@ -776,18 +956,18 @@ fn swap_native_to_native_tokens_happy_path() {
             &swap_msg,
             &[Coin {
                 denom: IBC_TOKEN_DENOM.into(),
-                amount: Uint128::new(16),
+                amount: Uint128::new(18),
             }],
         )
         .unwrap();
 
     let info = get_info(&router, &amm_addr);
-    assert_eq!(info.token1_reserve, Uint128::new(101));
-    assert_eq!(info.token2_reserve, Uint128::new(100));
+    assert_eq!(info.token1_reserve, Uint128::new(1_102));
+    assert_eq!(info.token2_reserve, Uint128::new(1_100));
 
     // Check balances of owner and buyer reflect the sale transaction
     let native_balance: Coin = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(native_balance.amount, Uint128::new(1999));
+    assert_eq!(native_balance.amount, Uint128::new(1998));
     let ibc_balance: Coin = bank_balance(&mut router, &buyer, IBC_TOKEN_DENOM.to_string());
     assert_eq!(ibc_balance.amount, Uint128::new(0));
 
@@ -837,7 +1017,7 @@ fn token_to_token_swap() {
     const NATIVE_TOKEN_DENOM: &str = "juno";
 
     let owner = Addr::unchecked("owner");
-    let funds = coins(2000, NATIVE_TOKEN_DENOM);
+    let funds = coins(3000, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap()
     });
@@ -860,10 +1040,10 @@ fn token_to_token_swap() {
     let amm1 = create_amm(&mut router, &owner, &token1, NATIVE_TOKEN_DENOM.to_string());
     let amm2 = create_amm(&mut router, &owner, &token2, NATIVE_TOKEN_DENOM.to_string());
 
-    // Add initial liquidity to both pools
+    // Add initial liquidity to both pools, the first 1000 of liquidity are locked
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
         spender: amm1.to_string(),
-        amount: Uint128::new(100),
+        amount: Uint128::new(1_100),
         expires: None,
     };
     let _res = router
@@ -871,9 +1051,9 @@ fn token_to_token_swap() {
         .unwrap();
 
     let add_liquidity_msg = ExecuteMsg::AddLiquidity {
-        token1_amount: Uint128::new(100),
+        token1_amount: Uint128::new(1_100),
         min_liquidity: Uint128::new(100),
-        max_token2: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
         expiration: None,
     };
     router
@@ -883,14 +1063,14 @@ fn token_to_token_swap() {
             &add_liquidity_msg,
             &[Coin {
                 denom: NATIVE_TOKEN_DENOM.into(),
-                amount: Uint128::new(100),
+                amount: Uint128::new(1_100),
             }],
         )
         .unwrap();
 
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
         spender: amm2.to_string(),
-        amount: Uint128::new(100),
+        amount: Uint128::new(1_100),
         expires: None,
     };
     let _res = router
@@ -898,9 +1078,9 @@ fn token_to_token_swap() {
         .unwrap();
 
     let add_liquidity_msg = ExecuteMsg::AddLiquidity {
-        token1_amount: Uint128::new(100),
+        token1_amount: Uint128::new(1_100),
         min_liquidity: Uint128::new(100),
-        max_token2: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
         expiration: None,
     };
     router
@@ -910,7 +1090,7 @@ fn token_to_token_swap() {
             &add_liquidity_msg,
             &[Coin {
                 denom: NATIVE_TOKEN_DENOM.into(),
-                amount: Uint128::new(100),
+                amount: Uint128::new(1_100),
             }],
         )
         .unwrap();
@@ -938,16 +1118,16 @@ fn token_to_token_swap() {
 
     // ensure balances updated
     let token1_balance = token1.balance(&router, owner.clone()).unwrap();
-    assert_eq!(token1_balance, Uint128::new(4890));
+    assert_eq!(token1_balance, Uint128::new(3890));
 
     let token2_balance = token2.balance(&router, owner.clone()).unwrap();
-    assert_eq!(token2_balance, Uint128::new(4908));
+    assert_eq!(token2_balance, Uint128::new(3908));
 
     let amm1_native_balance = bank_balance(&mut router, &amm1, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(amm1_native_balance.amount, Uint128::new(91));
+    assert_eq!(amm1_native_balance.amount, Uint128::new(1_091));
 
     let amm2_native_balance = bank_balance(&mut router, &amm2, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(amm2_native_balance.amount, Uint128::new(109));
+    assert_eq!(amm2_native_balance.amount, Uint128::new(1_109));
 
     // Swap token2 for token1
     let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
@@ -972,16 +1152,16 @@ fn token_to_token_swap() {
 
     // ensure balances updated
     let token1_balance = token1.balance(&router, owner.clone()).unwrap();
-    assert_eq!(token1_balance, Uint128::new(4900));
+    assert_eq!(token1_balance, Uint128::new(3900));
 
     let token2_balance = token2.balance(&router, owner.clone()).unwrap();
-    assert_eq!(token2_balance, Uint128::new(4898));
+    assert_eq!(token2_balance, Uint128::new(3898));
 
     let amm1_native_balance = bank_balance(&mut router, &amm1, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(amm1_native_balance.amount, Uint128::new(101));
+    assert_eq!(amm1_native_balance.amount, Uint128::new(1_101));
 
     let amm2_native_balance = bank_balance(&mut router, &amm2, NATIVE_TOKEN_DENOM.to_string());
-    assert_eq!(amm2_native_balance.amount, Uint128::new(99));
+    assert_eq!(amm2_native_balance.amount, Uint128::new(1_099));
 
     // assert internal state is consistent
     let info_amm1 = get_info(&router, &amm1);
@@ -994,3 +1174,1206 @@ fn token_to_token_swap() {
     assert_eq!(info_amm2.token2_reserve, token2_balance);
     assert_eq!(info_amm2.token1_reserve, amm2_native_balance.amount);
 }
+
+#[test]
+fn swap_with_protocol_fee() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+    const IBC_TOKEN_DENOM: &str = "atom";
+
+    let owner = Addr::unchecked("owner");
+    let funds = vec![
+        Coin {
+            denom: NATIVE_TOKEN_DENOM.into(),
+            amount: Uint128::new(20000),
+        },
+        Coin {
+            denom: IBC_TOKEN_DENOM.into(),
+            amount: Uint128::new(20000),
+        },
+    ];
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &owner, funds).unwrap()
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: Some(owner.to_string()),
+        swap_fee: Decimal::percent(20),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let err = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SwapFeeTooHigh {
+            max: Decimal::percent(10),
+            provided: Decimal::percent(20)
+        },
+        err.downcast().unwrap()
+    );
+
+    // half of the 1% fee goes to the community pool
+    let msg = InstantiateMsg {
+        swap_fee: Decimal::percent(1),
+        protocol_fee: Some(ProtocolFee {
+            share: Decimal::percent(50),
+            recipient: None,
+        }),
+        ..msg
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.owner, Some(owner.to_string()));
+    assert_eq!(info.swap_fee, Decimal::percent(1));
+    assert_eq!(
+        info.protocol_fee,
+        Some(ProtocolFee {
+            share: Decimal::percent(50),
+            recipient: None,
+        })
+    );
+
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(10000),
+        min_liquidity: Uint128::new(9000),
+        max_token2: Uint128::new(10000),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &add_liquidity_msg,
+            &[
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10000),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10000),
+                },
+            ],
+        )
+        .unwrap();
+
+    let swap_msg = ExecuteMsg::Swap {
+        input_token: TokenSelect::Token1,
+        input_amount: Uint128::new(1000),
+        min_output: Uint128::new(900),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(1000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+
+    // the protocol fee is not added to the reserve
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(10995));
+    assert_eq!(info.token2_reserve, Uint128::new(9100));
+    let community_pool = Addr::unchecked(COMMUNITY_POOL);
+    let balance = bank_balance(&mut router, &community_pool, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::new(5));
+
+    // only the owner can update the fees
+    let fee_recipient = Addr::unchecked("fee_recipient");
+    let update_config_msg = ExecuteMsg::UpdateConfig {
+        owner: Some(owner.to_string()),
+        swap_fee: Decimal::percent(1),
+        protocol_fee: Some(ProtocolFee {
+            share: Decimal::percent(50),
+            recipient: Some(fee_recipient.to_string()),
+        }),
+    };
+    let err = router
+        .execute_contract(
+            Addr::unchecked("buyer"),
+            amm_addr.clone(),
+            &update_config_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    router
+        .execute_contract(owner.clone(), amm_addr.clone(), &update_config_msg, &[])
+        .unwrap();
+
+    let swap_msg = ExecuteMsg::Swap {
+        input_token: TokenSelect::Token2,
+        input_amount: Uint128::new(1000),
+        min_output: Uint128::new(1),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner,
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(1000, IBC_TOKEN_DENOM),
+        )
+        .unwrap();
+
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token2_reserve, Uint128::new(10095));
+    let balance = bank_balance(&mut router, &fee_recipient, IBC_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::new(5));
+}
+
+#[test]
+fn cw20_receive_hook() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+
+    let owner = Addr::unchecked("owner");
+    let funds = coins(2000, NATIVE_TOKEN_DENOM);
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &owner, funds).unwrap()
+    });
+
+    let token1 = create_cw20(
+        &mut router,
+        &owner,
+        "token1".to_string(),
+        "TOKENONE".to_string(),
+        Uint128::new(5000),
+    );
+    let token2 = create_cw20(
+        &mut router,
+        &owner,
+        "token2".to_string(),
+        "TOKENTWO".to_string(),
+        Uint128::new(5000),
+    );
+    let amm_addr = create_amm(&mut router, &owner, &token2, NATIVE_TOKEN_DENOM.to_string());
+
+    // Error: native tokens cannot be sent with the hook
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: amm_addr.to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&ReceiveMsg::AddLiquidity {
+            token1_amount: Uint128::new(100),
+            min_liquidity: Uint128::new(100),
+            max_token2: Uint128::new(100),
+            expiration: None,
+        })
+        .unwrap(),
+    };
+    let err = router
+        .execute_contract(owner.clone(), token2.addr(), &send_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::InsufficientFunds {}, err.downcast().unwrap());
+
+    let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: amm_addr.to_string(),
+        amount: Uint128::new(1_100),
+        expires: None,
+    };
+    router
+        .execute_contract(owner.clone(), token2.addr(), &allowance_msg, &[])
+        .unwrap();
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(1_100),
+        min_liquidity: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &add_liquidity_msg,
+            &coins(1_100, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+
+    // Error: token not in the pool
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: amm_addr.to_string(),
+        amount: Uint128::new(10),
+        msg: to_binary(&ReceiveMsg::Swap {
+            min_output: Uint128::new(9),
+            recipient: Some("buyer".to_string()),
+            expiration: None,
+        })
+        .unwrap(),
+    };
+    let err = router
+        .execute_contract(owner.clone(), token1.addr(), &send_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidCw20Token {
+            token: token1.addr().to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // swap without allowance
+    router
+        .execute_contract(owner.clone(), token2.addr(), &send_msg, &[])
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_091));
+    assert_eq!(info.token2_reserve, Uint128::new(1_110));
+    let buyer = Addr::unchecked("buyer");
+    let balance = bank_balance(&mut router, &buyer, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::new(9));
+
+    // cw20 pair, token1 sent with the hook and token2 allowed
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Cw20(token1.addr()),
+        token2_denom: Denom::Cw20(token2.addr()),
+        lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+    let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: amm_addr.to_string(),
+        amount: Uint128::new(1_100),
+        expires: None,
+    };
+    router
+        .execute_contract(owner.clone(), token2.addr(), &allowance_msg, &[])
+        .unwrap();
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: amm_addr.to_string(),
+        amount: Uint128::new(1_100),
+        msg: to_binary(&ReceiveMsg::AddLiquidity {
+            token1_amount: Uint128::new(1_100),
+            min_liquidity: Uint128::new(100),
+            max_token2: Uint128::new(1_100),
+            expiration: None,
+        })
+        .unwrap(),
+    };
+    router
+        .execute_contract(owner.clone(), token1.addr(), &send_msg, &[])
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_100));
+    assert_eq!(info.token2_reserve, Uint128::new(1_100));
+    assert_eq!(info.lp_token_supply, Uint128::new(1_100));
+
+    // token2 sent with the hook, the part not required is refunded
+    let allowance_msg = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: amm_addr.to_string(),
+        amount: Uint128::new(50),
+        expires: None,
+    };
+    router
+        .execute_contract(owner.clone(), token1.addr(), &allowance_msg, &[])
+        .unwrap();
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: amm_addr.to_string(),
+        amount: Uint128::new(60),
+        msg: to_binary(&ReceiveMsg::AddLiquidity {
+            token1_amount: Uint128::new(50),
+            min_liquidity: Uint128::new(50),
+            max_token2: Uint128::new(60),
+            expiration: None,
+        })
+        .unwrap(),
+    };
+    router
+        .execute_contract(owner.clone(), token2.addr(), &send_msg, &[])
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_150));
+    assert_eq!(info.token2_reserve, Uint128::new(1_151));
+    assert_eq!(info.lp_token_supply, Uint128::new(1_150));
+    let token2_balance = token2.balance(&router, amm_addr.clone()).unwrap();
+    assert_eq!(token2_balance, Uint128::new(1_151));
+
+    // zap in token1 sent with the hook, 15 are swapped for 14 token2
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: amm_addr.to_string(),
+        amount: Uint128::new(30),
+        msg: to_binary(&ReceiveMsg::ZapIn {
+            min_liquidity: Uint128::new(14),
+            expiration: None,
+        })
+        .unwrap(),
+    };
+    router
+        .execute_contract(owner.clone(), token1.addr(), &send_msg, &[])
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_180));
+    assert_eq!(info.token2_reserve, Uint128::new(1_151));
+    assert_eq!(info.lp_token_supply, Uint128::new(1_164));
+}
+
+#[test]
+fn twap() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+    const IBC_TOKEN_DENOM: &str = "atom";
+
+    let owner = Addr::unchecked("owner");
+    let funds = vec![
+        Coin {
+            denom: NATIVE_TOKEN_DENOM.into(),
+            amount: Uint128::new(2000),
+        },
+        Coin {
+            denom: IBC_TOKEN_DENOM.into(),
+            amount: Uint128::new(2000),
+        },
+    ];
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &owner, funds).unwrap()
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(1_100),
+        min_liquidity: Uint128::new(100),
+        max_token2: Uint128::new(1_100),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &add_liquidity_msg,
+            &[
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(1_100),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(1_100),
+                },
+            ],
+        )
+        .unwrap();
+
+    // price of 1 for 100 seconds, then 1001/1210 for 100 seconds
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+    let swap_msg = ExecuteMsg::Swap {
+        input_token: TokenSelect::Token1,
+        input_amount: Uint128::new(110),
+        min_output: Uint128::new(99),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner,
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(110, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_210));
+    assert_eq!(info.token2_reserve, Uint128::new(1_001));
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let twap = |router: &SignApp, window| -> StdResult<TwapResponse> {
+        router
+            .wrap()
+            .query_wasm_smart(&amm_addr, &QueryMsg::Twap { window })
+    };
+    let res = twap(&router, 200).unwrap();
+    assert_eq!(
+        res.token1_price,
+        Decimal256::from_str("0.913636363636363636").unwrap()
+    );
+    assert_eq!(
+        res.token2_price,
+        Decimal256::from_str("1.104395604395604395").unwrap()
+    );
+
+    // window starting between two snapshots
+    let res = twap(&router, 150).unwrap();
+    assert_eq!(
+        res.token1_price,
+        Decimal256::from_str("0.884848484848484848").unwrap()
+    );
+    let res = twap(&router, 50).unwrap();
+    assert_eq!(
+        res.token1_price,
+        Decimal256::from_str("0.827272727272727272").unwrap()
+    );
+
+    twap(&router, 0).unwrap_err();
+    twap(&router, 86_401).unwrap_err();
+    // Error: pool is younger than the window
+    twap(&router, 300).unwrap_err();
+}
+
+#[test]
+fn simulate_and_swap_exact_output() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+    const IBC_TOKEN_DENOM: &str = "atom";
+
+    let owner = Addr::unchecked("owner");
+    let funds = vec![
+        Coin {
+            denom: NATIVE_TOKEN_DENOM.into(),
+            amount: Uint128::new(20_000),
+        },
+        Coin {
+            denom: IBC_TOKEN_DENOM.into(),
+            amount: Uint128::new(20_000),
+        },
+    ];
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &owner, funds).unwrap()
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(10_000),
+        min_liquidity: Uint128::new(9_000),
+        max_token2: Uint128::new(10_000),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &add_liquidity_msg,
+            &[
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10_000),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10_000),
+                },
+            ],
+        )
+        .unwrap();
+
+    let simulation: SimulateSwapResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &amm_addr,
+            &QueryMsg::SimulateSwap {
+                input_token: TokenSelect::Token1,
+                input_amount: Uint128::new(1_000),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        simulation,
+        SimulateSwapResponse {
+            output_amount: Uint128::new(906),
+            fee_amount: Uint128::new(3),
+            spot_price: Decimal::one(),
+            price_impact: Decimal::from_str("0.090661089388014913").unwrap(),
+        }
+    );
+
+    let reverse_simulation: ReverseSimulateSwapResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &amm_addr,
+            &QueryMsg::ReverseSimulateSwap {
+                input_token: TokenSelect::Token1,
+                output_amount: Uint128::new(906),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        reverse_simulation,
+        ReverseSimulateSwapResponse {
+            input_amount: Uint128::new(1_000),
+            fee_amount: Uint128::new(3),
+            spot_price: Decimal::one(),
+            price_impact: Decimal::from_str("0.090661089388014913").unwrap(),
+        }
+    );
+
+    // Error: output exceeds the reserve
+    router
+        .wrap()
+        .query_wasm_smart::<ReverseSimulateSwapResponse>(
+            &amm_addr,
+            &QueryMsg::ReverseSimulateSwap {
+                input_token: TokenSelect::Token1,
+                output_amount: Uint128::new(10_000),
+            },
+        )
+        .unwrap_err();
+
+    // Error: max input too low
+    let swap_msg = ExecuteMsg::SwapExactOutput {
+        input_token: TokenSelect::Token1,
+        output_amount: Uint128::new(906),
+        max_input: Uint128::new(999),
+        recipient: None,
+        expiration: None,
+    };
+    let err = router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(999, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SwapMaxError {
+            max: Uint128::new(999),
+            required: Uint128::new(1_000)
+        },
+        err.downcast().unwrap()
+    );
+
+    // Error: funds don't match the max input
+    let swap_msg = ExecuteMsg::SwapExactOutput {
+        input_token: TokenSelect::Token1,
+        output_amount: Uint128::new(906),
+        max_input: Uint128::new(1_100),
+        recipient: None,
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(1_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap_err();
+
+    // The input not required is refunded
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(1_100, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(11_000));
+    assert_eq!(info.token2_reserve, Uint128::new(9_094));
+    let owner_balance = bank_balance(&mut router, &owner, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(owner_balance.amount, Uint128::new(9_000));
+    let owner_balance = bank_balance(&mut router, &owner, IBC_TOKEN_DENOM.to_string());
+    assert_eq!(owner_balance.amount, Uint128::new(10_906));
+}
+
+#[test]
+fn stableswap() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "usign";
+    const IBC_TOKEN_DENOM: &str = "wusign";
+
+    let owner = Addr::unchecked("owner");
+    let funds = vec![
+        Coin {
+            denom: NATIVE_TOKEN_DENOM.into(),
+            amount: Uint128::new(3_000_000),
+        },
+        Coin {
+            denom: IBC_TOKEN_DENOM.into(),
+            amount: Uint128::new(3_000_000),
+        },
+    ];
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &owner, funds).unwrap()
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: Some(owner.to_string()),
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: Some(PoolType::Stableswap { amp: 0 }),
+        lp_token: None,
+    };
+
+    // Error: invalid amplification
+    let err = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAmp {
+            amp: 0,
+            max: 1_000_000
+        },
+        err.downcast().unwrap()
+    );
+
+    let msg = InstantiateMsg {
+        pool_type: Some(PoolType::Stableswap { amp: 100 }),
+        ..msg
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+    let product_amm_addr = router
+        .instantiate_contract(
+            amm_id,
+            owner.clone(),
+            &InstantiateMsg {
+                pool_type: None,
+                ..msg
+            },
+            &[],
+            "amm",
+            None,
+        )
+        .unwrap();
+
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(1_000_000),
+        min_liquidity: Uint128::new(999_000),
+        max_token2: Uint128::new(1_000_000),
+        expiration: None,
+    };
+    for addr in [&amm_addr, &product_amm_addr] {
+        router
+            .execute_contract(
+                owner.clone(),
+                addr.clone(),
+                &add_liquidity_msg,
+                &[
+                    Coin {
+                        denom: NATIVE_TOKEN_DENOM.into(),
+                        amount: Uint128::new(1_000_000),
+                    },
+                    Coin {
+                        denom: IBC_TOKEN_DENOM.into(),
+                        amount: Uint128::new(1_000_000),
+                    },
+                ],
+            )
+            .unwrap();
+    }
+    assert_eq!(get_info(&router, &amm_addr).amp, Some(100));
+    assert_eq!(get_info(&router, &product_amm_addr).amp, None);
+
+    // Much less slippage than the constant product pool
+    let simulate = |router: &SignApp, addr: &Addr| -> SimulateSwapResponse {
+        router
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::SimulateSwap {
+                    input_token: TokenSelect::Token1,
+                    input_amount: Uint128::new(100_000),
+                },
+            )
+            .unwrap()
+    };
+    let stable = simulate(&router, &amm_addr);
+    let product = simulate(&router, &product_amm_addr);
+    assert_eq!(product.output_amount, Uint128::new(90_661));
+    assert!(stable.output_amount > Uint128::new(99_600));
+    assert_eq!(stable.spot_price, Decimal::one());
+    assert!(stable.price_impact < Decimal::permille(1));
+
+    let swap_msg = ExecuteMsg::Swap {
+        input_token: TokenSelect::Token1,
+        input_amount: Uint128::new(100_000),
+        min_output: stable.output_amount,
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(100_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_100_000));
+    assert_eq!(
+        info.token2_reserve,
+        Uint128::new(1_000_000) - stable.output_amount
+    );
+
+    // The exact output swap back restores the reserves, up to the fees
+    let reverse: ReverseSimulateSwapResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &amm_addr,
+            &QueryMsg::ReverseSimulateSwap {
+                input_token: TokenSelect::Token2,
+                output_amount: Uint128::new(100_000),
+            },
+        )
+        .unwrap();
+    assert!(reverse.input_amount > stable.output_amount);
+    let swap_msg = ExecuteMsg::SwapExactOutput {
+        input_token: TokenSelect::Token2,
+        output_amount: Uint128::new(100_000),
+        max_input: reverse.input_amount,
+        recipient: None,
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &swap_msg,
+            &coins(reverse.input_amount.u128(), IBC_TOKEN_DENOM),
+        )
+        .unwrap();
+    // At least the output is bought, rounding up the input
+    let info = get_info(&router, &amm_addr);
+    assert!(info.token1_reserve <= Uint128::new(1_000_000));
+    assert!(info.token1_reserve >= Uint128::new(999_999));
+    assert!(info.token2_reserve > Uint128::new(1_000_000));
+
+    // Error: only the owner can ramp the amplification
+    let ramp_msg = ExecuteMsg::RampAmp {
+        future_amp: 1_000,
+        future_time: router.block_info().time.seconds() + 2 * 86_400,
+    };
+    let err = router
+        .execute_contract(Addr::unchecked("buyer"), amm_addr.clone(), &ramp_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // Error: the pool was created less than a day ago
+    router
+        .execute_contract(owner.clone(), amm_addr.clone(), &ramp_msg, &[])
+        .unwrap_err();
+
+    // Error: constant product pools have no amplification
+    let err = router
+        .execute_contract(owner.clone(), product_amm_addr, &ramp_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NotStableswap {}, err.downcast().unwrap());
+
+    router.update_block(|block| block.time = block.time.plus_seconds(86_400));
+    // Error: amplification changed by more than 10 times
+    let err = router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::RampAmp {
+                future_amp: 1_001,
+                future_time: router.block_info().time.seconds() + 2 * 86_400,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAmpRamp {
+            reason: "the amplification can change by a factor of 10 at most".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // Ramp from 100 to 1000 over two days
+    let ramp_msg = ExecuteMsg::RampAmp {
+        future_amp: 1_000,
+        future_time: router.block_info().time.seconds() + 2 * 86_400,
+    };
+    router
+        .execute_contract(owner.clone(), amm_addr.clone(), &ramp_msg, &[])
+        .unwrap();
+    router.update_block(|block| block.time = block.time.plus_seconds(86_400));
+    assert_eq!(get_info(&router, &amm_addr).amp, Some(550));
+
+    router
+        .execute_contract(
+            owner.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::StopRampAmp {},
+            &[],
+        )
+        .unwrap();
+    router.update_block(|block| block.time = block.time.plus_seconds(86_400));
+    assert_eq!(get_info(&router, &amm_addr).amp, Some(550));
+
+    // The prices of the invariant are accumulated, close to 1 with token1 scarcer after fees
+    let twap: TwapResponse = router
+        .wrap()
+        .query_wasm_smart(&amm_addr, &QueryMsg::Twap { window: 86_400 })
+        .unwrap();
+    assert!(twap.token1_price > Decimal256::one());
+    assert!(twap.token1_price < Decimal256::percent(101));
+    assert!(twap.token2_price < Decimal256::one());
+    assert!(twap.token2_price > Decimal256::percent(99));
+}
+
+#[test]
+fn zap_in_and_out() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+    const IBC_TOKEN_DENOM: &str = "atom";
+
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    router.borrow_mut().init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &owner,
+                vec![
+                    Coin {
+                        denom: NATIVE_TOKEN_DENOM.into(),
+                        amount: Uint128::new(10_000),
+                    },
+                    Coin {
+                        denom: IBC_TOKEN_DENOM.into(),
+                        amount: Uint128::new(10_000),
+                    },
+                ],
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &user, coins(1_000, NATIVE_TOKEN_DENOM))
+            .unwrap()
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, owner.clone(), &msg, &[], "amm", None)
+        .unwrap();
+
+    let zap_in_msg = ExecuteMsg::ZapIn {
+        input_token: TokenSelect::Token1,
+        amount: Uint128::new(1_000),
+        min_liquidity: Uint128::new(486),
+        expiration: None,
+    };
+    // Error: no liquidity to swap with
+    router
+        .execute_contract(
+            user.clone(),
+            amm_addr.clone(),
+            &zap_in_msg,
+            &coins(1_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap_err();
+
+    let add_liquidity_msg = ExecuteMsg::AddLiquidity {
+        token1_amount: Uint128::new(10_000),
+        min_liquidity: Uint128::new(9_000),
+        max_token2: Uint128::new(10_000),
+        expiration: None,
+    };
+    router
+        .execute_contract(
+            owner,
+            amm_addr.clone(),
+            &add_liquidity_msg,
+            &[
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10_000),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(10_000),
+                },
+            ],
+        )
+        .unwrap();
+
+    // Error: min liquidity too high
+    let err = router
+        .execute_contract(
+            user.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::ZapIn {
+                input_token: TokenSelect::Token1,
+                amount: Uint128::new(1_000),
+                min_liquidity: Uint128::new(487),
+                expiration: None,
+            },
+            &coins(1_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MinLiquidityError {
+            min_liquidity: Uint128::new(487),
+            liquidity_available: Uint128::new(486)
+        },
+        err.downcast().unwrap()
+    );
+
+    // 489 are swapped for 464, the rest is deposited with them
+    let res = router
+        .execute_contract(
+            user.clone(),
+            amm_addr.clone(),
+            &zap_in_msg,
+            &coins(1_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+    let event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "swap_amount" && a.value == "489"));
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(11_000));
+    assert_eq!(info.token2_reserve, Uint128::new(10_000));
+    let lp_token = Cw20Contract(Addr::unchecked(info.lp_token_address));
+    assert_eq!(
+        lp_token.balance(&router, user.clone()).unwrap(),
+        Uint128::new(486)
+    );
+
+    // The liquidity is burned from an allowance, like RemoveLiquidity
+    router
+        .execute_contract(
+            user.clone(),
+            lp_token.addr(),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: amm_addr.to_string(),
+                amount: Uint128::new(486),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Error: min output too high
+    let err = router
+        .execute_contract(
+            user.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::ZapOut {
+                amount: Uint128::new(486),
+                output_token: TokenSelect::Token1,
+                min_output: Uint128::new(994),
+                expiration: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SwapMinError {
+            min: Uint128::new(994),
+            available: Uint128::new(993)
+        },
+        err.downcast().unwrap()
+    );
+
+    // 509 token1 removed, 463 token2 removed and swapped for 484 token1
+    router
+        .execute_contract(
+            user.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::ZapOut {
+                amount: Uint128::new(486),
+                output_token: TokenSelect::Token1,
+                min_output: Uint128::new(993),
+                expiration: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(10_007));
+    assert_eq!(info.token2_reserve, Uint128::new(10_000));
+    assert_eq!(info.lp_token_supply, Uint128::new(10_000));
+    let balance = bank_balance(&mut router, &user, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::new(993));
+    let balance = bank_balance(&mut router, &user, IBC_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::zero());
+}
+
+#[test]
+fn minimum_liquidity_locked() {
+    let mut router = mock_app();
+
+    const NATIVE_TOKEN_DENOM: &str = "juno";
+    const IBC_TOKEN_DENOM: &str = "atom";
+
+    let attacker = Addr::unchecked("attacker");
+    let victim = Addr::unchecked("victim");
+    router.borrow_mut().init_modules(|router, _, storage| {
+        for addr in [&attacker, &victim] {
+            let funds = vec![
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(2_000_000),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(2_000_000),
+                },
+            ];
+            router.bank.init_balance(storage, addr, funds).unwrap();
+        }
+    });
+
+    let amm_id = router.store_code(contract_amm());
+    let lp_token_id = router.store_code(contract_cw20());
+    let msg = InstantiateMsg {
+        token1_denom: Denom::Native(NATIVE_TOKEN_DENOM.into()),
+        token2_denom: Denom::Native(IBC_TOKEN_DENOM.into()),
+        lp_token_code_id: lp_token_id,
+        owner: None,
+        swap_fee: Decimal::permille(3),
+        protocol_fee: None,
+        pool_type: None,
+        lp_token: None,
+    };
+    let amm_addr = router
+        .instantiate_contract(amm_id, attacker.clone(), &msg, &[], "amm", None)
+        .unwrap();
+    let lp_token = Cw20Contract(Addr::unchecked(
+        get_info(&router, &amm_addr).lp_token_address,
+    ));
+
+    let add_liquidity = |router: &mut SignApp, sender: &Addr, token1: u128, token2: u128| {
+        router.execute_contract(
+            sender.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::AddLiquidity {
+                token1_amount: Uint128::new(token1),
+                min_liquidity: Uint128::zero(),
+                max_token2: Uint128::new(token2),
+                expiration: None,
+            },
+            &[
+                Coin {
+                    denom: NATIVE_TOKEN_DENOM.into(),
+                    amount: Uint128::new(token1),
+                },
+                Coin {
+                    denom: IBC_TOKEN_DENOM.into(),
+                    amount: Uint128::new(token2),
+                },
+            ],
+        )
+    };
+
+    // Error: a single unit of liquidity cannot be minted on the first deposit
+    let err = add_liquidity(&mut router, &attacker, 1, 1).unwrap_err();
+    assert_eq!(
+        ContractError::InitialLiquidityTooLow {
+            minimum: Uint128::new(1_001),
+            provided: Uint128::new(1)
+        },
+        err.downcast().unwrap()
+    );
+
+    // The attacker owns a single unit, the minimum liquidity is locked in the pool
+    add_liquidity(&mut router, &attacker, 1_001, 1_001).unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.lp_token_supply, Uint128::new(1_001));
+    let balance = lp_token.balance(&router, attacker.clone()).unwrap();
+    assert_eq!(balance, Uint128::new(1));
+    let balance = lp_token.balance(&router, amm_addr.clone()).unwrap();
+    assert_eq!(balance, Uint128::new(1_000));
+
+    // The attacker inflates the token1 reserve with a swap, 999 token2 out
+    router
+        .execute_contract(
+            attacker.clone(),
+            amm_addr.clone(),
+            &ExecuteMsg::Swap {
+                input_token: TokenSelect::Token1,
+                input_amount: Uint128::new(1_000_000),
+                min_output: Uint128::zero(),
+                expiration: None,
+            },
+            &coins(1_000_000, NATIVE_TOKEN_DENOM),
+        )
+        .unwrap();
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_001_001));
+    assert_eq!(info.token2_reserve, Uint128::new(2));
+
+    // Error: the deposit would be rounded down to no liquidity and donated to the pool
+    let err = add_liquidity(&mut router, &victim, 999, 1).unwrap_err();
+    assert_eq!(ContractError::ZeroLiquidity {}, err.downcast().unwrap());
+    let balance = bank_balance(&mut router, &victim, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(balance.amount, Uint128::new(2_000_000));
+
+    // The victim loses less than a unit of liquidity to the rounding, 9 for 10_000 token1
+    add_liquidity(&mut router, &victim, 10_000, 1).unwrap();
+    let balance = lp_token.balance(&router, victim.clone()).unwrap();
+    assert_eq!(balance, Uint128::new(9));
+
+    // The attacker owns a unit of the 1_010 minted, most of the inflated reserve is locked
+    let info = get_info(&router, &amm_addr);
+    assert_eq!(info.token1_reserve, Uint128::new(1_011_001));
+    assert_eq!(info.lp_token_supply, Uint128::new(1_010));
+    let balance = lp_token.balance(&router, amm_addr.clone()).unwrap();
+    assert_eq!(balance, Uint128::new(1_000));
+}
@@ -2,4 +2,5 @@ pub mod contract;
 pub mod error;
 mod integration_test;
 pub mod msg;
+pub mod stableswap;
 pub mod state;
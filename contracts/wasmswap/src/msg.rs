@@ -1,15 +1,59 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Decimal256, Uint128};
 
-use cw20::{Denom, Expiration};
+use cw20::{Cw20ReceiveMsg, Denom, Expiration};
+use cw20_base::msg::InstantiateMarketingInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub token1_denom: Denom,
     pub token2_denom: Denom,
     pub lp_token_code_id: u64,
+    /// Can update the fees, the fees are fixed without an owner
+    pub owner: Option<String>,
+    /// Fee taken from the swap input, 0.003 for 0.3%
+    pub swap_fee: Decimal,
+    pub protocol_fee: Option<ProtocolFee>,
+    /// Constant product when None
+    pub pool_type: Option<PoolType>,
+    /// Derived from the pair when None
+    pub lp_token: Option<LpTokenInfo>,
+}
+
+/// Metadata of the lp token, the fields not set are derived from the pair
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpTokenInfo {
+    /// "<TOKEN1>-<TOKEN2> LP" by default, with the symbol of cw20 tokens and the denom of
+    /// native tokens
+    pub name: Option<String>,
+    /// "<TOKEN1>-<TOKEN2>" by default, with up to 5 letters of each token
+    pub symbol: Option<String>,
+    /// 6 by default
+    pub decimals: Option<u8>,
+    /// Described as the lp token of the pair by default, updated by the owner
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+/// Invariant used to price the swaps
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolType {
+    /// x * y = k
+    ConstantProduct,
+    /// Curve stableswap invariant for tokens of the same value and decimals, the amplification
+    /// `amp` is between 1 and 1_000_000
+    Stableswap { amp: u64 },
+}
+
+/// Part of the swap fee sent to the protocol instead of the liquidity providers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProtocolFee {
+    /// Part of the swap fee, 0.2 for a fifth
+    pub share: Decimal,
+    /// Native fees go to the community pool without a recipient, cw20 fees stay in the pool
+    pub recipient: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,6 +98,68 @@ pub enum ExecuteMsg {
         min_token: Uint128,
         expiration: Option<Expiration>,
     },
+    /// Replace the owner and fees, can only be called by the owner
+    UpdateConfig {
+        owner: Option<String>,
+        swap_fee: Decimal,
+        protocol_fee: Option<ProtocolFee>,
+    },
+    /// Tokens of the pool sent with `Send` and a `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+    /// Swap for at least `output_amount`, paying up to `max_input`. Native input is sent as
+    /// `max_input` and the part not required is refunded.
+    SwapExactOutput {
+        input_token: TokenSelect,
+        output_amount: Uint128,
+        max_input: Uint128,
+        recipient: Option<String>,
+        expiration: Option<Expiration>,
+    },
+    /// Ramp the amplification of a stableswap pool linearly to `future_amp` at `future_time`,
+    /// in seconds. Can only be called by the owner, at most once a day, by a factor of up to 10
+    /// and over a day at least.
+    RampAmp { future_amp: u64, future_time: u64 },
+    /// Stop the ramp at the current amplification, can only be called by the owner
+    StopRampAmp {},
+    /// Add liquidity with `amount` of a single token, the part swapped for the other token is
+    /// chosen to deposit both in the ratio of the reserves
+    ZapIn {
+        input_token: TokenSelect,
+        amount: Uint128,
+        min_liquidity: Uint128,
+        expiration: Option<Expiration>,
+    },
+    /// Remove `amount` of liquidity and swap the other token for `output_token`
+    ZapOut {
+        amount: Uint128,
+        output_token: TokenSelect,
+        min_output: Uint128,
+        expiration: Option<Expiration>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Swap the tokens sent for the other token, sent to `recipient` or the sender
+    Swap {
+        min_output: Uint128,
+        recipient: Option<String>,
+        expiration: Option<Expiration>,
+    },
+    /// Add liquidity with the tokens sent as `token1_amount` or `max_token2`. The other token
+    /// must be a cw20 allowed to the pool, native tokens cannot be sent with the hook.
+    AddLiquidity {
+        token1_amount: Uint128,
+        min_liquidity: Uint128,
+        max_token2: Uint128,
+        expiration: Option<Expiration>,
+    },
+    /// Add liquidity with the tokens sent only, see `ExecuteMsg::ZapIn`
+    ZapIn {
+        min_liquidity: Uint128,
+        expiration: Option<Expiration>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -70,6 +176,20 @@ pub enum QueryMsg {
     Token2ForToken1Price {
         token2_amount: Uint128,
     },
+    /// Average prices over the last `window` seconds, up to a day
+    Twap {
+        window: u64,
+    },
+    /// Output of swapping `input_amount`
+    SimulateSwap {
+        input_token: TokenSelect,
+        input_amount: Uint128,
+    },
+    /// Input required to receive `output_amount`
+    ReverseSimulateSwap {
+        input_token: TokenSelect,
+        output_amount: Uint128,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -80,6 +200,11 @@ pub struct InfoResponse {
     pub token2_denom: Denom,
     pub lp_token_supply: Uint128,
     pub lp_token_address: String,
+    pub owner: Option<String>,
+    pub swap_fee: Decimal,
+    pub protocol_fee: Option<ProtocolFee>,
+    /// Current amplification of a stableswap pool, None for a constant product pool
+    pub amp: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -91,3 +216,33 @@ pub struct Token1ForToken2PriceResponse {
 pub struct Token2ForToken1PriceResponse {
     pub token1_amount: Uint128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapResponse {
+    /// Average price of token1 in token2
+    pub token1_price: Decimal256,
+    /// Average price of token2 in token1
+    pub token2_price: Decimal256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateSwapResponse {
+    pub output_amount: Uint128,
+    /// Swap fee paid on the input, including the protocol fee
+    pub fee_amount: Uint128,
+    /// Price of the input token in the output token before the swap
+    pub spot_price: Decimal,
+    /// Part of the output lost to the move of the price, the fee excluded
+    pub price_impact: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReverseSimulateSwapResponse {
+    pub input_amount: Uint128,
+    /// Swap fee paid on the input, including the protocol fee
+    pub fee_amount: Uint128,
+    /// Price of the input token in the output token before the swap
+    pub spot_price: Decimal,
+    /// Part of the output lost to the move of the price, the fee excluded
+    pub price_impact: Decimal,
+}
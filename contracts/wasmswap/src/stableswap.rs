@@ -0,0 +1,309 @@
+//! Curve stableswap invariant for two tokens,
+//! `A * n^n * (x + y) + D = A * D * n^n + D^3 / (n^n * x * y)` with n = 2.
+//! The amplification `A` flattens the curve around the balanced point, the pool behaves like a
+//! constant sum for small imbalances and like a constant product for large ones.
+
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128, Uint512};
+use std::convert::TryInto;
+
+// Number of tokens of the pool
+const N_COINS: u128 = 2;
+// The invariants converge in a few iterations, this is a safety bound
+const MAX_ITERATIONS: usize = 256;
+// Spot prices are scaled by 10^18, the precision of Decimal
+const PRICE_SCALE_FACTOR: u128 = 1_000_000_000_000_000_000;
+
+fn abs_diff(a: Uint512, b: Uint512) -> Uint512 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Invariant `D` of the reserves, the total of the reserves when balanced
+fn compute_d(amp: u64, x: Uint128, y: Uint128) -> StdResult<Uint512> {
+    let (x, y) = (Uint512::from(x), Uint512::from(y));
+    let sum = x + y;
+    if sum.is_zero() {
+        return Ok(Uint512::zero());
+    }
+    let n = Uint512::from(N_COINS);
+    let ann = Uint512::from(amp) * n * n;
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // D^3 / (n^n * x * y)
+        let d_p = d
+            .checked_mul(d)?
+            .checked_div(x * n)
+            .map_err(StdError::divide_by_zero)?
+            .checked_mul(d)?
+            .checked_div(y * n)
+            .map_err(StdError::divide_by_zero)?;
+        let d_prev = d;
+        let numerator = (ann.checked_mul(sum)? + d_p * n).checked_mul(d)?;
+        let denominator =
+            (ann - Uint512::from(1u128)).checked_mul(d)? + (n + Uint512::from(1u128)) * d_p;
+        d = numerator
+            .checked_div(denominator)
+            .map_err(StdError::divide_by_zero)?;
+        if abs_diff(d, d_prev) <= Uint512::from(1u128) {
+            return Ok(d);
+        }
+    }
+    Err(StdError::generic_err(
+        "Stableswap invariant did not converge",
+    ))
+}
+
+/// Reserve of the other token keeping the invariant `d` when one reserve is `x`
+fn compute_y(amp: u64, x: Uint128, d: Uint512) -> StdResult<Uint128> {
+    let x = Uint512::from(x);
+    let n = Uint512::from(N_COINS);
+    let ann = Uint512::from(amp) * n * n;
+
+    // y^2 + (b - D) * y = c, solved with Newton's method
+    let c = d
+        .checked_mul(d)?
+        .checked_div(x * n)
+        .map_err(StdError::divide_by_zero)?
+        .checked_mul(d)?
+        .checked_div(ann * n)
+        .map_err(StdError::divide_by_zero)?;
+    let b = x + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        let denominator = (y * n + b).checked_sub(d).map_err(StdError::overflow)?;
+        y = (y.checked_mul(y)? + c)
+            .checked_div(denominator)
+            .map_err(StdError::divide_by_zero)?;
+        if abs_diff(y, y_prev) <= Uint512::from(1u128) {
+            return Ok(y.try_into()?);
+        }
+    }
+    Err(StdError::generic_err(
+        "Stableswap invariant did not converge",
+    ))
+}
+
+/// Input left once the swap fee is taken, with the precision of the constant product pools
+fn apply_fee(input_amount: Uint128, swap_fee: Decimal, fee_scale: u128) -> Uint128 {
+    let fee = Uint128::new(fee_scale) * swap_fee;
+    input_amount.multiply_ratio(fee_scale - fee.u128(), fee_scale)
+}
+
+/// Output of swapping `input_amount`, rounded down
+pub fn get_input_price(
+    amp: u64,
+    input_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+    fee_scale: u128,
+) -> StdResult<Uint128> {
+    if input_reserve.is_zero() || output_reserve.is_zero() {
+        return Err(StdError::generic_err("No liquidity"));
+    };
+
+    let d = compute_d(amp, input_reserve, output_reserve)?;
+    let new_input_reserve = input_reserve
+        .checked_add(apply_fee(input_amount, swap_fee, fee_scale))
+        .map_err(StdError::overflow)?;
+    let new_output_reserve = compute_y(amp, new_input_reserve, d)?;
+    // One unit is kept by the pool against the rounding of the invariant
+    Ok(output_reserve
+        .saturating_sub(new_output_reserve)
+        .saturating_sub(Uint128::new(1)))
+}
+
+/// Input required to buy `output_amount`, rounded up
+pub fn get_output_price(
+    amp: u64,
+    output_amount: Uint128,
+    input_reserve: Uint128,
+    output_reserve: Uint128,
+    swap_fee: Decimal,
+    fee_scale: u128,
+) -> StdResult<Uint128> {
+    if input_reserve.is_zero() || output_reserve.is_zero() {
+        return Err(StdError::generic_err("No liquidity"));
+    };
+    if output_amount >= output_reserve {
+        return Err(StdError::generic_err("Output exceeds the reserve"));
+    }
+
+    let d = compute_d(amp, input_reserve, output_reserve)?;
+    // Output increased by the unit kept by the pool on swaps
+    let new_output_reserve = (output_reserve - output_amount).saturating_sub(Uint128::new(1));
+    if new_output_reserve.is_zero() {
+        return Err(StdError::generic_err("Output exceeds the reserve"));
+    }
+    let new_input_reserve = compute_y(amp, new_output_reserve, d)?;
+    let input_after_fee = new_input_reserve
+        .saturating_sub(input_reserve)
+        .checked_add(Uint128::new(1))
+        .map_err(StdError::overflow)?;
+
+    // Gross up the input by the fee, rounded up
+    let fee = Uint128::new(fee_scale) * swap_fee;
+    let denominator = Uint512::from(fee_scale - fee.u128());
+    let numerator =
+        Uint512::from(input_after_fee.full_mul(fee_scale)) + denominator - Uint512::from(1u128);
+    Ok((numerator / denominator).try_into()?)
+}
+
+/// Price of the input token in the output token, the marginal rate of the invariant
+pub fn spot_price(amp: u64, input_reserve: Uint128, output_reserve: Uint128) -> StdResult<Decimal> {
+    if input_reserve.is_zero() || output_reserve.is_zero() {
+        return Err(StdError::generic_err("No liquidity"));
+    };
+    let d = compute_d(amp, input_reserve, output_reserve)?;
+    let (x, y) = (Uint512::from(input_reserve), Uint512::from(output_reserve));
+    let n = Uint512::from(N_COINS);
+    let ann = Uint512::from(amp) * n * n;
+
+    // (Ann + D^3 / (4 x^2 y)) / (Ann + D^3 / (4 x y^2)), both terms multiplied by 4xy
+    let ann_term = ann.checked_mul(n * n)?.checked_mul(x)?.checked_mul(y)?;
+    let d_cubed = d.checked_mul(d)?.checked_mul(d)?;
+    let numerator = ann_term + d_cubed / x;
+    let denominator = ann_term + d_cubed / y;
+    let price: Uint128 = numerator
+        .checked_mul(Uint512::from(PRICE_SCALE_FACTOR))?
+        .checked_div(denominator)
+        .map_err(StdError::divide_by_zero)?
+        .try_into()?;
+    Ok(Decimal::new(price))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE_SCALE_FACTOR: u128 = 1_000_000;
+
+    #[test]
+    fn test_compute_d() {
+        // Balanced reserves, D is their sum
+        let d = compute_d(100, Uint128::new(1_000_000), Uint128::new(1_000_000)).unwrap();
+        assert_eq!(d, Uint512::from(2_000_000u128));
+
+        // Imbalanced reserves, D is between the constant product and the constant sum
+        let d = compute_d(100, Uint128::new(1_500_000), Uint128::new(500_000)).unwrap();
+        assert!(d < Uint512::from(2_000_000u128));
+        assert!(d > Uint512::from(2 * 866_025u128));
+
+        assert_eq!(
+            compute_d(100, Uint128::zero(), Uint128::zero()).unwrap(),
+            Uint512::zero()
+        );
+    }
+
+    #[test]
+    fn test_get_input_price() {
+        let (reserve, fee) = (Uint128::new(1_000_000_000), Decimal::permille(3));
+
+        // Close to 1:1 with a high amplification, minus the fee
+        let output = get_input_price(
+            100,
+            Uint128::new(1_000_000),
+            reserve,
+            reserve,
+            fee,
+            FEE_SCALE_FACTOR,
+        )
+        .unwrap();
+        assert!(output > Uint128::new(996_900) && output < Uint128::new(997_000));
+
+        // Much less slippage than the constant product for a tenth of the reserve
+        let output = get_input_price(
+            100,
+            Uint128::new(100_000_000),
+            reserve,
+            reserve,
+            Decimal::zero(),
+            FEE_SCALE_FACTOR,
+        )
+        .unwrap();
+        assert!(output > Uint128::new(99_900_000));
+
+        // A low amplification gets closer to the constant product, 90_909_090
+        let output = get_input_price(
+            1,
+            Uint128::new(100_000_000),
+            reserve,
+            reserve,
+            Decimal::zero(),
+            FEE_SCALE_FACTOR,
+        )
+        .unwrap();
+        assert!(output > Uint128::new(90_909_090) && output < Uint128::new(99_000_000));
+
+        // Error: no liquidity
+        let err = get_input_price(
+            100,
+            Uint128::new(1),
+            Uint128::zero(),
+            reserve,
+            fee,
+            FEE_SCALE_FACTOR,
+        )
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("No liquidity"));
+    }
+
+    #[test]
+    fn test_get_output_price() {
+        let fee = Decimal::permille(3);
+        let (input_reserve, output_reserve) = (Uint128::new(1_234_567), Uint128::new(987_654));
+
+        // The input always buys the output
+        for output in (1..980_000u128).step_by(9_999) {
+            let input = get_output_price(
+                85,
+                Uint128::new(output),
+                input_reserve,
+                output_reserve,
+                fee,
+                FEE_SCALE_FACTOR,
+            )
+            .unwrap();
+            let bought = get_input_price(
+                85,
+                input,
+                input_reserve,
+                output_reserve,
+                fee,
+                FEE_SCALE_FACTOR,
+            )
+            .unwrap();
+            assert!(bought >= Uint128::new(output));
+        }
+
+        // Error: output takes the whole reserve
+        let err = get_output_price(
+            85,
+            output_reserve,
+            input_reserve,
+            output_reserve,
+            fee,
+            FEE_SCALE_FACTOR,
+        )
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("Output exceeds the reserve"));
+    }
+
+    #[test]
+    fn test_spot_price() {
+        let price = spot_price(100, Uint128::new(1_000_000), Uint128::new(1_000_000)).unwrap();
+        assert_eq!(price, Decimal::one());
+
+        // The token in excess is cheaper, but much less than in a constant product pool
+        let price = spot_price(100, Uint128::new(1_500_000), Uint128::new(500_000)).unwrap();
+        assert!(price < Decimal::one() && price > Decimal::percent(90));
+        let reverse = spot_price(100, Uint128::new(500_000), Uint128::new(1_500_000)).unwrap();
+        assert!(reverse > Decimal::one());
+    }
+}
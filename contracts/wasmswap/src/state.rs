@@ -1,9 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128, Uint256};
 use cw20::Denom;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 pub const LP_TOKEN: Item<Addr> = Item::new("lp_token");
 
@@ -15,3 +15,45 @@ pub struct Token {
 
 pub const TOKEN1: Item<Token> = Item::new("token1");
 pub const TOKEN2: Item<Token> = Item::new("token2");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Option<Addr>,
+    pub swap_fee: Decimal,
+    /// Part of the swap fee sent to the protocol
+    pub protocol_fee_share: Decimal,
+    /// Community pool when None
+    pub protocol_fee_recipient: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Prices weighted by the seconds they lasted, updated before every reserve change
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CumulativePrices {
+    /// Price of token1 in token2, scaled by 10^18
+    pub price1_cumulative: Uint256,
+    /// Price of token2 in token1, scaled by 10^18
+    pub price2_cumulative: Uint256,
+    /// Block time of the last update, in seconds
+    pub last_updated: u64,
+}
+
+pub const CUMULATIVE_PRICES: Item<CumulativePrices> = Item::new("cumulative_prices");
+
+/// Snapshots of the cumulative prices keyed by their block time, in seconds
+pub const OBSERVATIONS: Map<u64, CumulativePrices> = Map::new("observations");
+
+/// Amplification of a stableswap pool, ramped linearly from `initial_amp` at `initial_time` to
+/// `future_amp` at `future_time`. Constant product pools have none.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Amplification {
+    pub initial_amp: u64,
+    /// Block time the ramp started, in seconds
+    pub initial_time: u64,
+    pub future_amp: u64,
+    /// Block time the ramp ends, in seconds
+    pub future_time: u64,
+}
+
+pub const AMPLIFICATION: Item<Amplification> = Item::new("amplification");
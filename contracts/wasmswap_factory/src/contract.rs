@@ -0,0 +1,436 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply};
+use cosmwasm_std::{StdResult, WasmMsg};
+use cw2::set_contract_version;
+use cw20::Denom;
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use s_std::{Response, SubMsg};
+
+use wasmswap::msg::{InstantiateMsg as PoolInstantiateMsg, LpTokenInfo, PoolType};
+
+use crate::msg::{
+    AllPairsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, PairResponse, QueryMsg,
+};
+use crate::state::{normalize, pair_key, Config, Pair, CONFIG, PAIRS, PENDING_PAIR};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:wasmswap_factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_POOL_REPLY_ID: u64 = 1;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if let Some(recipient) = msg
+        .protocol_fee
+        .as_ref()
+        .and_then(|fee| fee.recipient.as_ref())
+    {
+        deps.api.addr_validate(recipient)?;
+    }
+    let config = Config {
+        pool_code_id: msg.pool_code_id,
+        lp_token_code_id: msg.lp_token_code_id,
+        pool_owner: msg
+            .pool_owner
+            .map(|owner| deps.api.addr_validate(&owner))
+            .transpose()?,
+        swap_fee: msg.swap_fee,
+        protocol_fee: msg.protocol_fee,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreatePair {
+            denoms,
+            pool_type,
+            lp_token,
+        } => execute_create_pair(deps, env, info, denoms, pool_type, lp_token),
+    }
+}
+
+/// Anyone can create the pool of a new pair, with the fees of the factory
+pub fn execute_create_pair(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    denoms: [Denom; 2],
+    pool_type: Option<PoolType>,
+    lp_token: Option<LpTokenInfo>,
+) -> Result<Response, ContractError> {
+    for denom in denoms.iter() {
+        _validate_denom(deps.as_ref(), denom)?;
+    }
+    if denoms[0] == denoms[1] {
+        return Err(ContractError::InvalidDenom {
+            reason: "a pair needs two different denoms".to_string(),
+        });
+    }
+    let denoms = normalize(denoms);
+    if let Some(pair) = PAIRS.may_load(deps.storage, pair_key(&denoms))? {
+        return Err(ContractError::PairExists {
+            pool: pair.pool.to_string(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let pool_owner = config.pool_owner.map(|owner| owner.to_string());
+    let (key1, key2) = pair_key(&denoms);
+    let instantiate_pool_msg = WasmMsg::Instantiate {
+        code_id: config.pool_code_id,
+        funds: vec![],
+        admin: pool_owner.clone(),
+        label: format!("wasmswap-{}-{}", key1, key2),
+        msg: to_binary(&PoolInstantiateMsg {
+            token1_denom: denoms[0].clone(),
+            token2_denom: denoms[1].clone(),
+            lp_token_code_id: config.lp_token_code_id,
+            owner: pool_owner,
+            swap_fee: config.swap_fee,
+            protocol_fee: config.protocol_fee,
+            pool_type,
+            lp_token,
+        })?,
+    };
+    // Registered on reply, once the pool address is known
+    PENDING_PAIR.save(deps.storage, &denoms)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_pair")
+        .add_attribute("token1_denom", key1)
+        .add_attribute("token2_denom", key2)
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate_pool_msg,
+            INSTANTIATE_POOL_REPLY_ID,
+        )))
+}
+
+/********************************* REPLY **************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_POOL_REPLY_ID {
+        return Err(ContractError::UnknownReplyId { id: msg.id });
+    }
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|_| ContractError::InstantiatePoolError {})?;
+
+    // Validate contract address
+    let pool = deps.api.addr_validate(&res.contract_address)?;
+    let denoms = PENDING_PAIR.load(deps.storage)?;
+    PENDING_PAIR.remove(deps.storage);
+    PAIRS.save(
+        deps.storage,
+        pair_key(&denoms),
+        &Pair {
+            denoms,
+            pool: pool.clone(),
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("pool", pool.as_str()))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Pair { denoms } => to_binary(&query_pair(deps, denoms)?),
+        QueryMsg::AllPairs { start_after, limit } => {
+            to_binary(&query_all_pairs(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        pool_code_id: config.pool_code_id,
+        lp_token_code_id: config.lp_token_code_id,
+        pool_owner: config.pool_owner.map(|owner| owner.to_string()),
+        swap_fee: config.swap_fee,
+        protocol_fee: config.protocol_fee,
+    })
+}
+
+pub fn query_pair(deps: Deps, denoms: [Denom; 2]) -> StdResult<PairResponse> {
+    let pair = PAIRS.load(deps.storage, pair_key(&normalize(denoms)))?;
+    Ok(_pair_response(pair))
+}
+
+pub fn query_all_pairs(
+    deps: Deps,
+    start_after: Option<[Denom; 2]>,
+    limit: Option<u32>,
+) -> StdResult<AllPairsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|denoms| Bound::exclusive(pair_key(&normalize(denoms))));
+
+    let pairs = PAIRS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, pair)| _pair_response(pair)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllPairsResponse { pairs })
+}
+
+/********************************* HELPERS ************************************/
+
+fn _validate_denom(deps: Deps, denom: &Denom) -> Result<(), ContractError> {
+    match denom {
+        Denom::Native(denom) if denom.is_empty() => Err(ContractError::InvalidDenom {
+            reason: "empty native denom".to_string(),
+        }),
+        Denom::Native(_) => Ok(()),
+        Denom::Cw20(addr) => {
+            deps.api.addr_validate(addr.as_str())?;
+            Ok(())
+        }
+    }
+}
+
+fn _pair_response(pair: Pair) -> PairResponse {
+    PairResponse {
+        denoms: pair.denoms,
+        pool: pair.pool.to_string(),
+    }
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{Addr, Decimal, OwnedDeps, SubMsgResponse, SubMsgResult};
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                pool_code_id: 2,
+                lp_token_code_id: 1,
+                pool_owner: Some("owner".to_string()),
+                swap_fee: Decimal::permille(3),
+                protocol_fee: None,
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    /// Reply of a successful `WasmMsg::Instantiate`, protobuf encoded
+    fn instantiate_reply(id: u64, contract_address: &str) -> Reply {
+        let mut data = vec![0x0a, contract_address.len() as u8];
+        data.extend_from_slice(contract_address.as_bytes());
+        Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data)),
+            }),
+        }
+    }
+
+    fn create_pair(deps: DepsMut, denoms: [Denom; 2]) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::CreatePair {
+            denoms,
+            pool_type: None,
+            lp_token: None,
+        };
+        execute(deps, mock_env(), mock_info("anyone", &[]), msg)
+    }
+
+    fn native(denom: &str) -> Denom {
+        Denom::Native(denom.to_string())
+    }
+
+    fn cw20(addr: &str) -> Denom {
+        Denom::Cw20(Addr::unchecked(addr))
+    }
+
+    #[test]
+    fn test_create_pair() {
+        let mut deps = setup_contract();
+
+        // Error: same denom twice
+        assert!(matches!(
+            create_pair(deps.as_mut(), [native("usign"), native("usign")]),
+            Err(ContractError::InvalidDenom { .. })
+        ));
+
+        // the native denom becomes token1 of the pool
+        let res = create_pair(deps.as_mut(), [cw20("moon"), native("usign")]).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    code_id: 2,
+                    funds: vec![],
+                    admin: Some("owner".to_string()),
+                    label: "wasmswap-native:usign-cw20:moon".to_string(),
+                    msg: to_binary(&PoolInstantiateMsg {
+                        token1_denom: native("usign"),
+                        token2_denom: cw20("moon"),
+                        lp_token_code_id: 1,
+                        owner: Some("owner".to_string()),
+                        swap_fee: Decimal::permille(3),
+                        protocol_fee: None,
+                        pool_type: None,
+                        lp_token: None,
+                    })
+                    .unwrap(),
+                },
+                INSTANTIATE_POOL_REPLY_ID,
+            )]
+        );
+
+        // Error: unknown reply
+        assert!(matches!(
+            reply(deps.as_mut(), mock_env(), instantiate_reply(2, "pool1")),
+            Err(ContractError::UnknownReplyId { id: 2 })
+        ));
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            instantiate_reply(INSTANTIATE_POOL_REPLY_ID, "pool1"),
+        )
+        .unwrap();
+
+        // the pair is found in any order
+        let pair = query_pair(deps.as_ref(), [native("usign"), cw20("moon")]).unwrap();
+        assert_eq!(
+            pair,
+            PairResponse {
+                denoms: [native("usign"), cw20("moon")],
+                pool: "pool1".to_string(),
+            }
+        );
+        assert_eq!(
+            query_pair(deps.as_ref(), [cw20("moon"), native("usign")]).unwrap(),
+            pair
+        );
+
+        // Error: pair exists in either order
+        assert!(matches!(
+            create_pair(deps.as_mut(), [native("usign"), cw20("moon")]),
+            Err(ContractError::PairExists { .. })
+        ));
+
+        // the pool type and lp token metadata are passed to the pool
+        let lp_token = LpTokenInfo {
+            name: Some("Stable LP".to_string()),
+            symbol: None,
+            decimals: None,
+            marketing: None,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::CreatePair {
+                denoms: [native("usign"), native("uusd")],
+                pool_type: Some(PoolType::Stableswap { amp: 100 }),
+                lp_token: Some(lp_token.clone()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    code_id: 2,
+                    funds: vec![],
+                    admin: Some("owner".to_string()),
+                    label: "wasmswap-native:usign-native:uusd".to_string(),
+                    msg: to_binary(&PoolInstantiateMsg {
+                        token1_denom: native("usign"),
+                        token2_denom: native("uusd"),
+                        lp_token_code_id: 1,
+                        owner: Some("owner".to_string()),
+                        swap_fee: Decimal::permille(3),
+                        protocol_fee: None,
+                        pool_type: Some(PoolType::Stableswap { amp: 100 }),
+                        lp_token: Some(lp_token),
+                    })
+                    .unwrap(),
+                },
+                INSTANTIATE_POOL_REPLY_ID,
+            )]
+        );
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let mut deps = setup_contract();
+        let pairs = [
+            ([native("usign"), cw20("moon")], "pool1"),
+            ([native("usign"), cw20("sun")], "pool2"),
+            ([cw20("moon"), cw20("sun")], "pool3"),
+        ];
+        for (denoms, pool) in pairs.iter() {
+            create_pair(deps.as_mut(), denoms.clone()).unwrap();
+            reply(
+                deps.as_mut(),
+                mock_env(),
+                instantiate_reply(INSTANTIATE_POOL_REPLY_ID, pool),
+            )
+            .unwrap();
+        }
+
+        let res = query_all_pairs(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.pairs.len(), 3);
+
+        // paginate one pair at a time
+        let mut start_after = None;
+        let mut pools = vec![];
+        loop {
+            let res = query_all_pairs(deps.as_ref(), start_after, Some(1)).unwrap();
+            match res.pairs.first() {
+                Some(pair) => {
+                    pools.push(pair.pool.clone());
+                    start_after = Some(pair.denoms.clone());
+                }
+                None => break,
+            }
+        }
+        pools.sort();
+        assert_eq!(pools, vec!["pool1", "pool2", "pool3"]);
+    }
+}
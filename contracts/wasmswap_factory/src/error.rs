@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Error instantiating the pool")]
+    InstantiatePoolError {},
+
+    #[error("Invalid denom: {reason}")]
+    InvalidDenom { reason: String },
+
+    #[error("Pair already exists: {pool}")]
+    PairExists { pool: String },
+}
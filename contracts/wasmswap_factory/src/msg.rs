@@ -0,0 +1,64 @@
+use cosmwasm_std::Decimal;
+use cw20::Denom;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use wasmswap::msg::{LpTokenInfo, PoolType, ProtocolFee};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Code id of the wasmswap contract instantiated for every pair
+    pub pool_code_id: u64,
+    /// Code id of the cw20-base contract used as the lp token of the pools
+    pub lp_token_code_id: u64,
+    /// Owner and admin of the pools, can update their fees
+    pub pool_owner: Option<String>,
+    pub swap_fee: Decimal,
+    pub protocol_fee: Option<ProtocolFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiate the pool of a pair not registered yet, a constant product pool with lp token
+    /// metadata derived from the pair by default
+    CreatePair {
+        denoms: [Denom; 2],
+        pool_type: Option<PoolType>,
+        lp_token: Option<LpTokenInfo>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Pool of the pair, in any order of the denoms
+    Pair {
+        denoms: [Denom; 2],
+    },
+    AllPairs {
+        start_after: Option<[Denom; 2]>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub pool_code_id: u64,
+    pub lp_token_code_id: u64,
+    pub pool_owner: Option<String>,
+    pub swap_fee: Decimal,
+    pub protocol_fee: Option<ProtocolFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairResponse {
+    /// Normalized order of the denoms, `token1_denom` and `token2_denom` of the pool
+    pub denoms: [Denom; 2],
+    pub pool: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllPairsResponse {
+    pub pairs: Vec<PairResponse>,
+}
@@ -0,0 +1,59 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw20::Denom;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use wasmswap::msg::ProtocolFee;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub pool_code_id: u64,
+    pub lp_token_code_id: u64,
+    pub pool_owner: Option<Addr>,
+    pub swap_fee: Decimal,
+    pub protocol_fee: Option<ProtocolFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Pair {
+    pub denoms: [Denom; 2],
+    pub pool: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Pairs keyed by `pair_key` of their normalized denoms
+pub const PAIRS: Map<(String, String), Pair> = Map::new("pairs");
+
+/// Denoms of the pool being instantiated, saved on reply
+pub const PENDING_PAIR: Item<[Denom; 2]> = Item::new("pending_pair");
+
+/// Order the denoms of a pair, native denoms first
+pub fn normalize(denoms: [Denom; 2]) -> [Denom; 2] {
+    let [a, b] = denoms;
+    if (denom_order(&b), denom_key(&b)) < (denom_order(&a), denom_key(&a)) {
+        [b, a]
+    } else {
+        [a, b]
+    }
+}
+
+/// Storage key of normalized denoms
+pub fn pair_key(denoms: &[Denom; 2]) -> (String, String) {
+    (denom_key(&denoms[0]), denom_key(&denoms[1]))
+}
+
+fn denom_order(denom: &Denom) -> u8 {
+    match denom {
+        Denom::Native(_) => 0,
+        Denom::Cw20(_) => 1,
+    }
+}
+
+fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(addr) => format!("cw20:{}", addr),
+    }
+}
@@ -0,0 +1,674 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env};
+use cosmwasm_std::{MessageInfo, StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+use cw_utils::one_coin;
+use s_std::{CosmosMsg, Response};
+use wasmswap::msg::{
+    ExecuteMsg as PoolExecuteMsg, InfoResponse as PoolInfoResponse, QueryMsg as PoolQueryMsg,
+    Token1ForToken2PriceResponse, Token2ForToken1PriceResponse, TokenSelect,
+};
+
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, SimulateRouteResponse, SwapOperation,
+};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:wasmswap_router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+/********************************* MESSAGES ***********************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::ExecuteRoute {
+            route,
+            minimum_receive,
+            recipient,
+        } => {
+            let offer = one_coin(&info)?;
+            execute_route(
+                deps,
+                env,
+                info.sender,
+                Denom::Native(offer.denom),
+                route,
+                minimum_receive,
+                recipient,
+            )
+        }
+        ExecuteMsg::ExecuteSwapOperation {
+            operation,
+            recipient,
+        } => execute_swap_operation(deps, env, info, operation, recipient),
+        ExecuteMsg::AssertMinimumReceive {
+            denom,
+            recipient,
+            prev_balance,
+            minimum_receive,
+        } => execute_assert_minimum_receive(
+            deps,
+            env,
+            info,
+            denom,
+            recipient,
+            prev_balance,
+            minimum_receive,
+        ),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&msg.sender)?;
+    match from_binary(&msg.msg)? {
+        ReceiveMsg::ExecuteRoute {
+            route,
+            minimum_receive,
+            recipient,
+        } => execute_route(
+            deps,
+            env,
+            sender,
+            Denom::Cw20(info.sender),
+            route,
+            minimum_receive,
+            recipient,
+        ),
+    }
+}
+
+/// Swap along the route with a call of the router for every swap, as the input of a swap is
+/// only known once the previous one is executed
+pub fn execute_route(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_denom: Denom,
+    route: Vec<SwapOperation>,
+    minimum_receive: Uint128,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    if route.is_empty() {
+        return Err(ContractError::EmptyRoute {});
+    }
+
+    // The output of every swap must be the input of the next one
+    let mut denom = offer_denom;
+    for operation in route.iter() {
+        deps.api.addr_validate(&operation.pool)?;
+        let (input_denom, output_denom) = _operation_denoms(deps.as_ref(), operation)?;
+        if input_denom != denom {
+            return Err(ContractError::InvalidRoute {
+                reason: format!("pool {} does not take {:?}", operation.pool, denom),
+            });
+        }
+        denom = output_denom;
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender,
+    };
+    let prev_balance = _query_balance(deps.as_ref(), &denom, &recipient)?;
+
+    let last = route.len() - 1;
+    let mut msgs = route
+        .into_iter()
+        .enumerate()
+        .map(|(i, operation)| {
+            _execute_msg(
+                &env.contract.address,
+                &ExecuteMsg::ExecuteSwapOperation {
+                    operation,
+                    recipient: (i == last).then(|| recipient.to_string()),
+                },
+            )
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    msgs.push(_execute_msg(
+        &env.contract.address,
+        &ExecuteMsg::AssertMinimumReceive {
+            denom,
+            recipient: recipient.to_string(),
+            prev_balance,
+            minimum_receive,
+        },
+    )?);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_route")
+        .add_attribute("recipient", recipient.as_str())
+        .add_messages(msgs))
+}
+
+pub fn execute_swap_operation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operation: SwapOperation,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (input_denom, _) = _operation_denoms(deps.as_ref(), &operation)?;
+    let input_amount = _query_balance(deps.as_ref(), &input_denom, &env.contract.address)?;
+    // The minimum is only asserted on the output of the route
+    let swap_msg = match recipient {
+        Some(recipient) => PoolExecuteMsg::SwapAndSendTo {
+            input_token: operation.input_token,
+            input_amount,
+            recipient,
+            min_token: Uint128::zero(),
+            expiration: None,
+        },
+        None => PoolExecuteMsg::Swap {
+            input_token: operation.input_token,
+            input_amount,
+            min_output: Uint128::zero(),
+            expiration: None,
+        },
+    };
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let funds = match input_denom {
+        Denom::Native(denom) => coins(input_amount.u128(), denom),
+        Denom::Cw20(addr) => {
+            // wasmswap takes cw20 tokens with `TransferFrom`
+            msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: operation.pool.clone(),
+                        amount: input_amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+            vec![]
+        }
+    };
+    msgs.push(
+        WasmMsg::Execute {
+            contract_addr: operation.pool.clone(),
+            msg: to_binary(&swap_msg)?,
+            funds,
+        }
+        .into(),
+    );
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_swap_operation")
+        .add_attribute("pool", operation.pool)
+        .add_attribute("input_amount", input_amount)
+        .add_messages(msgs))
+}
+
+pub fn execute_assert_minimum_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: Denom,
+    recipient: String,
+    prev_balance: Uint128,
+    minimum_receive: Uint128,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let balance = _query_balance(deps.as_ref(), &denom, &recipient)?;
+    let received = balance.saturating_sub(prev_balance);
+    if received < minimum_receive {
+        return Err(ContractError::MinimumReceiveAssertion {
+            minimum_receive,
+            received,
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "assert_minimum_receive")
+        .add_attribute("received", received))
+}
+
+/********************************* QUERIES ************************************/
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::SimulateRoute {
+            offer_amount,
+            route,
+        } => to_binary(&query_simulate_route(deps, offer_amount, route)?),
+    }
+}
+
+pub fn query_simulate_route(
+    deps: Deps,
+    offer_amount: Uint128,
+    route: Vec<SwapOperation>,
+) -> StdResult<SimulateRouteResponse> {
+    let mut amount = offer_amount;
+    for operation in route {
+        amount = match operation.input_token {
+            TokenSelect::Token1 => {
+                let res: Token1ForToken2PriceResponse = deps.querier.query_wasm_smart(
+                    operation.pool,
+                    &PoolQueryMsg::Token1ForToken2Price {
+                        token1_amount: amount,
+                    },
+                )?;
+                res.token2_amount
+            }
+            TokenSelect::Token2 => {
+                let res: Token2ForToken1PriceResponse = deps.querier.query_wasm_smart(
+                    operation.pool,
+                    &PoolQueryMsg::Token2ForToken1Price {
+                        token2_amount: amount,
+                    },
+                )?;
+                res.token1_amount
+            }
+        };
+    }
+    Ok(SimulateRouteResponse { amount })
+}
+
+/********************************* HELPERS ************************************/
+
+/// Input and output denoms of the swap
+fn _operation_denoms(deps: Deps, operation: &SwapOperation) -> StdResult<(Denom, Denom)> {
+    let info: PoolInfoResponse = deps
+        .querier
+        .query_wasm_smart(&operation.pool, &PoolQueryMsg::Info {})?;
+    Ok(match operation.input_token {
+        TokenSelect::Token1 => (info.token1_denom, info.token2_denom),
+        TokenSelect::Token2 => (info.token2_denom, info.token1_denom),
+    })
+}
+
+fn _query_balance(deps: Deps, denom: &Denom, address: &Addr) -> StdResult<Uint128> {
+    match denom {
+        Denom::Native(denom) => Ok(deps.querier.query_balance(address, denom)?.amount),
+        Denom::Cw20(addr) => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                addr,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+    }
+}
+
+fn _execute_msg(router: &Addr, msg: &ExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: router.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/********************************* TESTS ************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{
+        ContractResult, Decimal, OwnedDeps, QuerierResult, SubMsg, SystemError, SystemResult,
+        WasmQuery,
+    };
+    use cw_utils::PaymentError;
+
+    const MOON: &str = "moon";
+    const SUN: &str = "sun";
+
+    /// pool1 is usign/moon and pool2 moon/sun, both at a 2:1 price
+    fn mock_wasm(query: &WasmQuery) -> QuerierResult {
+        let res = match query {
+            WasmQuery::Smart { contract_addr, msg } => match contract_addr.as_str() {
+                "pool1" | "pool2" => {
+                    let (token1_denom, token2_denom) = if contract_addr == "pool1" {
+                        (Denom::Native("usign".to_string()), cw20(MOON))
+                    } else {
+                        (cw20(MOON), cw20(SUN))
+                    };
+                    match from_binary(msg).unwrap() {
+                        PoolQueryMsg::Info {} => to_binary(&PoolInfoResponse {
+                            token1_reserve: Uint128::new(1_000),
+                            token1_denom,
+                            token2_reserve: Uint128::new(1_000),
+                            token2_denom,
+                            lp_token_supply: Uint128::new(1_000),
+                            lp_token_address: "lp_token".to_string(),
+                            owner: None,
+                            swap_fee: Decimal::permille(3),
+                            protocol_fee: None,
+                            amp: None,
+                        }),
+                        PoolQueryMsg::Token1ForToken2Price { token1_amount } => {
+                            to_binary(&Token1ForToken2PriceResponse {
+                                token2_amount: token1_amount / Uint128::new(2),
+                            })
+                        }
+                        PoolQueryMsg::Token2ForToken1Price { token2_amount } => {
+                            to_binary(&Token2ForToken1PriceResponse {
+                                token1_amount: token2_amount * Uint128::new(2),
+                            })
+                        }
+                        _ => {
+                            return SystemResult::Err(SystemError::UnsupportedRequest {
+                                kind: "pool query".to_string(),
+                            })
+                        }
+                    }
+                }
+                MOON | SUN => to_binary(&BalanceResponse {
+                    balance: Uint128::new(100),
+                }),
+                _ => {
+                    return SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.to_string(),
+                    })
+                }
+            },
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    }
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock_wasm);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        deps
+    }
+
+    fn cw20(addr: &str) -> Denom {
+        Denom::Cw20(Addr::unchecked(addr))
+    }
+
+    fn operation(pool: &str, input_token: TokenSelect) -> SwapOperation {
+        SwapOperation {
+            pool: pool.to_string(),
+            input_token,
+        }
+    }
+
+    fn router_msg(msg: &ExecuteMsg) -> SubMsg<s_std::SignMsgWrapper> {
+        SubMsg::new(_execute_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR), msg).unwrap())
+    }
+
+    #[test]
+    fn test_execute_route() {
+        let mut deps = setup_contract();
+        let route = vec![
+            operation("pool1", TokenSelect::Token1),
+            operation("pool2", TokenSelect::Token1),
+        ];
+        let msg = ExecuteMsg::ExecuteRoute {
+            route: route.clone(),
+            minimum_receive: Uint128::new(10),
+            recipient: None,
+        };
+
+        // Error: no input
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(
+            err,
+            Err(ContractError::Payment(PaymentError::NoFunds {}))
+        ));
+
+        // Error: first pool does not take the input
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(40, "uatom")),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::InvalidRoute { .. })));
+
+        // Error: second pool does not take the output of the first one
+        let invalid_msg = ExecuteMsg::ExecuteRoute {
+            route: vec![
+                operation("pool1", TokenSelect::Token1),
+                operation("pool2", TokenSelect::Token2),
+            ],
+            minimum_receive: Uint128::new(10),
+            recipient: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(40, "usign")),
+            invalid_msg,
+        );
+        assert!(matches!(err, Err(ContractError::InvalidRoute { .. })));
+
+        // the last swap sends the output to the sender, then the output is checked
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(40, "usign")),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                router_msg(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: route[0].clone(),
+                    recipient: None,
+                }),
+                router_msg(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: route[1].clone(),
+                    recipient: Some("alice".to_string()),
+                }),
+                router_msg(&ExecuteMsg::AssertMinimumReceive {
+                    denom: cw20(SUN),
+                    recipient: "alice".to_string(),
+                    prev_balance: Uint128::new(100),
+                    minimum_receive: Uint128::new(10),
+                }),
+            ]
+        );
+
+        // cw20 input sent to the router
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "alice".to_string(),
+            amount: Uint128::new(40),
+            msg: to_binary(&ReceiveMsg::ExecuteRoute {
+                route: vec![operation("pool1", TokenSelect::Token2)],
+                minimum_receive: Uint128::new(10),
+                recipient: Some("bob".to_string()),
+            })
+            .unwrap(),
+        });
+        let err = execute(deps.as_mut(), mock_env(), mock_info(SUN, &[]), msg.clone());
+        assert!(matches!(err, Err(ContractError::InvalidRoute { .. })));
+        let res = execute(deps.as_mut(), mock_env(), mock_info(MOON, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0],
+            router_msg(&ExecuteMsg::ExecuteSwapOperation {
+                operation: operation("pool1", TokenSelect::Token2),
+                recipient: Some("bob".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_execute_swap_operation() {
+        let mut deps = setup_contract();
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(40, "usign"));
+        let msg = ExecuteMsg::ExecuteSwapOperation {
+            operation: operation("pool2", TokenSelect::Token1),
+            recipient: None,
+        };
+
+        // Error: only the router swaps its balance
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+
+        // cw20 input is allowed to the pool
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: MOON.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: "pool2".to_string(),
+                        amount: Uint128::new(100),
+                        expires: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: "pool2".to_string(),
+                    msg: to_binary(&PoolExecuteMsg::Swap {
+                        input_token: TokenSelect::Token1,
+                        input_amount: Uint128::new(100),
+                        min_output: Uint128::zero(),
+                        expiration: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+
+        // native input is sent with the swap of the last pool
+        let msg = ExecuteMsg::ExecuteSwapOperation {
+            operation: operation("pool1", TokenSelect::Token1),
+            recipient: Some("alice".to_string()),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: "pool1".to_string(),
+                msg: to_binary(&PoolExecuteMsg::SwapAndSendTo {
+                    input_token: TokenSelect::Token1,
+                    input_amount: Uint128::new(40),
+                    recipient: "alice".to_string(),
+                    min_token: Uint128::zero(),
+                    expiration: None,
+                })
+                .unwrap(),
+                funds: coins(40, "usign"),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_assert_minimum_receive() {
+        let mut deps = setup_contract();
+        deps.querier.update_balance("alice", coins(50, "usign"));
+        let msg = |minimum_receive| ExecuteMsg::AssertMinimumReceive {
+            denom: Denom::Native("usign".to_string()),
+            recipient: "alice".to_string(),
+            prev_balance: Uint128::new(30),
+            minimum_receive: Uint128::new(minimum_receive),
+        };
+        let router = mock_info(MOCK_CONTRACT_ADDR, &[]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg(20));
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+
+        let err = execute(deps.as_mut(), mock_env(), router.clone(), msg(21));
+        assert!(matches!(
+            err,
+            Err(ContractError::MinimumReceiveAssertion { .. })
+        ));
+        execute(deps.as_mut(), mock_env(), router, msg(20)).unwrap();
+    }
+
+    #[test]
+    fn test_simulate_route() {
+        let deps = setup_contract();
+        let route = vec![
+            operation("pool1", TokenSelect::Token1),
+            operation("pool2", TokenSelect::Token1),
+        ];
+        let res = query_simulate_route(deps.as_ref(), Uint128::new(40), route).unwrap();
+        assert_eq!(res.amount, Uint128::new(10));
+
+        let route = vec![
+            operation("pool2", TokenSelect::Token2),
+            operation("pool1", TokenSelect::Token2),
+        ];
+        let res = query_simulate_route(deps.as_ref(), Uint128::new(10), route).unwrap();
+        assert_eq!(res.amount, Uint128::new(40));
+    }
+}
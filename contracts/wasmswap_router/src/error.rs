@@ -0,0 +1,29 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Route must have at least one swap")]
+    EmptyRoute {},
+
+    #[error("Invalid route: {reason}")]
+    InvalidRoute { reason: String },
+
+    #[error(
+        "Received less than the minimum: minimum_receive: {minimum_receive}, received: {received}"
+    )]
+    MinimumReceiveAssertion {
+        minimum_receive: Uint128,
+        received: Uint128,
+    },
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
@@ -0,0 +1,67 @@
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Denom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use wasmswap::msg::TokenSelect;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// Swap of `input_token` for the other token of the wasmswap `pool`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapOperation {
+    pub pool: String,
+    pub input_token: TokenSelect,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// cw20 input sent with `Send`
+    Receive(Cw20ReceiveMsg),
+    /// Swap the native funds sent along the route. The output of every swap is the input of
+    /// the next one, the output of the last swap is sent to `recipient`, the sender by default.
+    ExecuteRoute {
+        route: Vec<SwapOperation>,
+        minimum_receive: Uint128,
+        recipient: Option<String>,
+    },
+    /// Swap the whole balance of the router, can only be called by the router
+    ExecuteSwapOperation {
+        operation: SwapOperation,
+        /// Recipient of the output, the router when None
+        recipient: Option<String>,
+    },
+    /// Check the output received by the recipient, can only be called by the router
+    AssertMinimumReceive {
+        denom: Denom,
+        recipient: String,
+        prev_balance: Uint128,
+        minimum_receive: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    ExecuteRoute {
+        route: Vec<SwapOperation>,
+        minimum_receive: Uint128,
+        recipient: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Output of the route for `offer_amount` of the first input token
+    SimulateRoute {
+        offer_amount: Uint128,
+        route: Vec<SwapOperation>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateRouteResponse {
+    pub amount: Uint128,
+}
@@ -0,0 +1,196 @@
+use cosmwasm_std::{coins, to_binary, Addr, QuerierWrapper, StdResult, Uint128, WasmMsg};
+use cw1155::TokenId;
+use s1::ROYALTY_FEE_SCHEDULE;
+use s1155::msg::{ConfigResponse, ExecuteMsg as S1155ExecuteMsg, QueryMsg as S1155QueryMsg};
+use s721::msg::{CollectionInfoResponse, ExecuteMsg as S721ExecuteMsg, QueryMsg as S721QueryMsg};
+use s_std::{error::FeeError, SubMsg, NATIVE_DENOM};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing;
+
+/// Sign NFT held by a contract, received through `SendNft`, `SendFrom` or `BatchSendFrom`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Nft {
+    S721 {
+        contract: Addr,
+        token_id: String,
+    },
+    S1155 {
+        contract: Addr,
+        batch: Vec<(TokenId, Uint128)>,
+    },
+}
+
+impl Nft {
+    pub fn contract(&self) -> &Addr {
+        match self {
+            Nft::S721 { contract, .. } | Nft::S1155 { contract, .. } => contract,
+        }
+    }
+
+    /// Royalty fee charged by the nft contract to transfer the token
+    pub fn transfer_fee(&self) -> Result<u128, FeeError> {
+        match self {
+            Nft::S721 { .. } => ROYALTY_FEE_SCHEDULE.fee(1),
+            Nft::S1155 { batch, .. } => ROYALTY_FEE_SCHEDULE.fee(batch.len()),
+        }
+    }
+
+    /// Transfer the token held by `owner` to `recipient`, paying `fee` to the nft contract
+    pub fn transfer_msg(&self, owner: &Addr, recipient: &str, fee: u128) -> StdResult<SubMsg> {
+        let msg = match self {
+            Nft::S721 { token_id, .. } => to_binary(&S721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.clone(),
+            })?,
+            Nft::S1155 { batch, .. } => match batch.as_slice() {
+                [(token_id, value)] => to_binary(&S1155ExecuteMsg::SendFrom {
+                    from: owner.to_string(),
+                    to: recipient.to_string(),
+                    token_id: token_id.clone(),
+                    value: *value,
+                    msg: None,
+                })?,
+                _ => to_binary(&S1155ExecuteMsg::BatchSendFrom {
+                    from: owner.to_string(),
+                    to: recipient.to_string(),
+                    batch: batch.clone(),
+                    msg: None,
+                })?,
+            },
+        };
+        Ok(SubMsg::new(WasmMsg::Execute {
+            contract_addr: self.contract().to_string(),
+            msg,
+            funds: coins(fee, NATIVE_DENOM),
+        }))
+    }
+
+    /// Royalty address of the nft contract
+    pub fn royalty_address(&self, querier: &QuerierWrapper) -> StdResult<Addr> {
+        let royalty_address = match self {
+            Nft::S721 { contract, .. } => {
+                let res: CollectionInfoResponse =
+                    querier.query_wasm_smart(contract, &S721QueryMsg::CollectionInfo {})?;
+                res.royalty_address
+            }
+            Nft::S1155 { contract, .. } => {
+                let res: ConfigResponse =
+                    querier.query_wasm_smart(contract, &S1155QueryMsg::Config {})?;
+                res.royalty_address
+            }
+        };
+        // Validated by the nft contract
+        Ok(Addr::unchecked(royalty_address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{
+        coins, to_binary, Addr, Binary, QuerierWrapper, StdResult, Uint128, WasmMsg,
+    };
+    use s1::ROYALTY_FEE;
+    use s1155::msg::ExecuteMsg as S1155ExecuteMsg;
+    use s721::msg::{ExecuteMsg as S721ExecuteMsg, QueryMsg as S721QueryMsg};
+    use s_std::{SubMsg, NATIVE_DENOM};
+
+    use crate::testing::mock_nft_querier;
+    use crate::Nft;
+
+    #[test]
+    fn test_royalty_address() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock_nft_querier("royalty"));
+        let querier = QuerierWrapper::new(&deps.querier);
+
+        let nft = Nft::S721 {
+            contract: Addr::unchecked("s721"),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(nft.royalty_address(&querier).unwrap(), "royalty");
+        let nft = Nft::S1155 {
+            contract: Addr::unchecked("s1155"),
+            batch: vec![("token".to_string(), Uint128::new(1))],
+        };
+        assert_eq!(nft.royalty_address(&querier).unwrap(), "royalty");
+
+        // Error: other queries are not mocked
+        let res: StdResult<Binary> = querier.query_wasm_smart("s721", &S721QueryMsg::NumTokens {});
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_transfer_msg() {
+        let owner = Addr::unchecked("market");
+
+        let nft = Nft::S721 {
+            contract: Addr::unchecked("s721"),
+            token_id: "token".to_string(),
+        };
+        assert_eq!(nft.transfer_fee(), Ok(ROYALTY_FEE));
+        assert_eq!(
+            nft.transfer_msg(&owner, "buyer", ROYALTY_FEE).unwrap(),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "s721".to_string(),
+                msg: to_binary(&S721ExecuteMsg::TransferNft {
+                    recipient: "buyer".to_string(),
+                    token_id: "token".to_string(),
+                })
+                .unwrap(),
+                funds: coins(ROYALTY_FEE, NATIVE_DENOM),
+            })
+        );
+
+        // single s1155 token is sent with `SendFrom`
+        let nft = Nft::S1155 {
+            contract: Addr::unchecked("s1155"),
+            batch: vec![("token".to_string(), Uint128::new(2))],
+        };
+        assert_eq!(nft.transfer_fee(), Ok(ROYALTY_FEE));
+        assert_eq!(
+            nft.transfer_msg(&owner, "buyer", ROYALTY_FEE).unwrap(),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "s1155".to_string(),
+                msg: to_binary(&S1155ExecuteMsg::SendFrom {
+                    from: "market".to_string(),
+                    to: "buyer".to_string(),
+                    token_id: "token".to_string(),
+                    value: Uint128::new(2),
+                    msg: None,
+                })
+                .unwrap(),
+                funds: coins(ROYALTY_FEE, NATIVE_DENOM),
+            })
+        );
+
+        // multiple s1155 tokens are sent with `BatchSendFrom`
+        let batch = vec![
+            ("token1".to_string(), Uint128::new(1)),
+            ("token2".to_string(), Uint128::new(3)),
+        ];
+        let nft = Nft::S1155 {
+            contract: Addr::unchecked("s1155"),
+            batch: batch.clone(),
+        };
+        assert_eq!(nft.transfer_fee(), Ok(ROYALTY_FEE * 2));
+        assert_eq!(
+            nft.transfer_msg(&owner, "buyer", ROYALTY_FEE * 2).unwrap(),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "s1155".to_string(),
+                msg: to_binary(&S1155ExecuteMsg::BatchSendFrom {
+                    from: "market".to_string(),
+                    to: "buyer".to_string(),
+                    batch,
+                    msg: None,
+                })
+                .unwrap(),
+                funds: coins(ROYALTY_FEE * 2, NATIVE_DENOM),
+            })
+        );
+    }
+}
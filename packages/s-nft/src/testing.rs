@@ -0,0 +1,53 @@
+use cosmwasm_std::{from_slice, to_binary, Binary, Coin, QuerierResult};
+use cosmwasm_std::{StdResult, SystemError, SystemResult, WasmQuery};
+use s1::ROYALTY_FEE;
+use s1155::msg::{ConfigResponse, QueryMsg as S1155QueryMsg};
+use s721::msg::{CollectionInfoResponse, QueryMsg as S721QueryMsg};
+use s_std::NATIVE_DENOM;
+
+/// Wasm querier of nft contracts with `royalty` as creator and royalty address, to be set
+/// with `MockQuerier::update_wasm`. Any contract answers the s721 `CollectionInfo` and s1155
+/// `Config` queries, other queries are unsupported.
+pub fn mock_nft_querier(royalty: &str) -> impl Fn(&WasmQuery) -> QuerierResult {
+    let royalty = royalty.to_string();
+    move |query| match query {
+        WasmQuery::Smart { msg, .. } => match _nft_query(msg, &royalty) {
+            Some(res) => SystemResult::Ok(res.into()),
+            None => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: format!("nft query: {}", String::from_utf8_lossy(msg)),
+            }),
+        },
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "nft raw query".to_string(),
+        }),
+    }
+}
+
+fn _nft_query(msg: &Binary, royalty: &str) -> Option<StdResult<Binary>> {
+    if let Ok(S721QueryMsg::CollectionInfo {}) = from_slice(msg) {
+        return Some(to_binary(&CollectionInfoResponse {
+            creator: royalty.to_string(),
+            description: String::from("Document"),
+            image: "https://example.com/image.png".to_string(),
+            external_link: None,
+            royalty_address: royalty.to_string(),
+            base_uri: None,
+            multisig: String::from("multisig"),
+            min_fee: Coin::new(0, NATIVE_DENOM),
+            royalty_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
+            royalty_share: 95,
+        }));
+    }
+    if let Ok(S1155QueryMsg::Config {}) = from_slice(msg) {
+        return Some(to_binary(&ConfigResponse {
+            minter: royalty.to_string(),
+            royalty_address: royalty.to_string(),
+            base_uri: None,
+            multisig: String::from("multisig"),
+            min_fee: Coin::new(0, NATIVE_DENOM),
+            royalty_fee: Coin::new(ROYALTY_FEE, NATIVE_DENOM),
+            royalty_share: 95,
+        }));
+    }
+    None
+}
@@ -1,3 +1,4 @@
+use cosmwasm_std::OverflowError;
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -8,4 +9,7 @@ pub enum FeeError {
 
     #[error("{0}")]
     Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
 }
@@ -5,6 +5,8 @@ mod route;
 
 pub const NATIVE_DENOM: &str = "usign";
 pub const MULTISIG: &str = "sign1nfvgxep88xrqza3534e92tlpnvvxctf4laa3kd";
+/// Percent of the primary sale proceeds sent to the multisig
+pub const MULTISIG_SHARE: u64 = 10;
 
 pub use msg::{create_fund_community_pool_msg, SignMsg, SignMsgWrapper};
 
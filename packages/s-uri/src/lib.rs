@@ -0,0 +1,160 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::{ParseError, Url};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum UriError {
+    #[error("Invalid uri: {0}")]
+    Invalid(#[from] ParseError),
+
+    #[error("Uri longer than {max} bytes")]
+    TooLong { max: u32 },
+
+    #[error("Uri scheme not allowed: {scheme}")]
+    SchemeNotAllowed { scheme: String },
+
+    #[error("Uri host not allowed: {host}")]
+    HostNotAllowed { host: String },
+}
+
+/// Token uri validation policy of a collection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UriPolicy {
+    /// Allowed uri schemes, such as `ipfs`, `ar` or `https`
+    pub schemes: Vec<String>,
+    /// Maximum uri length in bytes
+    pub max_length: Option<u32>,
+    /// Allowed hosts of `http` and `https` uris, any host when None
+    pub hosts: Option<Vec<String>>,
+}
+
+impl Default for UriPolicy {
+    fn default() -> Self {
+        UriPolicy {
+            schemes: vec!["ipfs".to_string(), "ar".to_string(), "https".to_string()],
+            max_length: None,
+            hosts: None,
+        }
+    }
+}
+
+impl UriPolicy {
+    /// Return an error if the uri is not allowed by the policy
+    pub fn validate(&self, uri: &str) -> Result<(), UriError> {
+        if let Some(max) = self.max_length {
+            if uri.len() > max as usize {
+                return Err(UriError::TooLong { max });
+            }
+        }
+
+        let url = match Url::parse(uri) {
+            Ok(url) => url,
+            // Some tools emit bare CIDs without the `ipfs://` scheme
+            Err(ParseError::RelativeUrlWithoutBase) if _is_cid(uri) => {
+                Url::parse(&format!("ipfs://{}", uri))?
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let scheme = url.scheme();
+        if !self.schemes.iter().any(|allowed| allowed == scheme) {
+            return Err(UriError::SchemeNotAllowed {
+                scheme: scheme.to_string(),
+            });
+        }
+
+        if let (Some(hosts), "http" | "https") = (&self.hosts, scheme) {
+            let host = url.host_str().unwrap_or_default();
+            if !hosts.iter().any(|allowed| allowed == host) {
+                return Err(UriError::HostNotAllowed {
+                    host: host.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the first path segment is a base58 CIDv0 or a base32 CIDv1
+fn _is_cid(uri: &str) -> bool {
+    let cid = uri.split('/').next().unwrap_or_default();
+    let v0 =
+        cid.len() == 46 && cid.starts_with("Qm") && cid.chars().all(|c| c.is_ascii_alphanumeric());
+    let v1 = cid.len() >= 59
+        && cid.starts_with('b')
+        && cid
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c));
+    v0 || v1
+}
+
+#[cfg(test)]
+mod tests {
+    use url::ParseError;
+
+    use crate::{UriError, UriPolicy};
+
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    const CID_V1: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    #[test]
+    fn test_default_policy() {
+        let policy = UriPolicy::default();
+        policy.validate("https://example.com/1.json").unwrap();
+        policy
+            .validate(&format!("ipfs://{}/1.json", CID_V1))
+            .unwrap();
+        policy
+            .validate("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U")
+            .unwrap();
+
+        // bare CIDs are ipfs uris
+        policy.validate(CID_V0).unwrap();
+        policy.validate(&format!("{}/1.json", CID_V1)).unwrap();
+
+        assert_eq!(
+            policy.validate("http://example.com/1.json"),
+            Err(UriError::SchemeNotAllowed {
+                scheme: "http".to_string()
+            })
+        );
+        assert_eq!(
+            policy.validate("not a uri"),
+            Err(UriError::Invalid(ParseError::RelativeUrlWithoutBase))
+        );
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let policy = UriPolicy {
+            schemes: vec!["https".to_string(), "ipfs".to_string()],
+            max_length: Some(80),
+            hosts: Some(vec!["gateway.sign.net".to_string()]),
+        };
+        policy
+            .validate("https://gateway.sign.net/ipfs/QmHash/1.json")
+            .unwrap();
+        // host allow list only applies to web uris
+        policy
+            .validate(&format!("ipfs://{}/1.json", CID_V1))
+            .unwrap();
+
+        assert_eq!(
+            policy.validate("https://example.com/1.json"),
+            Err(UriError::HostNotAllowed {
+                host: "example.com".to_string()
+            })
+        );
+        assert_eq!(
+            policy.validate(&format!("https://gateway.sign.net/{}", "a".repeat(80))),
+            Err(UriError::TooLong { max: 80 })
+        );
+        assert_eq!(
+            policy.validate("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"),
+            Err(UriError::SchemeNotAllowed {
+                scheme: "ar".to_string()
+            })
+        );
+    }
+}
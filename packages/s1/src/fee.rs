@@ -0,0 +1,129 @@
+use cosmwasm_std::Uint128;
+use s_std::error::FeeError;
+
+/// Per item fee of the items from the `min_items`th up to the next tier
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeTier {
+    pub min_items: u64,
+    pub per_item: u128,
+}
+
+/// Fee charged for an operation over a number of items
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeeSchedule {
+    /// Same fee regardless of the number of items
+    Flat(u128),
+    /// Same fee for every item
+    PerItem(u128),
+    /// Per item fee of the tier of every item, like tax brackets, so adding items never lowers
+    /// the total. Tiers must be sorted by `min_items`, items below the first tier are free.
+    Tiered(&'static [FeeTier]),
+    /// Per item fee, total will not exceed `max`
+    Capped { per_item: u128, max: u128 },
+}
+
+impl FeeSchedule {
+    /// Total fee for `items`, return an error if the fee overflows
+    pub fn fee(&self, items: usize) -> Result<u128, FeeError> {
+        let count = Uint128::from(items as u128);
+        match *self {
+            FeeSchedule::Flat(fee) => Ok(fee),
+            FeeSchedule::PerItem(per_item) => Ok(count.checked_mul(per_item.into())?.u128()),
+            FeeSchedule::Tiered(tiers) => {
+                let mut fee = Uint128::zero();
+                for (i, tier) in tiers.iter().enumerate() {
+                    // Items numbered from `min_items`, the first item is 1, to the next tier
+                    let start = u128::from(tier.min_items.max(1));
+                    let end = tiers
+                        .get(i + 1)
+                        .map_or(u128::MAX, |next| next.min_items.into())
+                        .min(count.u128() + 1);
+                    let tier_items = end.saturating_sub(start);
+                    fee = fee.checked_add(
+                        Uint128::from(tier_items).checked_mul(tier.per_item.into())?,
+                    )?;
+                }
+                Ok(fee.u128())
+            }
+            FeeSchedule::Capped { per_item, max } => Ok(count
+                .checked_mul(per_item.into())
+                .map_or(max, |fee| fee.u128().min(max))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{OverflowError, OverflowOperation};
+
+    use crate::{FeeError, FeeSchedule, FeeTier};
+
+    const TIERS: &[FeeTier] = &[
+        FeeTier {
+            min_items: 1,
+            per_item: 1000,
+        },
+        FeeTier {
+            min_items: 10,
+            per_item: 900,
+        },
+    ];
+
+    #[test]
+    fn test_flat_fee() {
+        let schedule = FeeSchedule::Flat(1000);
+        assert_eq!(schedule.fee(1), Ok(1000));
+        assert_eq!(schedule.fee(20), Ok(1000));
+    }
+
+    #[test]
+    fn test_per_item_fee() {
+        let schedule = FeeSchedule::PerItem(1000);
+        assert_eq!(schedule.fee(0), Ok(0));
+        assert_eq!(schedule.fee(3), Ok(3000));
+
+        // Overflow returns an error instead of panicking
+        let schedule = FeeSchedule::PerItem(u128::MAX);
+        assert_eq!(
+            schedule.fee(2),
+            Err(FeeError::Overflow(OverflowError::new(
+                OverflowOperation::Mul,
+                2u128,
+                u128::MAX
+            )))
+        );
+    }
+
+    #[test]
+    fn test_tiered_fee() {
+        let schedule = FeeSchedule::Tiered(TIERS);
+        assert_eq!(schedule.fee(0), Ok(0));
+        assert_eq!(schedule.fee(9), Ok(9000));
+        // bulk discount on the items from the 10th onwards
+        assert_eq!(schedule.fee(10), Ok(9900));
+        assert_eq!(schedule.fee(20), Ok(18900));
+
+        // adding items never lowers the fee
+        for items in 0..100 {
+            assert!(schedule.fee(items).unwrap() <= schedule.fee(items + 1).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_capped_fee() {
+        let schedule = FeeSchedule::Capped {
+            per_item: 1000,
+            max: 5000,
+        };
+        assert_eq!(schedule.fee(2), Ok(2000));
+        assert_eq!(schedule.fee(5), Ok(5000));
+        assert_eq!(schedule.fee(100), Ok(5000));
+
+        // Overflow is always above the cap
+        let schedule = FeeSchedule::Capped {
+            per_item: u128::MAX,
+            max: 5000,
+        };
+        assert_eq!(schedule.fee(2), Ok(5000));
+    }
+}
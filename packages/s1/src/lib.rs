@@ -2,9 +2,28 @@ use cosmwasm_std::{coins, Addr, BankMsg, Decimal, MessageInfo, Uint128};
 use cw_utils::must_pay;
 use s_std::{create_fund_community_pool_msg, error::FeeError, SubMsg, NATIVE_DENOM};
 
+mod fee;
+
+pub use fee::{FeeSchedule, FeeTier};
+
 // governance parameters
 pub const OWNER_PERCENT: u64 = 95;
 pub const ROYALTY_FEE: u128 = 1000; // 0.001SIGN
+pub const ROYALTY_FEE_SCHEDULE: FeeSchedule = FeeSchedule::Tiered(&[
+    FeeTier {
+        min_items: 1,
+        per_item: ROYALTY_FEE,
+    },
+    FeeTier {
+        min_items: 10,
+        per_item: 900, // 10% discount from the 10th item
+    },
+    FeeTier {
+        min_items: 50,
+        per_item: 800, // 20% discount from the 50th item
+    },
+]);
+pub const SALE_ROYALTY_PERCENT: u64 = 5; // 5% of sale price
 
 /// Royalty payment and distribute fees, return an error if the fee is not enough
 pub fn check_royalty_payment(
@@ -26,16 +45,38 @@ pub fn royalty_payment(fee: u128, owner: Addr) -> Vec<SubMsg> {
     let mut msgs: Vec<SubMsg> = vec![];
     let owner_fee = (Uint128::from(fee) * Decimal::percent(OWNER_PERCENT)).u128();
 
-    msgs.push(SubMsg::new(BankMsg::Send {
-        to_address: owner.to_string(),
-        amount: coins(owner_fee, NATIVE_DENOM),
-    }));
+    // empty transfers are rejected by the bank module
+    if owner_fee > 0 {
+        msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(owner_fee, NATIVE_DENOM),
+        }));
+    }
 
     let dist_amount = fee - owner_fee;
-    msgs.push(SubMsg::new(create_fund_community_pool_msg(coins(
-        dist_amount,
-        NATIVE_DENOM,
-    ))));
+    if dist_amount > 0 {
+        msgs.push(SubMsg::new(create_fund_community_pool_msg(coins(
+            dist_amount,
+            NATIVE_DENOM,
+        ))));
+    }
+
+    msgs
+}
+
+/// Sale payment, royalty on the price is distributed with `royalty_payment`
+/// and the remainder is paid to the seller
+pub fn sale_payment(price: u128, royalty_owner: Addr, seller: Addr) -> Vec<SubMsg> {
+    let royalty = (Uint128::from(price) * Decimal::percent(SALE_ROYALTY_PERCENT)).u128();
+    let mut msgs = royalty_payment(royalty, royalty_owner);
+
+    let proceeds = price - royalty;
+    if proceeds > 0 {
+        msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: seller.to_string(),
+            amount: coins(proceeds, NATIVE_DENOM),
+        }));
+    }
 
     msgs
 }
@@ -45,7 +86,10 @@ mod tests {
     use cosmwasm_std::{coins, Addr, BankMsg, MessageInfo};
     use s_std::{create_fund_community_pool_msg, NATIVE_DENOM};
 
-    use crate::{check_royalty_payment, royalty_payment, FeeError, SubMsg, ROYALTY_FEE};
+    use crate::{
+        check_royalty_payment, royalty_payment, sale_payment, FeeError, SubMsg, ROYALTY_FEE,
+        ROYALTY_FEE_SCHEDULE,
+    };
 
     #[test]
     fn test_check_royalty_payment() {
@@ -99,4 +143,44 @@ mod tests {
         assert_eq!(res[0], bank_msg);
         assert_eq!(res[1], community_msg)
     }
+
+    #[test]
+    fn test_sale_payment() {
+        let res = sale_payment(100_000, Addr::unchecked("owner"), Addr::unchecked("seller"));
+        let bank_msg = SubMsg::new(BankMsg::Send {
+            to_address: "owner".to_string(),
+            amount: coins(4750, NATIVE_DENOM.to_string()),
+        });
+        let community_msg = SubMsg::new(create_fund_community_pool_msg(coins(250, NATIVE_DENOM)));
+        let seller_msg = SubMsg::new(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(95_000, NATIVE_DENOM.to_string()),
+        });
+        assert_eq!(res, vec![bank_msg, community_msg, seller_msg]);
+
+        // royalty rounds down to zero, no empty transfers
+        let res = sale_payment(10, Addr::unchecked("owner"), Addr::unchecked("seller"));
+        let seller_msg = SubMsg::new(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(10, NATIVE_DENOM.to_string()),
+        });
+        assert_eq!(res, vec![seller_msg]);
+    }
+
+    #[test]
+    fn test_royalty_fee_schedule() {
+        assert_eq!(ROYALTY_FEE_SCHEDULE.fee(1), Ok(ROYALTY_FEE));
+        assert_eq!(ROYALTY_FEE_SCHEDULE.fee(2), Ok(ROYALTY_FEE * 2));
+        assert_eq!(ROYALTY_FEE_SCHEDULE.fee(10), Ok(9900));
+        assert_eq!(ROYALTY_FEE_SCHEDULE.fee(49), Ok(45000));
+        assert_eq!(ROYALTY_FEE_SCHEDULE.fee(50), Ok(45800));
+
+        // adding items never lowers the fee
+        for items in 0..200 {
+            assert!(
+                ROYALTY_FEE_SCHEDULE.fee(items).unwrap()
+                    <= ROYALTY_FEE_SCHEDULE.fee(items + 1).unwrap()
+            );
+        }
+    }
 }